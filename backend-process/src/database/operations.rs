@@ -3,8 +3,15 @@ use crate::database::models::{ImageMapping, InputMapping};
 use crate::database::sqlite::SqLite;
 use firmware_api::display_zones::DisplayZones;
 use rusqlite::fallible_streaming_iterator::FallibleStreamingIterator;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 use std::io::{Error, ErrorKind};
+use std::time::Duration;
+
+/// Settings stored under this serial apply to every launchpad that has no setting of its own
+pub const DEFAULT_DEVICE_SERIAL: &str = "";
+
+/// Image path stored for a zone a launchpad cleared, so the default image does not show instead
+const CLEARED_IMAGE_PATH: &str = "";
 
 pub struct Operations {
     database: SqLite,
@@ -24,6 +31,12 @@ impl Operations {
         instance
             .create_config_mapping_table()
             .expect("Failed to create config_mapping table");
+        instance
+            .migrate_to_device_serials()
+            .expect("Failed to migrate tables to per device settings");
+        instance
+            .add_long_press_column()
+            .expect("Failed to add the long press to config_mapping");
 
         instance
     }
@@ -35,12 +48,14 @@ impl Operations {
             .ok_or(String::from("Operations not initialized"))
     }
 
-    /// Creates input_mapping table (button_id, actions) if it doesn't exist.
+    /// Creates input_mapping table (device_serial, button_id, actions) if it doesn't exist.
     fn create_input_mapping_table(&self) -> Result<(), String> {
         const CREATE_INPUT_MAPPING_TABLE: &str = "
             CREATE TABLE IF NOT EXISTS input_mapping (
-            button_id INTEGER PRIMARY KEY,
-            actions TEXT NOT NULL
+            device_serial TEXT NOT NULL DEFAULT '',
+            button_id INTEGER NOT NULL,
+            actions TEXT NOT NULL,
+            PRIMARY KEY (device_serial, button_id)
         )";
 
         self.open_connection()?
@@ -50,12 +65,14 @@ impl Operations {
         Ok(())
     }
 
-    /// Creates image_mapping table (display_zone_id, image_path) if it doesn't exist.
+    /// Creates image_mapping table (device_serial, display_zone_id, image_path) if it doesn't exist.
     fn create_image_mapping_table(&self) -> Result<(), String> {
         const CREATE_IMAGE_MAPPING_TABLE: &str = "
             CREATE TABLE IF NOT EXISTS image_mapping (
-            display_zone_id INTEGER PRIMARY KEY,
-            image_path TEXT NOT NULL
+            device_serial TEXT NOT NULL DEFAULT '',
+            display_zone_id INTEGER NOT NULL,
+            image_path TEXT NOT NULL,
+            PRIMARY KEY (device_serial, display_zone_id)
         )";
 
         self.open_connection()?
@@ -65,13 +82,14 @@ impl Operations {
         Ok(())
     }
 
-    /// Creates config_mapping table (id, brightness) as singleton if it doesn't exist.
+    /// Creates config_mapping table (device_serial, brightness, long_press_ms) with a row per device if it doesn't exist.
     fn create_config_mapping_table(&self) -> Result<(), String> {
         // Extend this with any other params if required
         const CREATE_CONFIG_MAPPING_TABLE: &str = "
             CREATE TABLE IF NOT EXISTS config_mapping (
-            id INTEGER PRIMARY KEY DEFAULT 1 CHECK (id = 1),
-            brightness INTEGER)
+            device_serial TEXT PRIMARY KEY NOT NULL DEFAULT '',
+            brightness INTEGER,
+            long_press_ms INTEGER)
         ";
 
         self.open_connection()?
@@ -81,71 +99,187 @@ impl Operations {
         Ok(())
     }
 
-    /// Sets or updates button-to-action mapping using UPSERT.
-    pub fn set_mapping_for_input(&self, input_mapping: InputMapping) -> Result<usize, String> {
+    /// Databases created before multiple devices were supported have no device_serial column.
+    ///
+    /// Their tables are rebuilt (SQLite cannot change a primary key in place) with every
+    /// existing row becoming a default setting, so a single launchpad keeps its configuration.
+    fn migrate_to_device_serials(&self) -> Result<(), String> {
+        const MIGRATIONS: &[(&str, &str, &str)] = &[
+            (
+                "input_mapping",
+                "device_serial TEXT NOT NULL DEFAULT '', button_id INTEGER NOT NULL, \
+                 actions TEXT NOT NULL, PRIMARY KEY (device_serial, button_id)",
+                "button_id, actions",
+            ),
+            (
+                "image_mapping",
+                "device_serial TEXT NOT NULL DEFAULT '', display_zone_id INTEGER NOT NULL, \
+                 image_path TEXT NOT NULL, PRIMARY KEY (device_serial, display_zone_id)",
+                "display_zone_id, image_path",
+            ),
+            (
+                "config_mapping",
+                "device_serial TEXT PRIMARY KEY NOT NULL DEFAULT '', brightness INTEGER",
+                "brightness",
+            ),
+        ];
+
+        let connection = self.open_connection()?;
+
+        for (table, columns, copied_columns) in MIGRATIONS {
+            let has_serial_column: bool = connection
+                .query_row(
+                    &format!(
+                        "SELECT COUNT(*) > 0 FROM pragma_table_info('{}') WHERE name = 'device_serial'",
+                        table
+                    ),
+                    [],
+                    |row| row.get(0),
+                )
+                .map_err(|e| e.to_string())?;
+
+            if has_serial_column {
+                continue;
+            }
+
+            connection
+                .execute_batch(&format!(
+                    "BEGIN;
+                    CREATE TABLE {table}_migrated ({columns});
+                    INSERT INTO {table}_migrated (device_serial, {copied_columns})
+                        SELECT '', {copied_columns} FROM {table};
+                    DROP TABLE {table};
+                    ALTER TABLE {table}_migrated RENAME TO {table};
+                    COMMIT;"
+                ))
+                .map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    /// Databases created before the long press could be configured have no long_press_ms column
+    fn add_long_press_column(&self) -> Result<(), String> {
+        let connection = self.open_connection()?;
+        let has_long_press_column: bool = connection
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('config_mapping') WHERE name = 'long_press_ms'",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+
+        if !has_long_press_column {
+            connection
+                .execute(
+                    "ALTER TABLE config_mapping ADD COLUMN long_press_ms INTEGER",
+                    (),
+                )
+                .map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets or updates button-to-action mapping of a device using UPSERT.
+    pub fn set_mapping_for_input(
+        &self,
+        device_serial: &str,
+        input_mapping: InputMapping,
+    ) -> Result<usize, String> {
         let input_mapping: InputMappingStorageFormat = input_mapping.try_into()?;
-        const SET_INPUT_MAPPING: &str = "INSERT INTO input_mapping (button_id, actions) VALUES (?1, ?2) \
-                                            ON CONFLICT(button_id) DO UPDATE SET actions=?2";
+        const SET_INPUT_MAPPING: &str = "INSERT INTO input_mapping (device_serial, button_id, actions) VALUES (?1, ?2, ?3) \
+                                            ON CONFLICT(device_serial, button_id) DO UPDATE SET actions=?3";
 
         self.open_connection()?
             .execute(
                 SET_INPUT_MAPPING,
-                (&input_mapping.input_id, &input_mapping.actions),
+                (
+                    device_serial,
+                    &input_mapping.input_id,
+                    &input_mapping.actions,
+                ),
             )
             .map_err(|e| e.to_string())
     }
 
-    /// Sets or updates display zone image mapping using UPSERT.
-    pub fn set_image_for_display_zone(&self, image_mapping: ImageMapping) -> Result<usize, Error> {
+    /// Sets or updates display zone image mapping of a device using UPSERT.
+    pub fn set_image_for_display_zone(
+        &self,
+        device_serial: &str,
+        image_mapping: ImageMapping,
+    ) -> Result<usize, Error> {
         let input_mapping: ImageMappingStorageFormat = image_mapping.into();
-        const SET_INPUT_MAPPING: &str = "INSERT INTO image_mapping (display_zone_id, image_path) VALUES (?1, ?2) \
-                                            ON CONFLICT(display_zone_id) DO UPDATE SET image_path=?2";
+        const SET_INPUT_MAPPING: &str = "INSERT INTO image_mapping (device_serial, display_zone_id, image_path) VALUES (?1, ?2, ?3) \
+                                            ON CONFLICT(device_serial, display_zone_id) DO UPDATE SET image_path=?3";
 
         self.open_connection()
             .map_err(|e| Error::new(ErrorKind::ConnectionRefused, e))?
             .execute(
                 SET_INPUT_MAPPING,
-                (&input_mapping.display_zone, &input_mapping.image_path),
+                (
+                    device_serial,
+                    &input_mapping.display_zone,
+                    &input_mapping.image_path,
+                ),
             )
             .map_err(Error::other)
     }
 
-    /// Deletes image mapping for specified display zone.
+    /// Deletes image mapping of a device for specified display zone, a device other than the
+    /// default keeps the zone cleared instead of falling back to the default image.
     pub fn clear_image_for_display_zone(
         &self,
+        device_serial: &str,
         display_zones: DisplayZones,
     ) -> Result<usize, Error> {
         const REMOVE_IMAGE_FOR_DISPLAY_ZONES: &str =
-            "DELETE FROM image_mapping WHERE display_zone_id = ?";
+            "DELETE FROM image_mapping WHERE device_serial = ?1 AND display_zone_id = ?2";
+
+        if device_serial != DEFAULT_DEVICE_SERIAL {
+            return self.set_image_for_display_zone(
+                device_serial,
+                ImageMapping {
+                    display_zone: display_zones,
+                    image_path: String::from(CLEARED_IMAGE_PATH),
+                },
+            );
+        }
 
         let int_value = u8::from(display_zones);
 
         self.open_connection()
             .map_err(|e| Error::new(ErrorKind::ConnectionRefused, e))?
-            .execute(REMOVE_IMAGE_FOR_DISPLAY_ZONES, params![int_value])
+            .execute(
+                REMOVE_IMAGE_FOR_DISPLAY_ZONES,
+                params![device_serial, int_value],
+            )
             .map_err(Error::other)
     }
 
-    /// Sets or updates brightness config value using UPSERT.
-    pub fn set_brightness(&self, brightness: u8) -> Result<usize, Error> {
-        const SET_BRIGHTNESS: &str = "INSERT INTO config_mapping (id, brightness) VALUES (1, ?1)\
-                                        ON CONFLICT(id) DO UPDATE SET brightness=?1";
+    /// Sets or updates brightness config value of a device using UPSERT.
+    pub fn set_brightness(&self, device_serial: &str, brightness: u8) -> Result<usize, Error> {
+        const SET_BRIGHTNESS: &str = "INSERT INTO config_mapping (device_serial, brightness) VALUES (?1, ?2)\
+                                        ON CONFLICT(device_serial) DO UPDATE SET brightness=?2";
         self.open_connection()
             .map_err(|e| Error::new(ErrorKind::ConnectionRefused, e))?
-            .execute(SET_BRIGHTNESS, params![brightness])
+            .execute(SET_BRIGHTNESS, params![device_serial, brightness])
             .map_err(Error::other)
     }
 
-    /// Gets stored brightness value, returns None if not set.
-    pub fn get_stored_brightness(&self) -> Result<Option<u8>, Error> {
-        const GET_BRIGHTNESS_VALUE: &str = "SELECT brightness FROM config_mapping WHERE id = 1";
+    /// Gets stored brightness value of a device (falling back to the default), returns None if not set.
+    pub fn get_stored_brightness(&self, device_serial: &str) -> Result<Option<u8>, Error> {
+        // The device's own value sorts before the default
+        const GET_BRIGHTNESS_VALUE: &str = "SELECT brightness FROM config_mapping \
+                                            WHERE device_serial IN (?1, '') AND brightness IS NOT NULL \
+                                            ORDER BY device_serial = '' LIMIT 1";
 
         let conn = self
             .open_connection()
             .map_err(|e| Error::new(ErrorKind::ConnectionRefused, e))?;
         let mut stmt = conn.prepare(GET_BRIGHTNESS_VALUE).map_err(Error::other)?;
 
-        let mut rows = stmt.query(params![]).map_err(Error::other)?;
+        let mut rows = stmt.query(params![device_serial]).map_err(Error::other)?;
         let singleton_row = match rows.nth(0) {
             Ok(row) => row,
             _ => return Ok(None),
@@ -158,19 +292,73 @@ impl Operations {
         Ok(None)
     }
 
-    /// Deletes all image mappings from database.
-    pub fn clear_all_display_zone_images(&self) -> Result<usize, Error> {
-        const CLEAR_ALL_DISPLAY_ZONE_IMAGES: &str = "DELETE FROM image_mapping";
+    /// Sets or updates how long the buttons of a device are held for a long press using UPSERT.
+    pub fn set_long_press(
+        &self,
+        device_serial: &str,
+        long_press: Duration,
+    ) -> Result<usize, Error> {
+        const SET_LONG_PRESS: &str = "INSERT INTO config_mapping (device_serial, long_press_ms) VALUES (?1, ?2)\
+                                        ON CONFLICT(device_serial) DO UPDATE SET long_press_ms=?2";
+        let long_press_ms = u64::try_from(long_press.as_millis()).unwrap_or(u64::MAX);
 
         self.open_connection()
             .map_err(|e| Error::new(ErrorKind::ConnectionRefused, e))?
-            .execute(CLEAR_ALL_DISPLAY_ZONE_IMAGES, ())
+            .execute(SET_LONG_PRESS, params![device_serial, long_press_ms])
             .map_err(Error::other)
     }
 
-    /// Returns all image mappings from database.
-    pub fn get_all_image_mappings(&self) -> Result<Vec<ImageMapping>, String> {
-        const GET_ALL_IMAGE_MAPPINGS: &str = "SELECT * FROM image_mapping";
+    /// Gets the stored long press of a device (falling back to the default), returns None if not set.
+    pub fn get_stored_long_press(&self, device_serial: &str) -> Result<Option<Duration>, Error> {
+        // The device's own value sorts before the default
+        const GET_LONG_PRESS_VALUE: &str = "SELECT long_press_ms FROM config_mapping \
+                                            WHERE device_serial IN (?1, '') AND long_press_ms IS NOT NULL \
+                                            ORDER BY device_serial = '' LIMIT 1";
+
+        let long_press_ms: Option<u64> = self
+            .open_connection()
+            .map_err(|e| Error::new(ErrorKind::ConnectionRefused, e))?
+            .query_row(GET_LONG_PRESS_VALUE, params![device_serial], |row| {
+                row.get(0)
+            })
+            .optional()
+            .map_err(Error::other)?;
+
+        Ok(long_press_ms.map(Duration::from_millis))
+    }
+
+    /// Deletes all image mappings of a device from database, a device other than the default
+    /// keeps the zones that have a default image cleared instead of falling back to it.
+    pub fn clear_all_display_zone_images(&self, device_serial: &str) -> Result<usize, Error> {
+        const CLEAR_ALL_DISPLAY_ZONE_IMAGES: &str =
+            "DELETE FROM image_mapping WHERE device_serial = ?1";
+        const HIDE_DEFAULT_DISPLAY_ZONE_IMAGES: &str = "INSERT INTO image_mapping (device_serial, display_zone_id, image_path) \
+                                                        SELECT ?1, display_zone_id, ?2 FROM image_mapping WHERE device_serial = ''";
+
+        let conn = self
+            .open_connection()
+            .map_err(|e| Error::new(ErrorKind::ConnectionRefused, e))?;
+        let cleared = conn
+            .execute(CLEAR_ALL_DISPLAY_ZONE_IMAGES, params![device_serial])
+            .map_err(Error::other)?;
+        if device_serial != DEFAULT_DEVICE_SERIAL {
+            conn.execute(
+                HIDE_DEFAULT_DISPLAY_ZONE_IMAGES,
+                params![device_serial, CLEARED_IMAGE_PATH],
+            )
+            .map_err(Error::other)?;
+        }
+
+        Ok(cleared)
+    }
+
+    /// Returns all image mappings of a device, including defaults for zones it has no image for.
+    /// Zones the device cleared are left out.
+    pub fn get_all_image_mappings(&self, device_serial: &str) -> Result<Vec<ImageMapping>, String> {
+        const GET_ALL_IMAGE_MAPPINGS: &str = "SELECT display_zone_id, image_path FROM image_mapping \
+                                              WHERE image_path != ?2 AND (device_serial = ?1 \
+                                              OR (device_serial = '' AND display_zone_id NOT IN \
+                                              (SELECT display_zone_id FROM image_mapping WHERE device_serial = ?1)))";
 
         let conn = self.open_connection()?;
         let mut stmt = conn
@@ -178,7 +366,9 @@ impl Operations {
             .map_err(|e| e.to_string())?;
 
         let row_iter = stmt
-            .query_map([], |row| Ok(ImageMapping::try_from(row)))
+            .query_map(params![device_serial, CLEARED_IMAGE_PATH], |row| {
+                Ok(ImageMapping::try_from(row))
+            })
             .map_err(|e| e.to_string())?;
 
         row_iter
@@ -192,9 +382,12 @@ impl Operations {
             .collect()
     }
 
-    /// Returns all input mappings from database.
-    pub fn get_all_input_mappings(&self) -> Result<Vec<InputMapping>, String> {
-        const GET_ALL_INPUT_MAPPINGS: &str = "SELECT * FROM input_mapping";
+    /// Returns all input mappings of a device, including defaults for inputs it has no mapping for.
+    pub fn get_all_input_mappings(&self, device_serial: &str) -> Result<Vec<InputMapping>, String> {
+        const GET_ALL_INPUT_MAPPINGS: &str = "SELECT button_id, actions FROM input_mapping \
+                                              WHERE device_serial = ?1 \
+                                              OR (device_serial = '' AND button_id NOT IN \
+                                              (SELECT button_id FROM input_mapping WHERE device_serial = ?1))";
 
         let conn = self.open_connection()?;
         let mut stmt = conn
@@ -202,7 +395,9 @@ impl Operations {
             .map_err(|e| e.to_string())?;
 
         let row_iter = stmt
-            .query_map([], |row| Ok(InputMapping::try_from(row)))
+            .query_map(params![device_serial], |row| {
+                Ok(InputMapping::try_from(row))
+            })
             .map_err(|e| e.to_string())?;
 
         row_iter
@@ -224,6 +419,7 @@ mod tests {
     use firmware_api::display_zones::DisplayZones;
     use firmware_api::inputs::InputActions;
     use firmware_api::inputs::buttons::ButtonActions::{Button1Pressed, Button4Pressed};
+    use firmware_api::inputs::gestures::Gesture;
 
     #[test]
     fn allows_setting_input_mappings() {
@@ -238,10 +434,14 @@ mod tests {
         operations.create_input_mapping_table().unwrap();
 
         to_add.iter().for_each(|item| {
-            operations.set_mapping_for_input(item.clone()).unwrap();
+            operations
+                .set_mapping_for_input(DEFAULT_DEVICE_SERIAL, item.clone())
+                .unwrap();
         });
 
-        let all_rows = operations.get_all_input_mappings().unwrap();
+        let all_rows = operations
+            .get_all_input_mappings(DEFAULT_DEVICE_SERIAL)
+            .unwrap();
 
         assert_eq!(all_rows.len(), 2);
         to_add.iter().for_each(|item| {
@@ -250,13 +450,18 @@ mod tests {
 
         // Replace the button 4 bindings
         operations
-            .set_mapping_for_input(InputMapping::new(
-                InputActions::Button(Button4Pressed),
-                vec![Key::Add, Key::Backspace],
-            ))
+            .set_mapping_for_input(
+                DEFAULT_DEVICE_SERIAL,
+                InputMapping::new(
+                    InputActions::Button(Button4Pressed),
+                    vec![Key::Add, Key::Backspace],
+                ),
+            )
             .unwrap();
 
-        let new_rows = operations.get_all_input_mappings().unwrap();
+        let new_rows = operations
+            .get_all_input_mappings(DEFAULT_DEVICE_SERIAL)
+            .unwrap();
 
         assert_eq!(new_rows.len(), 2);
         // Doesn't contain an old version of button 4
@@ -266,6 +471,39 @@ mod tests {
         assert!(new_rows.contains(&to_add[1]));
     }
 
+    #[test]
+    fn gestures_have_their_own_mappings() {
+        let sqlite = SqLite::new(false);
+        let operations = Operations::new(sqlite.unwrap());
+
+        let to_add = &[
+            InputMapping::new(InputActions::Button(Button4Pressed), vec![Key::Option]),
+            InputMapping::new(
+                InputActions::Gesture(Gesture::LongPress(4)),
+                vec![Key::Escape],
+            ),
+            InputMapping::new(
+                InputActions::Gesture(Gesture::Chord(1, 4)),
+                vec![Key::Space],
+            ),
+        ];
+
+        operations.create_input_mapping_table().unwrap();
+        for item in to_add.iter() {
+            operations
+                .set_mapping_for_input(DEFAULT_DEVICE_SERIAL, item.clone())
+                .unwrap();
+        }
+
+        let all_rows = operations
+            .get_all_input_mappings(DEFAULT_DEVICE_SERIAL)
+            .unwrap();
+        assert_eq!(all_rows.len(), 3);
+        to_add.iter().for_each(|item| {
+            assert!(all_rows.contains(item));
+        });
+    }
+
     #[test]
     fn allows_setting_and_clearing_display_zone_images() {
         let sqlite = SqLite::new(false);
@@ -285,10 +523,14 @@ mod tests {
         operations.create_input_mapping_table().unwrap();
 
         for item in to_add.iter() {
-            operations.set_image_for_display_zone(item.clone()).unwrap();
+            operations
+                .set_image_for_display_zone(DEFAULT_DEVICE_SERIAL, item.clone())
+                .unwrap();
         }
 
-        let all_rows = operations.get_all_image_mappings().unwrap();
+        let all_rows = operations
+            .get_all_image_mappings(DEFAULT_DEVICE_SERIAL)
+            .unwrap();
 
         assert_eq!(all_rows.len(), 2);
         to_add.iter().for_each(|item| {
@@ -297,13 +539,18 @@ mod tests {
 
         // Replace the touchscreen 3 bindings
         operations
-            .set_image_for_display_zone(ImageMapping {
-                display_zone: DisplayZones::Touchscreen3,
-                image_path: String::from("231.jpg"),
-            })
+            .set_image_for_display_zone(
+                DEFAULT_DEVICE_SERIAL,
+                ImageMapping {
+                    display_zone: DisplayZones::Touchscreen3,
+                    image_path: String::from("231.jpg"),
+                },
+            )
             .unwrap();
 
-        let new_rows = operations.get_all_image_mappings().unwrap();
+        let new_rows = operations
+            .get_all_image_mappings(DEFAULT_DEVICE_SERIAL)
+            .unwrap();
 
         assert_eq!(new_rows.len(), 2);
         // Doesn't contain an old version of button 4
@@ -319,10 +566,12 @@ mod tests {
 
         // Test single clear
         operations
-            .clear_image_for_display_zone(DisplayZones::Touchscreen3)
+            .clear_image_for_display_zone(DEFAULT_DEVICE_SERIAL, DisplayZones::Touchscreen3)
             .unwrap();
 
-        let new_rows = operations.get_all_image_mappings().unwrap();
+        let new_rows = operations
+            .get_all_image_mappings(DEFAULT_DEVICE_SERIAL)
+            .unwrap();
         assert_eq!(new_rows.len(), 1);
     }
 
@@ -345,11 +594,17 @@ mod tests {
         operations.create_input_mapping_table().unwrap();
 
         for item in to_add.iter() {
-            operations.set_image_for_display_zone(item.clone()).unwrap();
+            operations
+                .set_image_for_display_zone(DEFAULT_DEVICE_SERIAL, item.clone())
+                .unwrap();
         }
 
-        operations.clear_all_display_zone_images().unwrap();
-        let new_rows = operations.get_all_image_mappings().unwrap();
+        operations
+            .clear_all_display_zone_images(DEFAULT_DEVICE_SERIAL)
+            .unwrap();
+        let new_rows = operations
+            .get_all_image_mappings(DEFAULT_DEVICE_SERIAL)
+            .unwrap();
 
         assert_eq!(new_rows.len(), 0);
     }
@@ -360,19 +615,223 @@ mod tests {
         let operations = Operations::new(sqlite.unwrap());
 
         // When there is no stored brightness
-        let brightness = operations.get_stored_brightness().unwrap();
+        let brightness = operations
+            .get_stored_brightness(DEFAULT_DEVICE_SERIAL)
+            .unwrap();
 
         assert_eq!(brightness, None);
 
-        operations.set_brightness(69).unwrap();
+        operations
+            .set_brightness(DEFAULT_DEVICE_SERIAL, 69)
+            .unwrap();
 
-        let brightness = operations.get_stored_brightness().unwrap().unwrap();
+        let brightness = operations
+            .get_stored_brightness(DEFAULT_DEVICE_SERIAL)
+            .unwrap()
+            .unwrap();
 
         assert_eq!(brightness, 69);
 
-        operations.set_brightness(20).unwrap();
-        let brightness = operations.get_stored_brightness().unwrap().unwrap();
+        operations
+            .set_brightness(DEFAULT_DEVICE_SERIAL, 20)
+            .unwrap();
+        let brightness = operations
+            .get_stored_brightness(DEFAULT_DEVICE_SERIAL)
+            .unwrap()
+            .unwrap();
 
         assert_eq!(brightness, 20);
     }
+
+    #[test]
+    fn cleared_zones_do_not_fall_back_to_defaults() {
+        let operations = Operations::new(SqLite::new(false).unwrap());
+        for display_zone in [DisplayZones::Button1, DisplayZones::Button2] {
+            operations
+                .set_image_for_display_zone(
+                    DEFAULT_DEVICE_SERIAL,
+                    ImageMapping {
+                        display_zone,
+                        image_path: String::from("default.jpg"),
+                    },
+                )
+                .unwrap();
+        }
+
+        operations
+            .clear_image_for_display_zone("AKP05-LEFT", DisplayZones::Button1)
+            .unwrap();
+
+        assert_eq!(
+            operations.get_all_image_mappings("AKP05-LEFT").unwrap(),
+            [ImageMapping {
+                display_zone: DisplayZones::Button2,
+                image_path: String::from("default.jpg"),
+            }]
+        );
+        assert_eq!(
+            operations
+                .get_all_image_mappings("AKP05-RIGHT")
+                .unwrap()
+                .len(),
+            2
+        );
+
+        operations
+            .clear_all_display_zone_images("AKP05-RIGHT")
+            .unwrap();
+
+        assert!(
+            operations
+                .get_all_image_mappings("AKP05-RIGHT")
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            operations
+                .get_all_image_mappings(DEFAULT_DEVICE_SERIAL)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn device_settings_override_defaults() {
+        let operations = Operations::new(SqLite::new(false).unwrap());
+
+        operations
+            .set_image_for_display_zone(
+                DEFAULT_DEVICE_SERIAL,
+                ImageMapping {
+                    display_zone: DisplayZones::Button1,
+                    image_path: String::from("default.jpg"),
+                },
+            )
+            .unwrap();
+        operations
+            .set_image_for_display_zone(
+                DEFAULT_DEVICE_SERIAL,
+                ImageMapping {
+                    display_zone: DisplayZones::Button2,
+                    image_path: String::from("shared.jpg"),
+                },
+            )
+            .unwrap();
+        operations
+            .set_image_for_display_zone(
+                "AKP05-LEFT",
+                ImageMapping {
+                    display_zone: DisplayZones::Button1,
+                    image_path: String::from("left.jpg"),
+                },
+            )
+            .unwrap();
+        operations
+            .set_brightness(DEFAULT_DEVICE_SERIAL, 50)
+            .unwrap();
+        operations.set_brightness("AKP05-LEFT", 10).unwrap();
+
+        let left = operations.get_all_image_mappings("AKP05-LEFT").unwrap();
+        assert_eq!(left.len(), 2);
+        assert!(left.contains(&ImageMapping {
+            display_zone: DisplayZones::Button1,
+            image_path: String::from("left.jpg"),
+        }));
+        assert!(left.contains(&ImageMapping {
+            display_zone: DisplayZones::Button2,
+            image_path: String::from("shared.jpg"),
+        }));
+
+        let right = operations.get_all_image_mappings("AKP05-RIGHT").unwrap();
+        assert!(right.contains(&ImageMapping {
+            display_zone: DisplayZones::Button1,
+            image_path: String::from("default.jpg"),
+        }));
+
+        assert_eq!(
+            operations.get_stored_brightness("AKP05-LEFT").unwrap(),
+            Some(10)
+        );
+        assert_eq!(
+            operations.get_stored_brightness("AKP05-RIGHT").unwrap(),
+            Some(50)
+        );
+    }
+
+    #[test]
+    fn migrates_tables_without_device_serials() {
+        let sqlite = SqLite::new(false).unwrap();
+        sqlite
+            .connection()
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE input_mapping (button_id INTEGER PRIMARY KEY, actions TEXT NOT NULL);
+                CREATE TABLE image_mapping (display_zone_id INTEGER PRIMARY KEY, image_path TEXT NOT NULL);
+                CREATE TABLE config_mapping (id INTEGER PRIMARY KEY DEFAULT 1 CHECK (id = 1), brightness INTEGER);
+                INSERT INTO input_mapping VALUES (4, '[Option]');
+                INSERT INTO image_mapping VALUES (13, 'fat.jpg');
+                INSERT INTO config_mapping VALUES (1, 42);",
+            )
+            .unwrap();
+
+        let operations = Operations::new(sqlite);
+
+        assert_eq!(
+            operations.get_all_input_mappings("any").unwrap(),
+            vec![InputMapping::new(
+                InputActions::Button(Button4Pressed),
+                vec![Key::Option]
+            )]
+        );
+        assert_eq!(
+            operations.get_all_image_mappings("any").unwrap(),
+            vec![ImageMapping {
+                display_zone: DisplayZones::Button3,
+                image_path: String::from("fat.jpg"),
+            }]
+        );
+        assert_eq!(operations.get_stored_brightness("any").unwrap(), Some(42));
+
+        // And the new primary keys are in place
+        operations.set_brightness("any", 7).unwrap();
+        assert_eq!(operations.get_stored_brightness("any").unwrap(), Some(7));
+        operations
+            .set_long_press("any", Duration::from_millis(800))
+            .unwrap();
+        assert_eq!(
+            operations.get_stored_long_press("any").unwrap(),
+            Some(Duration::from_millis(800))
+        );
+    }
+
+    #[test]
+    fn long_presses_fall_back_to_the_default() {
+        let operations = Operations::new(SqLite::new(false).unwrap());
+        assert_eq!(
+            operations.get_stored_long_press("AKP05-LEFT").unwrap(),
+            None
+        );
+
+        operations
+            .set_long_press(DEFAULT_DEVICE_SERIAL, Duration::from_millis(700))
+            .unwrap();
+        operations.set_brightness("AKP05-LEFT", 30).unwrap();
+        assert_eq!(
+            operations.get_stored_long_press("AKP05-LEFT").unwrap(),
+            Some(Duration::from_millis(700))
+        );
+
+        operations
+            .set_long_press("AKP05-LEFT", Duration::from_millis(300))
+            .unwrap();
+        assert_eq!(
+            operations.get_stored_long_press("AKP05-LEFT").unwrap(),
+            Some(Duration::from_millis(300))
+        );
+        assert_eq!(
+            operations.get_stored_brightness("AKP05-LEFT").unwrap(),
+            Some(30)
+        );
+    }
 }
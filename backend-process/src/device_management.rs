@@ -1,17 +1,18 @@
+use firmware_api::profiles::{self, DeviceProfile, PROFILES};
 use hidapi::{HidApi, HidDevice};
-use std::thread::sleep;
-use std::time::Duration;
+use std::collections::HashSet;
+use std::ffi::CString;
 
-struct DeviceIdentifier {
-    pid: u16,
-    vid: u16,
+/// An opened launchpad along with what identifies it
+pub struct DiscoveredLaunchpad {
+    /// Serial number reported by the unit, falls back to the HID path if it has none
+    pub serial: String,
+    pub path: CString,
+    pub device: HidDevice,
+    /// The model, decided from the vendor and product id the unit enumerated with
+    pub profile: &'static dyn DeviceProfile,
 }
 
-const AJAZZ_LAUNCHPAD: DeviceIdentifier = DeviceIdentifier {
-    vid: 0x0300,
-    pid: 0x3004,
-};
-
 pub struct DeviceManagement {
     hid_api: HidApi,
 }
@@ -23,27 +24,60 @@ impl DeviceManagement {
         }
     }
 
+    /// Only enumerates the models that have a profile
     fn refresh_launchpad_filter(&mut self) {
         self.hid_api.reset_devices().unwrap();
-        self.hid_api
-            .add_devices(AJAZZ_LAUNCHPAD.vid, AJAZZ_LAUNCHPAD.pid)
-            .unwrap();
+        for id in PROFILES
+            .iter()
+            .flat_map(|profile| profile.usb_identifiers())
+        {
+            self.hid_api
+                .add_devices(id.vendor_id, id.product_id)
+                .unwrap();
+        }
     }
-    pub fn scan_for_launchpad(&mut self) -> HidDevice {
-        loop {
-            self.refresh_launchpad_filter();
-
-            // Bit of a hack, there are 3 identified devices with the given vid/pid, so need to find the one that works
-            for device in self.hid_api.device_list() {
-                // Refer to https://learn.microsoft.com/en-us/windows-hardware/drivers/hid/hid-usages
-                let launchpad = self.hid_api.open_path(device.path());
-
-                if let Ok(device) = launchpad {
-                    return device;
-                }
+
+    /// Opens every connected launchpad that `is_open` does not already know about
+    ///
+    /// * `is_open` - whether the launchpad with the given serial is already in use
+    pub fn scan_for_launchpads(
+        &mut self,
+        is_open: impl Fn(&str) -> bool,
+    ) -> Vec<DiscoveredLaunchpad> {
+        self.refresh_launchpad_filter();
+
+        let mut opened_serials = HashSet::new();
+        let mut launchpads = Vec::new();
+
+        // Bit of a hack, each unit shows up as 3 devices with its vid/pid (one per interface),
+        // so need to find the one that works for every serial
+        for device in self.hid_api.device_list() {
+            let Some(profile) = profiles::find_profile(device.vendor_id(), device.product_id())
+            else {
+                continue;
+            };
+            let serial = device
+                .serial_number()
+                .filter(|serial| !serial.is_empty())
+                .map(String::from)
+                .unwrap_or_else(|| device.path().to_string_lossy().into_owned());
+
+            if opened_serials.contains(&serial) || is_open(&serial) {
+                continue;
             }
 
-            sleep(Duration::from_millis(500));
+            // Refer to https://learn.microsoft.com/en-us/windows-hardware/drivers/hid/hid-usages
+            if let Ok(launchpad) = self.hid_api.open_path(device.path()) {
+                opened_serials.insert(serial.clone());
+                launchpads.push(DiscoveredLaunchpad {
+                    serial,
+                    path: device.path().to_owned(),
+                    device: launchpad,
+                    profile,
+                });
+            }
         }
+
+        launchpads
     }
 }
@@ -0,0 +1,203 @@
+use firmware_api::profiles;
+#[cfg(target_os = "linux")]
+use log::{debug, info};
+use std::time::{Duration, Instant};
+
+/// How often the launchpads are scanned for when udev cannot tell when they are plugged in
+pub const RESCAN_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A launchpad being plugged in or out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HotplugEvent {
+    /// A launchpad (or, without udev, possibly one) was plugged in and should be scanned for
+    Connected,
+    /// A launchpad was unplugged, along with its serial number if it reported one
+    Disconnected { serial: Option<String> },
+}
+
+enum Source {
+    /// Events from udev, sent once its rules have been applied (i.e. the hidraw node can be
+    /// opened)
+    #[cfg(target_os = "linux")]
+    Udev(udev::MonitorSocket),
+    /// Asks for a scan every `RESCAN_INTERVAL` instead
+    Polling { next_scan: Instant },
+}
+
+/// Tells the backend when launchpads are plugged in or out, without blocking.
+///
+/// On Linux the HID devices are watched through udev, anywhere else (or when udev cannot be
+/// reached) a scan is asked for every `RESCAN_INTERVAL`.
+pub struct HotplugMonitor {
+    source: Source,
+}
+
+impl HotplugMonitor {
+    pub fn new() -> Self {
+        #[cfg(target_os = "linux")]
+        match udev::MonitorBuilder::new()
+            .and_then(|builder| builder.match_subsystem("hid"))
+            .and_then(|builder| builder.match_subsystem("hidraw"))
+            .and_then(|builder| builder.listen())
+        {
+            Ok(socket) => {
+                return Self {
+                    source: Source::Udev(socket),
+                };
+            }
+            Err(e) => info!(
+                "Could not watch udev for launchpads, polling instead: {}",
+                e
+            ),
+        }
+
+        Self::polling()
+    }
+
+    /// A monitor that asks for a scan straight away and every `RESCAN_INTERVAL` after that
+    pub fn polling() -> Self {
+        Self {
+            source: Source::Polling {
+                next_scan: Instant::now(),
+            },
+        }
+    }
+
+    /// Every launchpad plugged in or out since the last poll, returns straight away
+    pub fn poll(&mut self, now: Instant) -> Vec<HotplugEvent> {
+        match &mut self.source {
+            #[cfg(target_os = "linux")]
+            Source::Udev(socket) => socket
+                .iter()
+                .filter_map(|event| {
+                    let subsystem = event.subsystem()?.to_string_lossy().into_owned();
+                    // A hidraw node only knows which device it is through its parent
+                    let hid_device = match subsystem.as_str() {
+                        "hidraw" => event.parent_with_subsystem("hid").ok()??,
+                        _ => event.device(),
+                    };
+                    let property = |name| {
+                        hid_device
+                            .property_value(name)
+                            .map(|value| value.to_string_lossy().into_owned())
+                    };
+                    let hotplug_event = launchpad_event(
+                        &subsystem,
+                        &event.event_type().to_string(),
+                        property("HID_ID").as_deref(),
+                        property("HID_UNIQ").as_deref(),
+                    );
+                    debug!(
+                        "udev {} of {:?}: {:?}",
+                        event.event_type(),
+                        event.sysname(),
+                        hotplug_event
+                    );
+                    hotplug_event
+                })
+                .collect(),
+            Source::Polling { next_scan } => match now >= *next_scan {
+                true => {
+                    *next_scan = now + RESCAN_INTERVAL;
+                    vec![HotplugEvent::Connected]
+                }
+                false => Vec::new(),
+            },
+        }
+    }
+}
+
+/// What a udev event means for the backend, `None` for anything that is not a launchpad being
+/// plugged in or out
+///
+/// * `subsystem` - `hid` or `hidraw`
+/// * `action` - the udev action, e.g. `add`
+/// * `hid_id` - `HID_ID` of the (parent) hid device, `<bus>:<vendor id>:<product id>` in hex
+/// * `hid_uniq` - `HID_UNIQ` of the (parent) hid device, its serial number
+fn launchpad_event(
+    subsystem: &str,
+    action: &str,
+    hid_id: Option<&str>,
+    hid_uniq: Option<&str>,
+) -> Option<HotplugEvent> {
+    let (vendor_id, product_id) = parse_hid_id(hid_id?)?;
+    profiles::find_profile(vendor_id, product_id)?;
+
+    // hidapi opens the hidraw nodes, which only exist once the hid device is bound to a driver
+    match (subsystem, action) {
+        ("hidraw", "add") => Some(HotplugEvent::Connected),
+        ("hid", "remove") => Some(HotplugEvent::Disconnected {
+            serial: hid_uniq
+                .filter(|serial| !serial.is_empty())
+                .map(String::from),
+        }),
+        _ => None,
+    }
+}
+
+fn parse_hid_id(hid_id: &str) -> Option<(u16, u16)> {
+    let mut parts = hid_id.split(':').skip(1);
+    let vendor_id = u32::from_str_radix(parts.next()?, 16).ok()?;
+    let product_id = u32::from_str_radix(parts.next()?, 16).ok()?;
+
+    Some((
+        u16::try_from(vendor_id).ok()?,
+        u16::try_from(product_id).ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AKP05_HID_ID: &str = "0003:00000300:00003004";
+
+    #[test]
+    fn launchpads_are_connected_once_their_hidraw_node_is_added() {
+        assert_eq!(parse_hid_id(AKP05_HID_ID), Some((0x0300, 0x3004)));
+        assert_eq!(
+            launchpad_event("hidraw", "add", Some(AKP05_HID_ID), Some("")),
+            Some(HotplugEvent::Connected)
+        );
+        assert_eq!(
+            launchpad_event("hid", "add", Some(AKP05_HID_ID), None),
+            None
+        );
+        assert_eq!(
+            launchpad_event("hidraw", "remove", Some(AKP05_HID_ID), None),
+            None
+        );
+        assert_eq!(
+            launchpad_event("hid", "remove", Some(AKP05_HID_ID), Some("AKP05-LEFT")),
+            Some(HotplugEvent::Disconnected {
+                serial: Some("AKP05-LEFT".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn other_devices_are_ignored() {
+        assert_eq!(
+            launchpad_event("hidraw", "add", Some("0003:0000046D:0000C52B"), None),
+            None
+        );
+        assert_eq!(
+            launchpad_event("hidraw", "add", Some("not an id"), None),
+            None
+        );
+        assert_eq!(launchpad_event("hidraw", "add", None, None), None);
+    }
+
+    #[test]
+    fn polling_asks_for_a_scan_every_interval() {
+        let mut monitor = HotplugMonitor::polling();
+        let start = Instant::now() + Duration::from_millis(1);
+
+        assert_eq!(monitor.poll(start), [HotplugEvent::Connected]);
+        assert!(monitor.poll(start + RESCAN_INTERVAL / 2).is_empty());
+        assert_eq!(
+            monitor.poll(start + RESCAN_INTERVAL),
+            [HotplugEvent::Connected]
+        );
+    }
+}
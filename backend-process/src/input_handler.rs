@@ -1,15 +1,25 @@
 use enigo::{Enigo, Key, Keyboard};
 use firmware_api::device::InputHandler;
-use firmware_api::inputs::InputActions;
 use firmware_api::inputs::buttons::ButtonActions;
 use firmware_api::inputs::buttons::ButtonActions::Button1Pressed;
+use firmware_api::inputs::gestures::{Gesture, GestureRecognizer, GestureTimings};
 use firmware_api::inputs::knobs::KnobActions;
+use firmware_api::inputs::rotation::{KnobRotation, RotationAggregator};
 use firmware_api::inputs::touchscreen::TouchscreenAction;
+use firmware_api::inputs::{InputActions, InputEvent};
+use log::debug;
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::database::models;
 
+/// Turning a knob faster than this many detents per second repeats its keys more often
+const ACCELERATION_DETENTS_PER_SECOND: f64 = 10.0;
+
+/// Upper limit for how many times the keys of a single detent are repeated
+const MAX_ACCELERATION: f64 = 5.0;
+
 pub trait KeyActionExecutor {
     fn execute(&self, actions: &[Key]) -> Result<(), String>;
 }
@@ -77,16 +87,20 @@ impl KeyActionExecutor for EnigoKeyActionHandler {
     }
 }
 
-pub struct LaunchpadInputHandler<'a> {
+pub struct LaunchpadInputHandler {
     input_mapping: InputMapping,
-    key_action_executor: &'a dyn KeyActionExecutor,
+    key_action_executor: Arc<dyn KeyActionExecutor>,
+    rotations: Mutex<RotationAggregator>,
+    gestures: Mutex<GestureRecognizer>,
 }
 
-impl<'a> LaunchpadInputHandler<'a> {
-    pub fn new(mapping: InputMapping, key_action_executor: &'a dyn KeyActionExecutor) -> Self {
+impl LaunchpadInputHandler {
+    pub fn new(mapping: InputMapping, key_action_executor: Arc<dyn KeyActionExecutor>) -> Self {
         Self {
             input_mapping: mapping,
             key_action_executor,
+            rotations: Mutex::new(RotationAggregator::default()),
+            gestures: Mutex::new(GestureRecognizer::default()),
         }
     }
 
@@ -97,6 +111,44 @@ impl<'a> LaunchpadInputHandler<'a> {
         new_created_mappings
     }
 
+    /// A handler with `new_mapping` on top of the current mappings, which carries on with the
+    /// knob rotations and gestures that are still going on (their keys come from `new_mapping`)
+    pub fn with_updated_mappings(&self, new_mapping: InputMapping) -> Self {
+        Self {
+            input_mapping: self.new_updated_mappings(new_mapping),
+            key_action_executor: self.key_action_executor.clone(),
+            rotations: Mutex::new(take_state(&self.rotations)),
+            gestures: Mutex::new(take_state(&self.gestures)),
+        }
+    }
+
+    /// Changes how long the buttons are held for a long press (and until they repeat)
+    pub fn set_long_press(&self, long_press: Duration) {
+        if let Ok(mut gestures) = self.gestures.lock() {
+            let timings = GestureTimings {
+                long_press,
+                ..*gestures.timings()
+            };
+            gestures.set_timings(timings);
+        }
+    }
+
+    /// Whether any gesture made with `button` has keys mapped to it
+    fn has_gesture_mapping(&self, button: u8) -> bool {
+        self.input_mapping.0.keys().any(|input| match input {
+            InputActions::Gesture(
+                Gesture::Tap(gesture_button)
+                | Gesture::LongPress(gesture_button)
+                | Gesture::DoubleTap(gesture_button)
+                | Gesture::HoldRepeat(gesture_button),
+            ) => *gesture_button == button,
+            InputActions::Gesture(Gesture::Chord(first, second)) => {
+                *first == button || *second == button
+            }
+            _ => false,
+        })
+    }
+
     fn execute_keys(&self, input_action: InputActions) {
         if let Some(actions) = self.input_mapping.0.get(&input_action) {
             self.key_action_executor.execute(actions).ok();
@@ -107,23 +159,340 @@ impl<'a> LaunchpadInputHandler<'a> {
         self.execute_keys(InputActions::Touchscreen(touchscreen_action));
     }
 
-    fn handle_button(&self, button_action: ButtonActions) {
-        self.execute_keys(InputActions::Button(button_action));
+    /// Gestures made with the button are sent by `execute_gestures`. A button with keys mapped
+    /// to any of its gestures does not send the keys of its presses and releases, so a long
+    /// press does not type the keys of a tap first.
+    fn handle_button(&self, button_action: ButtonActions, read_at: Instant) {
+        if let Ok(mut gestures) = self.gestures.lock() {
+            gestures.push(&button_action, read_at);
+        }
+        if !self.has_gesture_mapping(button_action.button().0) {
+            self.execute_keys(InputActions::Button(button_action));
+        }
     }
 
-    fn handle_knob(&self, knob_action: KnobActions) {
-        self.execute_keys(InputActions::Knob(knob_action));
+    /// Detents are only collected here, their keys are sent by `execute_knob_rotations`.
+    /// Detents without a mapping are not collected, so they do not wait for their window.
+    fn handle_knob(&self, knob_action: KnobActions, read_at: Instant) {
+        let mapped = self
+            .input_mapping
+            .0
+            .contains_key(&InputActions::Knob(knob_action.clone()));
+        let collected = match self.rotations.lock() {
+            Ok(mut rotations) if mapped => rotations.push(&knob_action, read_at),
+            _ => false,
+        };
+
+        if !collected {
+            self.execute_keys(InputActions::Knob(knob_action));
+        }
     }
-}
-impl InputHandler for LaunchpadInputHandler<'_> {
-    fn handle(&self, action: InputActions) {
+
+    /// Sends the keys of every knob rotation that finished by `now`, once per detent and
+    /// more often when the knob was spun quickly
+    pub fn execute_knob_rotations(&self, now: Instant) {
+        let rotations = match self.rotations.lock() {
+            Ok(mut rotations) => rotations.tick(now),
+            Err(_) => return,
+        };
+
+        for rotation in rotations {
+            for _ in 0..repetitions(&rotation) {
+                self.execute_keys(InputActions::Knob(rotation.detent()));
+            }
+        }
+    }
+
+    /// Sends the keys of every gesture recognised by `now`
+    pub fn execute_gestures(&self, now: Instant) {
+        let gestures = match self.gestures.lock() {
+            Ok(mut gestures) => gestures.tick(now),
+            Err(_) => return,
+        };
+
+        for gesture in gestures {
+            self.execute_keys(InputActions::Gesture(gesture));
+        }
+    }
+
+    /// * `read_at`: when the input was read, gestures and knob rotations are timed from it
+    fn dispatch(&self, action: InputActions, read_at: Instant) {
         match action {
             InputActions::Touchscreen(touchscreen_action) => {
                 self.handle_touchscreen(touchscreen_action);
             }
-            InputActions::Knob(knob_action) => self.handle_knob(knob_action),
-            InputActions::Button(button_action) => self.handle_button(button_action),
+            InputActions::Knob(knob_action) => self.handle_knob(knob_action, read_at),
+            InputActions::Button(button_action) => self.handle_button(button_action, read_at),
+            gesture @ InputActions::Gesture(_) => self.execute_keys(gesture),
+            // Only mappable in memory for now, lets new events be tried out before they get a name
+            raw @ InputActions::Raw(_) => self.execute_keys(raw),
             InputActions::Unknown => (),
         }
     }
 }
+
+/// Leaves the default in its place, for a handler that is being replaced
+fn take_state<T: Default>(state: &Mutex<T>) -> T {
+    state
+        .lock()
+        .map(|mut state| std::mem::take(&mut *state))
+        .unwrap_or_default()
+}
+
+/// How many times the keys of a detent are sent for `rotation`
+fn repetitions(rotation: &KnobRotation) -> u32 {
+    let acceleration = (rotation.velocity.abs() / ACCELERATION_DETENTS_PER_SECOND)
+        .clamp(1.0, MAX_ACCELERATION)
+        .round() as u32;
+
+    rotation.delta.unsigned_abs() * acceleration
+}
+impl InputHandler for LaunchpadInputHandler {
+    fn handle(&self, action: InputActions) {
+        self.dispatch(action, Instant::now());
+    }
+
+    fn handle_event(&self, event: InputEvent) {
+        self.dispatch(event.action.clone(), event.read_at);
+        debug!(
+            "Handled input #{} of {} ({:?}) {:?} after it was read",
+            event.sequence,
+            event.device.serial_number,
+            event.action,
+            event.read_at.elapsed()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use firmware_api::inputs::DeviceIdentity;
+    use firmware_api::inputs::gestures::Gesture;
+    use firmware_api::inputs::knobs::Knob;
+    use firmware_api::session::Session;
+    use std::time::Duration;
+
+    /// Button 3 tapped, then knob 3 turned by three detents
+    const TAP_AND_TURN_SESSION: &str = r#"
+{"elapsed_us":0,"sequence":0,"serial_number":"AKP05-LEFT","model":"AKP05","report":[65,67,75,0,0,79,75,0,0,3,1,0,0]}
+{"elapsed_us":40000,"sequence":1,"serial_number":"AKP05-LEFT","model":"AKP05","report":[65,67,75,0,0,79,75,0,0,3,0,0,0]}
+{"elapsed_us":500000,"sequence":2,"serial_number":"AKP05-LEFT","model":"AKP05","report":[65,67,75,0,0,79,75,0,0,145,0,0,0]}
+{"elapsed_us":505000,"sequence":3,"serial_number":"AKP05-LEFT","model":"AKP05","report":[65,67,75,0,0,79,75,0,0,145,0,0,0]}
+{"elapsed_us":510000,"sequence":4,"serial_number":"AKP05-LEFT","model":"AKP05","report":[65,67,75,0,0,79,75,0,0,145,0,0,0]}
+"#;
+
+    #[derive(Default)]
+    struct RecordingExecutor {
+        executed: Mutex<Vec<Key>>,
+    }
+
+    impl KeyActionExecutor for RecordingExecutor {
+        fn execute(&self, actions: &[Key]) -> Result<(), String> {
+            self.executed.lock().unwrap().extend_from_slice(actions);
+            Ok(())
+        }
+    }
+
+    fn rotation(delta: i32, velocity: f64) -> KnobRotation {
+        KnobRotation {
+            knob: Knob::Knob1,
+            delta,
+            velocity,
+            at: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn spinning_knobs_quickly_repeats_their_keys() {
+        assert_eq!(repetitions(&rotation(1, 2.0)), 1);
+        assert_eq!(repetitions(&rotation(-2, -4.0)), 2);
+        assert_eq!(repetitions(&rotation(3, 30.0)), 9);
+        assert_eq!(repetitions(&rotation(-5, -200.0)), 25);
+    }
+
+    fn event(action: InputActions, read_at: Instant, sequence: u64) -> InputEvent {
+        InputEvent {
+            action,
+            read_at,
+            sequence,
+            device: DeviceIdentity {
+                serial_number: "AKP05-LEFT".into(),
+                model: "AKP05",
+            },
+            report: [0; 13],
+        }
+    }
+
+    #[test]
+    fn gestures_are_timed_from_when_the_input_was_read() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let handler = LaunchpadInputHandler::new(
+            InputMapping(HashMap::from([(
+                InputActions::Gesture(Gesture::Tap(2)),
+                vec![Key::Space],
+            )])),
+            executor.clone(),
+        );
+        let read_at = Instant::now();
+
+        handler.handle_event(event(
+            InputActions::Button(ButtonActions::Button2Pressed),
+            read_at,
+            0,
+        ));
+        handler.handle_event(event(
+            InputActions::Button(ButtonActions::Button2Released),
+            read_at + Duration::from_millis(50),
+            1,
+        ));
+        handler.execute_gestures(read_at + Duration::from_millis(100));
+        assert!(executor.executed.lock().unwrap().is_empty());
+
+        handler.execute_gestures(read_at + Duration::from_millis(300));
+        assert_eq!(*executor.executed.lock().unwrap(), [Key::Space]);
+    }
+
+    #[test]
+    fn unmapped_detents_are_not_collected() {
+        let handler = LaunchpadInputHandler::new(
+            InputMapping(HashMap::new()),
+            Arc::new(RecordingExecutor::default()),
+        );
+        let read_at = Instant::now();
+
+        handler.handle_event(event(
+            InputActions::Knob(KnobActions::Knob2Clockwise),
+            read_at,
+            0,
+        ));
+
+        assert_eq!(
+            handler.rotations.lock().unwrap().next_deadline(read_at),
+            None
+        );
+    }
+
+    #[test]
+    fn rotations_carry_on_when_the_mappings_change() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let handler = LaunchpadInputHandler::new(
+            InputMapping(HashMap::from([(
+                InputActions::Knob(KnobActions::Knob2Clockwise),
+                vec![Key::Tab],
+            )])),
+            executor.clone(),
+        );
+        let read_at = Instant::now();
+
+        handler.handle_event(event(
+            InputActions::Knob(KnobActions::Knob2Clockwise),
+            read_at,
+            0,
+        ));
+        let handler = handler.with_updated_mappings(InputMapping(HashMap::from([(
+            InputActions::Knob(KnobActions::Knob2Clockwise),
+            vec![Key::Space],
+        )])));
+        handler.execute_knob_rotations(read_at + Duration::from_secs(1));
+
+        assert_eq!(*executor.executed.lock().unwrap(), [Key::Space]);
+    }
+
+    #[test]
+    fn buttons_with_gestures_only_send_the_gesture_keys() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let handler = LaunchpadInputHandler::new(
+            InputMapping(HashMap::from([
+                (
+                    InputActions::Button(ButtonActions::Button2Pressed),
+                    vec![Key::Tab],
+                ),
+                (InputActions::Gesture(Gesture::Tap(2)), vec![Key::Space]),
+                (
+                    InputActions::Button(ButtonActions::Button3Pressed),
+                    vec![Key::Escape],
+                ),
+            ])),
+            executor.clone(),
+        );
+        let read_at = Instant::now();
+
+        handler.handle_event(event(
+            InputActions::Button(ButtonActions::Button2Pressed),
+            read_at,
+            0,
+        ));
+        handler.handle_event(event(
+            InputActions::Button(ButtonActions::Button2Released),
+            read_at + Duration::from_millis(50),
+            1,
+        ));
+        assert!(executor.executed.lock().unwrap().is_empty());
+
+        handler.execute_gestures(read_at + Duration::from_millis(400));
+        handler.handle_event(event(
+            InputActions::Button(ButtonActions::Button3Pressed),
+            read_at + Duration::from_millis(500),
+            2,
+        ));
+        assert_eq!(
+            *executor.executed.lock().unwrap(),
+            [Key::Space, Key::Escape]
+        );
+    }
+
+    #[test]
+    fn long_presses_can_be_shortened() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let handler = LaunchpadInputHandler::new(
+            InputMapping(HashMap::from([(
+                InputActions::Gesture(Gesture::LongPress(4)),
+                vec![Key::Space],
+            )])),
+            executor.clone(),
+        );
+        let read_at = Instant::now();
+
+        handler.set_long_press(Duration::from_millis(200));
+        handler.handle_event(event(
+            InputActions::Button(ButtonActions::Button4Pressed),
+            read_at,
+            0,
+        ));
+        handler.execute_gestures(read_at + Duration::from_millis(250));
+
+        assert_eq!(*executor.executed.lock().unwrap(), [Key::Space]);
+    }
+
+    #[test]
+    fn recorded_sessions_send_the_same_keys() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let handler = LaunchpadInputHandler::new(
+            InputMapping(HashMap::from([
+                (InputActions::Gesture(Gesture::Tap(3)), vec![Key::Space]),
+                (
+                    InputActions::Knob(KnobActions::Knob3Clockwise),
+                    vec![Key::Tab],
+                ),
+            ])),
+            executor.clone(),
+        );
+        let session = Session::read(TAP_AND_TURN_SESSION.as_bytes()).unwrap();
+        let start = Instant::now();
+
+        // Ticks like the main loop would have between the inputs
+        for event in session.events(start) {
+            handler.execute_gestures(event.read_at);
+            handler.execute_knob_rotations(event.read_at);
+            handler.handle_event(event);
+        }
+        handler.execute_gestures(start + Duration::from_secs(1));
+        handler.execute_knob_rotations(start + Duration::from_secs(1));
+
+        assert_eq!(
+            *executor.executed.lock().unwrap(),
+            [Key::Space, Key::Tab, Key::Tab, Key::Tab]
+        );
+    }
+}
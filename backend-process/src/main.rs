@@ -1,18 +1,40 @@
 mod database;
 mod device_management;
+mod hotplug;
 mod input_handler;
 mod protobuf_conversion;
 mod socket;
 
 use crate::database::operations::Operations;
 use crate::device_management::DeviceManagement;
+use crate::hotplug::{HotplugEvent, HotplugMonitor};
 use crate::input_handler::{
     EnigoKeyActionHandler, InputMapping, KeyActionExecutor, LaunchpadInputHandler,
 };
-use crate::socket::commands::IncomingCommands;
-use firmware_api::device;
+use crate::socket::commands::{CommandQueue, DeviceCommand, IncomingCommands};
+use crate::socket::connection::ServerHandler;
+use firmware_api::device::batch::Batch;
+use firmware_api::fade::BrightnessFade;
+use firmware_api::keep_alive::KeepAlive;
+use firmware_api::profiles::DeviceProfile;
+use firmware_api::{Error, device};
+use hidapi::HidDevice;
 use log::{debug, error, info};
-use std::fs::File;
+use signal_hook::consts::{SIGINT, SIGTERM};
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// How long the loop rests while there is no launchpad to read from, keeps the socket responsive
+const IDLE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How often an upload reports its progress and looks for commands cancelling it
+const UPLOAD_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+type Launchpad = device::Device<device::HidDeviceWrapper, LaunchpadInputHandler>;
 
 #[derive(Clone)]
 enum States {
@@ -33,7 +55,8 @@ impl StateMachine {
             current_state: States::InitialiseDevice,
         }
     }
-    fn next_state(&mut self, current_connections: u8, device_is_connected: bool) {
+    /// * `launchpad_plugged_in` - whether a launchpad was plugged in since the last scan
+    fn next_state(&mut self, current_connections: u8, launchpad_plugged_in: bool) {
         match self.current_state {
             States::InitialiseDevice => {
                 self.current_state = States::PruneConnections;
@@ -42,14 +65,14 @@ impl StateMachine {
                 connections if connections > 0 => self.current_state = States::ReadClientMessages,
                 _ => self.current_state = States::HandleDeviceInput,
             },
-            States::HandleDeviceInput => match device_is_connected {
-                true => match current_connections {
+            States::HandleDeviceInput => match launchpad_plugged_in {
+                true => self.current_state = States::InitialiseDevice,
+                false => match current_connections {
                     connections if connections > 0 => {
                         self.current_state = States::ReadClientMessages
                     }
                     _ => self.current_state = States::EstablishConnection,
                 },
-                false => self.current_state = States::InitialiseDevice,
             },
             States::ReadClientMessages => {
                 self.current_state = States::PruneConnections;
@@ -65,57 +88,313 @@ impl StateMachine {
     }
 }
 
+/// Opens the device and restores the images, mappings and brightness stored for its serial
+fn initialise_device(
+    db: &Operations,
+    hid_device: HidDevice,
+    profile: &'static dyn DeviceProfile,
+    device_serial: &str,
+    key_action_handler: Arc<dyn KeyActionExecutor>,
+) -> Launchpad {
+    // Image config fetching
+    let default_images = db.get_all_image_mappings(device_serial).unwrap();
+
+    // Input config fetching
+    let mut default_mappings = InputMapping::default();
+    default_mappings.override_config(db.get_all_input_mappings(device_serial).unwrap().into());
+    let input_handler = LaunchpadInputHandler::new(default_mappings, key_action_handler);
+    if let Some(long_press) = db.get_stored_long_press(device_serial).unwrap() {
+        input_handler.set_long_press(long_press);
+    }
+
+    // Brightness config fetching
+    let stored_brightness = db.get_stored_brightness(device_serial).unwrap();
+
+    let mut new_device = device::Device::new(
+        device::HidDeviceWrapper::new(hid_device, false), // No borrowing here
+        input_handler,
+    );
+    new_device.set_profile(profile);
+    new_device.set_serial_number(device_serial);
+    new_device.refresh().unwrap();
+
+    // Everything is restored in one go, so the panel does not fill in key by key
+    let mut batch = new_device.batch();
+    // Stop showing background image
+    batch.clear_all_images();
+
+    for default_mapping in default_images {
+        let staged = std::fs::read(default_mapping.image_path)
+            .map_err(Error::from)
+            .and_then(|image| {
+                batch.set_display_zone_image_from_bytes(default_mapping.display_zone, image)
+            });
+        if let Err(e) = staged {
+            error!("Failed to process image {}", e)
+        }
+    }
+    if let Some(brightness) = stored_brightness {
+        batch.set_brightness(brightness).ok();
+    }
+    if let Err(e) = batch.commit() {
+        error!("Failed to restore the display of {}: {}", device_serial, e)
+    }
+
+    new_device
+}
+
+/// Applies everything a launchpad was sent since the last read, in the order it was sent.
+/// Consecutive changes to the display zones are sent as one batch, so that e.g. a whole page
+/// of images switches at once.
+fn apply_commands(
+    dev: &mut Launchpad,
+    device_serial: &str,
+    device_commands: &[&DeviceCommand],
+    server: &mut ServerHandler,
+    fades: &mut HashMap<String, BrightnessFade>,
+) {
+    let mut batch = dev.batch();
+    for device_command in device_commands {
+        match &device_command.command {
+            IncomingCommands::SetKeyConfig(_)
+            | IncomingCommands::SetLongPress(_)
+            | IncomingCommands::SetBootLogo(_) => {
+                // Whatever was staged before the command goes first
+                commit_batch(batch, device_serial);
+                apply_unbatched_command(dev, device_serial, device_command, server);
+                batch = dev.batch();
+            }
+            command => {
+                if let Err(e) = stage_command(&mut batch, command) {
+                    error!("Failed to process image {}", e)
+                }
+            }
+        }
+    }
+    commit_batch(batch, device_serial);
+
+    // Only the latest brightness command counts, a fade is taken over by whatever follows it
+    let latest_brightness = device_commands.iter().rev().find(|device_command| {
+        matches!(
+            device_command.command,
+            IncomingCommands::SetBrightness(_) | IncomingCommands::FadeBrightness { .. }
+        )
+    });
+    match latest_brightness.map(|device_command| &device_command.command) {
+        Some(IncomingCommands::FadeBrightness {
+            brightness,
+            duration,
+            easing,
+        }) => {
+            let from = dev.brightness().unwrap_or(100);
+            match BrightnessFade::new(from, *brightness, *duration, *easing, Instant::now()) {
+                Ok(fade) => {
+                    fades.insert(device_serial.to_string(), fade);
+                }
+                Err(e) => error!("Failed to fade the brightness of {}: {}", device_serial, e),
+            }
+        }
+        Some(_) => {
+            fades.remove(device_serial);
+        }
+        None => {}
+    }
+}
+
+/// Runs a command that cannot be part of a batch
+fn apply_unbatched_command(
+    dev: &mut Launchpad,
+    device_serial: &str,
+    device_command: &DeviceCommand,
+    server: &mut ServerHandler,
+) {
+    match &device_command.command {
+        IncomingCommands::SetKeyConfig(mapping) => {
+            let input_handler = dev.handler().with_updated_mappings(mapping.clone());
+            dev.update_handler(input_handler);
+        }
+        IncomingCommands::SetLongPress(long_press) => dev.handler().set_long_press(*long_press),
+        IncomingCommands::SetBootLogo(file_path) => {
+            let Ok(image) = std::fs::read(file_path) else {
+                return;
+            };
+            let cancellation = server.start_upload(device_serial);
+            let mut last_update: Option<Instant> = None;
+            let upload_progress = |sent, total| {
+                if sent < total
+                    && last_update.is_some_and(|at| at.elapsed() < UPLOAD_PROGRESS_INTERVAL)
+                {
+                    return;
+                }
+                last_update = Some(Instant::now());
+
+                server.check_for_cancellations();
+                // The client may have gone away or not be reading, the upload goes on regardless
+                server
+                    .send_upload_progress(device_command.client_id, device_serial, sent, total)
+                    .ok();
+            };
+            match dev.set_background_image_with_progress(image, upload_progress, &cancellation) {
+                Ok(_) => {}
+                Err(Error::Cancelled) => {
+                    info!("Boot logo upload to {} cancelled", device_serial)
+                }
+                Err(e) => error!("Failed to upload boot logo {}", e),
+            }
+            server.finish_upload(device_serial);
+        }
+        _ => {}
+    }
+}
+
+fn commit_batch(
+    batch: Batch<'_, device::HidDeviceWrapper, LaunchpadInputHandler>,
+    device_serial: &str,
+) {
+    if !batch.is_empty()
+        && let Err(e) = batch.commit()
+    {
+        error!("Failed to update the display of {}: {}", device_serial, e)
+    }
+}
+
+/// Adds the display zone changes to `batch`, anything else is left to `apply_commands`
+fn stage_command(
+    batch: &mut Batch<'_, device::HidDeviceWrapper, LaunchpadInputHandler>,
+    command: &IncomingCommands,
+) -> Result<(), Error> {
+    match command {
+        IncomingCommands::SetDisplayZoneImage(mapping) => {
+            let image = std::fs::read(&mapping.image_path)?;
+            batch.set_display_zone_image_from_bytes(mapping.display_zone, image)?;
+        }
+        IncomingCommands::SetBrightness(brightness) => {
+            batch.set_brightness(*brightness)?;
+        }
+        IncomingCommands::ClearDisplayZoneImage(display_zone) => {
+            batch.clear_display_zone_image(*display_zone)?;
+        }
+        IncomingCommands::ClearAllDisplayZoneImages => {
+            batch.clear_all_images();
+        }
+        IncomingCommands::SetKeyConfig(_)
+        | IncomingCommands::SetLongPress(_)
+        | IncomingCommands::SetBootLogo(_)
+        | IncomingCommands::CancelUpload
+        | IncomingCommands::FadeBrightness { .. } => {}
+    }
+    Ok(())
+}
+
+/// Every command the clients sent since the last read
+fn read_pending_commands(server: &mut ServerHandler) -> Vec<DeviceCommand> {
+    let mut device_commands = Vec::new();
+    loop {
+        match server.handle_command_and_persist_config() {
+            Ok(device_command) => device_commands.push(device_command),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => return device_commands,
+            Err(e) => {
+                info!("No known command was handled: {}", e);
+            }
+        }
+    }
+}
+
 fn main() {
     env_logger::init();
     let mut state_machine = StateMachine::new();
     let db = Operations::new(database::sqlite::SqLite::new(true).unwrap());
     let mut server = socket::connection::ServerHandler::new(&db).expect("Failed to create server");
-    let mut device: Option<device::Device<device::HidDeviceWrapper, LaunchpadInputHandler>> = None;
-    let key_action_handler: Box<dyn KeyActionExecutor> = Box::new(EnigoKeyActionHandler::default());
-    loop {
+    let mut devices: HashMap<String, Launchpad> = HashMap::new();
+    let key_action_handler: Arc<dyn KeyActionExecutor> = Arc::new(EnigoKeyActionHandler::default());
+    let mut keep_alive = KeepAlive::default();
+    let mut fades: HashMap<String, BrightnessFade> = HashMap::new();
+    let mut device_management = DeviceManagement::new();
+    let mut hotplug = HotplugMonitor::new();
+    let mut queued_commands = CommandQueue::default();
+    let mut launchpad_plugged_in = false;
+    // The first signal lets the launchpads be disconnected, a second one exits straight away
+    let shutdown = Arc::new(AtomicBool::new(false));
+    for signal in [SIGINT, SIGTERM] {
+        signal_hook::flag::register_conditional_shutdown(signal, 1, shutdown.clone())
+            .and_then(|_| signal_hook::flag::register(signal, shutdown.clone()))
+            .expect("Failed to listen for signals");
+    }
+    while !shutdown.load(Ordering::Relaxed) {
+        for event in hotplug.poll(Instant::now()) {
+            match event {
+                HotplugEvent::Connected => launchpad_plugged_in = true,
+                HotplugEvent::Disconnected {
+                    serial: Some(serial),
+                } => {
+                    if devices.remove(&serial).is_some() {
+                        info!("Disconnected from launchpad {}", serial);
+                    }
+                }
+                // Noticed by the next read instead
+                HotplugEvent::Disconnected { serial: None } => {}
+            }
+        }
+        // Without a heartbeat the launchpads eventually fall back to their boot logo
+        if !devices.is_empty() && keep_alive.due(Instant::now()) {
+            for dev in devices.values() {
+                dev.send_heartbeat().ok();
+            }
+        }
+        for dev in devices.values() {
+            dev.handler().execute_knob_rotations(Instant::now());
+            dev.handler().execute_gestures(Instant::now());
+        }
+        fades.retain(|serial, fade| match devices.get(serial) {
+            Some(dev) => {
+                if let Err(e) = fade.tick(dev, Instant::now()) {
+                    debug!("Failed to fade the brightness of {}: {}", serial, e);
+                }
+                !fade.is_finished()
+            }
+            None => false,
+        });
+
         let current_state = state_machine.current_state();
-        let mut device_disconnected_during_read = false;
         match current_state {
             States::InitialiseDevice => {
-                let mut device_management = DeviceManagement::new();
-                let hid_device = device_management.scan_for_launchpad();
-                // Image config fetching
-                let default_images = db.get_all_image_mappings().unwrap();
-
-                // Input config fetching
-                let mut default_mappings = InputMapping::default();
-                default_mappings.override_config(db.get_all_input_mappings().unwrap().into());
-                let input_handler =
-                    LaunchpadInputHandler::new(default_mappings, key_action_handler.as_ref());
-
-                // Brightness config fetching
-                let stored_brightness = db.get_stored_brightness().unwrap();
-
-                let new_device = device::Device::new(
-                    device::HidDeviceWrapper::new(hid_device, false), // No borrowing here
-                    input_handler,
-                );
-                new_device.refresh().unwrap();
-
-                // Stop showing background image
-                new_device.clear_all_images().ok();
-
-                for default_mapping in default_images {
-                    match File::open(default_mapping.image_path) {
-                        Ok(image) => {
-                            new_device
-                                .set_display_zone_image(default_mapping.display_zone, image)
-                                .ok();
-                        }
-                        Err(e) => {
-                            error!("Failed to process image {}", e)
-                        }
+                launchpad_plugged_in = false;
+                let discovered =
+                    device_management.scan_for_launchpads(|serial| devices.contains_key(serial));
+
+                for launchpad in discovered {
+                    info!(
+                        "Connected to {} launchpad {} ({:?})",
+                        launchpad.profile.name(),
+                        launchpad.serial,
+                        launchpad.path
+                    );
+                    let mut new_device = initialise_device(
+                        &db,
+                        launchpad.device,
+                        launchpad.profile,
+                        &launchpad.serial,
+                        key_action_handler.clone(),
+                    );
+
+                    // On top of what was restored, what is not stored (e.g. a boot logo)
+                    let queued = queued_commands.take_for(&launchpad.serial, Instant::now());
+                    if !queued.is_empty() {
+                        info!(
+                            "Applying {} commands sent while {} was not connected",
+                            queued.len(),
+                            launchpad.serial
+                        );
+                        apply_commands(
+                            &mut new_device,
+                            &launchpad.serial,
+                            &queued.iter().collect::<Vec<_>>(),
+                            &mut server,
+                            &mut fades,
+                        );
                     }
+                    devices.insert(launchpad.serial, new_device);
                 }
-                if let Some(brightness) = stored_brightness {
-                    new_device.set_brightness(brightness).ok();
-                }
-                device = Some(new_device);
             }
             States::EstablishConnection => match server.add_new_connection_if_exists() {
                 Ok(_) => {
@@ -126,65 +405,51 @@ fn main() {
                 }
             },
             States::ReadClientMessages => {
-                if let Some(ref mut dev) = device {
-                    match server.handle_command_and_persist_config() {
-                        Ok(message_type) => match message_type {
-                            IncomingCommands::SetKeyConfig(mapping) => {
-                                let input_handler = LaunchpadInputHandler::new(
-                                    dev.handler().new_updated_mappings(mapping),
-                                    key_action_handler.as_ref(),
-                                );
-                                dev.update_handler(input_handler);
-                            }
-                            IncomingCommands::SetDisplayZoneImage(mapping) => {
-                                if let Ok(image) = File::open(mapping.image_path) {
-                                    dev.set_display_zone_image(mapping.display_zone, image).ok();
-                                }
-                            }
-                            IncomingCommands::SetBrightness(brightness) => {
-                                dev.set_brightness(brightness).ok();
-                            }
-                            IncomingCommands::ClearDisplayZoneImage(display_zone) => {
-                                dev.clear_display_zone_image(display_zone).ok();
-                            }
-                            IncomingCommands::SetBootLogo(file_path) => {
-                                if let Ok(image) = File::open(file_path) {
-                                    dev.set_background_image(image).ok();
-                                }
-                            }
-                            IncomingCommands::ClearAllDisplayZoneImages => {
-                                dev.clear_all_images().ok();
-                            }
-                        },
-                        Err(e) => {
-                            info!("No known command was handled: {}", e);
-                        }
-                    }
+                let device_commands = read_pending_commands(&mut server);
+                for (serial, dev) in devices.iter_mut() {
+                    let targeted: Vec<&DeviceCommand> = device_commands
+                        .iter()
+                        .filter(|device_command| device_command.targets(serial))
+                        .collect();
+                    apply_commands(dev, serial, &targeted, &mut server, &mut fades);
+                }
+
+                // Kept for the launchpads they are meant for that are not connected yet
+                for device_command in device_commands {
+                    queued_commands.push(device_command, devices.keys(), Instant::now());
                 }
             }
             States::HandleDeviceInput => {
-                if let Some(ref mut dev) = device {
-                    dev.read_input().unwrap_or_else(|e| {
-                        debug!("Error reading input: {}", e);
-                        if e.to_string() == "hidapi error: hid_read_timeout: device disconnected" {
-                            info!("Disconnected from device");
-                            device_disconnected_during_read = true;
-                        }
-                    });
+                if devices.is_empty() {
+                    sleep(IDLE_INTERVAL);
                 }
+                devices.retain(|serial, dev| match dev.read_input() {
+                    Ok(_) => true,
+                    Err(Error::Disconnected) => {
+                        info!("Disconnected from launchpad {}", serial);
+                        false
+                    }
+                    Err(e) => {
+                        debug!("Error reading input from {}: {}", serial, e);
+                        true
+                    }
+                });
             }
             States::PruneConnections => {
                 server.prune_connections().ok();
             }
         }
 
-        let device_is_connected = device.is_some() && !device_disconnected_during_read;
-        if device_disconnected_during_read {
-            device = None;
-        }
         state_machine.next_state(
             server.server().connected_clients() as u8,
-            device_is_connected,
+            launchpad_plugged_in,
         );
     }
+
+    for (serial, dev) in &devices {
+        match dev.disconnect() {
+            Ok(_) => info!("Disconnected from launchpad {}", serial),
+            Err(e) => error!("Failed to disconnect from {}: {}", serial, e),
+        }
+    }
 }
@@ -1,9 +1,11 @@
 use crate::database::models::{ImageMapping, InputMapping};
 use enigo::Key;
 use firmware_api::display_zones::DisplayZones;
+use firmware_api::fade::Easing;
 use firmware_api::inputs::InputActions;
 use firmware_api::inputs::InputActions::Unknown;
 use firmware_api::inputs::buttons::ButtonActions;
+use firmware_api::inputs::gestures::Gesture;
 use firmware_api::inputs::knobs::KnobActions;
 use firmware_api::inputs::touchscreen::TouchscreenAction;
 use messaging::protos;
@@ -21,6 +23,9 @@ pub struct DisplayZoneWrapper(DisplayZones);
 #[derive(Debug, PartialEq)]
 pub struct InputActionWrapper(InputActions);
 
+#[derive(Debug, PartialEq)]
+pub struct EasingWrapper(Easing);
+
 impl From<protos::inputs::InputId> for InputActionWrapper {
     fn from(value: protos::inputs::InputId) -> InputActionWrapper {
         match value {
@@ -144,6 +149,43 @@ impl From<protos::inputs::InputId> for InputActionWrapper {
             protos::inputs::InputId::TOUCHSCREEN_SWIPED_RIGHT => {
                 InputActionWrapper(InputActions::Touchscreen(TouchscreenAction::SwipedRight))
             }
+
+            protos::inputs::InputId::BUTTON_11_PRESSED => {
+                InputActionWrapper(InputActions::Button(ButtonActions::Button11Pressed))
+            }
+            protos::inputs::InputId::BUTTON_12_PRESSED => {
+                InputActionWrapper(InputActions::Button(ButtonActions::Button12Pressed))
+            }
+            protos::inputs::InputId::BUTTON_13_PRESSED => {
+                InputActionWrapper(InputActions::Button(ButtonActions::Button13Pressed))
+            }
+            protos::inputs::InputId::BUTTON_14_PRESSED => {
+                InputActionWrapper(InputActions::Button(ButtonActions::Button14Pressed))
+            }
+            protos::inputs::InputId::BUTTON_15_PRESSED => {
+                InputActionWrapper(InputActions::Button(ButtonActions::Button15Pressed))
+            }
+            protos::inputs::InputId::BUTTON_11_RELEASED => {
+                InputActionWrapper(InputActions::Button(ButtonActions::Button11Released))
+            }
+            protos::inputs::InputId::BUTTON_12_RELEASED => {
+                InputActionWrapper(InputActions::Button(ButtonActions::Button12Released))
+            }
+            protos::inputs::InputId::BUTTON_13_RELEASED => {
+                InputActionWrapper(InputActions::Button(ButtonActions::Button13Released))
+            }
+            protos::inputs::InputId::BUTTON_14_RELEASED => {
+                InputActionWrapper(InputActions::Button(ButtonActions::Button14Released))
+            }
+            protos::inputs::InputId::BUTTON_15_RELEASED => {
+                InputActionWrapper(InputActions::Button(ButtonActions::Button15Released))
+            }
+
+            // Gestures are numbered the way they are stored, see `Gesture::code`
+            gesture => Gesture::from_code(gesture.value() as u8)
+                .map_or(InputActionWrapper(Unknown), |gesture| {
+                    InputActionWrapper(InputActions::Gesture(gesture))
+                }),
         }
     }
 }
@@ -222,6 +264,21 @@ impl TryFrom<protos::display_zones::DisplayZone> for DisplayZoneWrapper {
             protos::display_zones::DisplayZone::BUTTON_10 => {
                 DisplayZoneWrapper(DisplayZones::Button10)
             }
+            protos::display_zones::DisplayZone::BUTTON_11 => {
+                DisplayZoneWrapper(DisplayZones::Button11)
+            }
+            protos::display_zones::DisplayZone::BUTTON_12 => {
+                DisplayZoneWrapper(DisplayZones::Button12)
+            }
+            protos::display_zones::DisplayZone::BUTTON_13 => {
+                DisplayZoneWrapper(DisplayZones::Button13)
+            }
+            protos::display_zones::DisplayZone::BUTTON_14 => {
+                DisplayZoneWrapper(DisplayZones::Button14)
+            }
+            protos::display_zones::DisplayZone::BUTTON_15 => {
+                DisplayZoneWrapper(DisplayZones::Button15)
+            }
             protos::display_zones::DisplayZone::TOUCHSCREEN_ZONE_1 => {
                 DisplayZoneWrapper(DisplayZones::Touchscreen1)
             }
@@ -245,6 +302,23 @@ impl TryFrom<protos::display_zones::DisplayZone> for DisplayZoneWrapper {
     }
 }
 
+impl EasingWrapper {
+    pub fn easing(&self) -> Easing {
+        self.0
+    }
+}
+
+impl From<protos::brightness::Easing> for EasingWrapper {
+    fn from(value: protos::brightness::Easing) -> Self {
+        match value {
+            protos::brightness::Easing::LINEAR => EasingWrapper(Easing::Linear),
+            protos::brightness::Easing::EASE_IN => EasingWrapper(Easing::EaseIn),
+            protos::brightness::Easing::EASE_OUT => EasingWrapper(Easing::EaseOut),
+            protos::brightness::Easing::EASE_IN_OUT => EasingWrapper(Easing::EaseInOut),
+        }
+    }
+}
+
 impl TryFrom<protos::key_config::KeyAction> for KeyWrapper {
     type Error = String;
     fn try_from(value: protos::key_config::KeyAction) -> Result<Self, Self::Error> {
@@ -428,4 +502,36 @@ mod tests {
             InputMapping::new(Unknown, vec![Key::Add])
         )
     }
+
+    #[test]
+    fn converts_easing() {
+        assert_eq!(
+            EasingWrapper::from(protos::brightness::Easing::EASE_IN_OUT).easing(),
+            Easing::EaseInOut
+        );
+        assert_eq!(
+            EasingWrapper::from(protos::brightness::Easing::default()).easing(),
+            Easing::Linear
+        );
+    }
+
+    #[test]
+    fn converts_gesture_input_ids() {
+        assert_eq!(
+            InputActionWrapper::from(protos::inputs::InputId::BUTTON_4_DOUBLE_TAP),
+            InputActionWrapper(InputActions::Gesture(Gesture::DoubleTap(4)))
+        );
+        assert_eq!(
+            InputActionWrapper::from(protos::inputs::InputId::CHORD_BUTTONS_3_8),
+            InputActionWrapper(InputActions::Gesture(Gesture::Chord(3, 8)))
+        );
+        for input_id in &protos::inputs::InputId::VALUES[1..] {
+            assert_ne!(
+                InputActionWrapper::from(*input_id),
+                InputActionWrapper(Unknown),
+                "{:?}",
+                input_id
+            );
+        }
+    }
 }
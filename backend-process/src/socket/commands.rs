@@ -1,12 +1,297 @@
 use crate::database::models::ImageMapping;
+use crate::database::operations::DEFAULT_DEVICE_SERIAL;
 use crate::input_handler::InputMapping;
 use firmware_api::display_zones::DisplayZones;
+use firmware_api::fade::Easing;
+use log::warn;
+use messaging::socket::ClientId;
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
 
+/// Commands kept at most for launchpads that are not connected, the oldest are dropped first
+pub const MAX_QUEUED_COMMANDS: usize = 256;
+
+/// How long a command for every launchpad is kept for the ones that connect later
+pub const DEFAULT_SERIAL_COMMAND_LIFETIME: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Clone)]
 pub enum IncomingCommands {
     SetKeyConfig(InputMapping),
     SetDisplayZoneImage(ImageMapping),
     ClearDisplayZoneImage(DisplayZones),
     ClearAllDisplayZoneImages,
+    /// How long a button is held for a long press
+    SetLongPress(Duration),
     SetBootLogo(String),
+    /// Stops the boot logo upload that is going on, see `ServerHandler::start_upload`
+    CancelUpload,
     SetBrightness(u8),
+    FadeBrightness {
+        brightness: u8,
+        duration: Duration,
+        easing: Easing,
+    },
+}
+
+impl IncomingCommands {
+    /// Whether the command is kept for launchpads that are not connected. Everything else is
+    /// restored from the database when they connect (a fade ends at the stored brightness), or
+    /// (cancelling an upload) has nothing to do.
+    pub fn is_queued(&self) -> bool {
+        matches!(self, IncomingCommands::SetBootLogo(_))
+    }
+}
+
+/// A command along with the launchpad it is meant for
+#[derive(Clone)]
+pub struct DeviceCommand {
+    /// Serial number of the target launchpad, empty (`DEFAULT_DEVICE_SERIAL`) targets every launchpad
+    pub device_serial: String,
+    pub command: IncomingCommands,
+    /// The client that sent the command, for anything sent back about it (e.g. upload progress)
+    pub client_id: ClientId,
+}
+
+impl DeviceCommand {
+    pub fn targets(&self, device_serial: &str) -> bool {
+        self.device_serial == DEFAULT_DEVICE_SERIAL || self.device_serial == device_serial
+    }
+}
+
+/// Commands sent while the launchpad they target was not connected, applied once it is.
+///
+/// Only what the database does not restore on connect is queued, see `IncomingCommands::is_queued`.
+#[derive(Default)]
+pub struct CommandQueue {
+    commands: VecDeque<QueuedCommand>,
+}
+
+struct QueuedCommand {
+    device_command: DeviceCommand,
+    /// Launchpads a command for every launchpad was already applied to
+    delivered: HashSet<String>,
+    queued_at: Instant,
+}
+
+impl QueuedCommand {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.device_command.device_serial == DEFAULT_DEVICE_SERIAL
+            && now.saturating_duration_since(self.queued_at) >= DEFAULT_SERIAL_COMMAND_LIFETIME
+    }
+}
+
+impl CommandQueue {
+    /// Queues the command for the launchpads it targets that are not `connected`, it was applied
+    /// to the others already. Commands for every launchpad stay queued for the ones that connect
+    /// within `DEFAULT_SERIAL_COMMAND_LIFETIME`.
+    pub fn push<'a>(
+        &mut self,
+        device_command: DeviceCommand,
+        connected: impl IntoIterator<Item = &'a String>,
+        now: Instant,
+    ) {
+        if !device_command.command.is_queued() {
+            return;
+        }
+        let delivered: HashSet<String> = connected
+            .into_iter()
+            .filter(|serial| device_command.targets(serial))
+            .cloned()
+            .collect();
+        if device_command.device_serial != DEFAULT_DEVICE_SERIAL && !delivered.is_empty() {
+            return;
+        }
+
+        self.commands.retain(|queued| !queued.is_expired(now));
+        if self.commands.len() == MAX_QUEUED_COMMANDS
+            && let Some(dropped) = self.commands.pop_front()
+        {
+            warn!(
+                "Too many commands queued, dropped the oldest one for '{}'",
+                dropped.device_command.device_serial
+            );
+        }
+        self.commands.push_back(QueuedCommand {
+            device_command,
+            delivered,
+            queued_at: now,
+        });
+    }
+
+    /// The commands for the launchpad that just connected, in the order they were sent. Commands
+    /// for every launchpad are handed to each launchpad once and stay queued for the others
+    /// until they expire.
+    pub fn take_for(&mut self, device_serial: &str, now: Instant) -> Vec<DeviceCommand> {
+        let mut taken = Vec::new();
+        self.commands.retain_mut(|queued| {
+            if queued.is_expired(now) {
+                return false;
+            }
+            if !queued.device_command.targets(device_serial)
+                || queued.delivered.contains(device_serial)
+            {
+                return true;
+            }
+            if queued.device_command.device_serial == DEFAULT_DEVICE_SERIAL {
+                queued.delivered.insert(device_serial.to_string());
+                taken.push(queued.device_command.clone());
+                return true;
+            }
+            taken.push(queued.device_command.clone());
+            false
+        });
+
+        taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(device_serial: &str) -> DeviceCommand {
+        DeviceCommand {
+            device_serial: device_serial.to_string(),
+            command: IncomingCommands::SetBrightness(50),
+            client_id: ClientId(0),
+        }
+    }
+
+    fn boot_logo(device_serial: &str, path: &str) -> DeviceCommand {
+        DeviceCommand {
+            device_serial: device_serial.to_string(),
+            command: IncomingCommands::SetBootLogo(path.to_string()),
+            client_id: ClientId(0),
+        }
+    }
+
+    fn boot_logos(device_commands: Vec<DeviceCommand>) -> Vec<String> {
+        device_commands
+            .into_iter()
+            .map(|device_command| match device_command.command {
+                IncomingCommands::SetBootLogo(path) => path,
+                _ => panic!("expected a boot logo"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn commands_target_their_launchpad_or_every_launchpad() {
+        assert!(command("AKP05-LEFT").targets("AKP05-LEFT"));
+        assert!(!command("AKP05-LEFT").targets("AKP05-RIGHT"));
+        assert!(command(DEFAULT_DEVICE_SERIAL).targets("AKP05-LEFT"));
+        assert!(command(DEFAULT_DEVICE_SERIAL).targets("AKP05-RIGHT"));
+    }
+
+    #[test]
+    fn stored_commands_are_not_queued() {
+        let now = Instant::now();
+        let mut queue = CommandQueue::default();
+
+        queue.push(command("AKP05-LEFT"), [], now);
+        // The database restores the brightness the fade ends at
+        queue.push(
+            DeviceCommand {
+                device_serial: String::from("AKP05-LEFT"),
+                command: IncomingCommands::FadeBrightness {
+                    brightness: 10,
+                    duration: Duration::from_secs(1),
+                    easing: Easing::Linear,
+                },
+                client_id: ClientId(0),
+            },
+            [],
+            now,
+        );
+
+        assert!(queue.take_for("AKP05-LEFT", now).is_empty());
+    }
+
+    #[test]
+    fn commands_for_connected_launchpads_are_not_queued() {
+        let connected = ["AKP05-LEFT".to_string()];
+        let now = Instant::now();
+        let mut queue = CommandQueue::default();
+
+        queue.push(boot_logo("AKP05-LEFT", "left.png"), &connected, now);
+
+        assert!(queue.take_for("AKP05-LEFT", now).is_empty());
+    }
+
+    #[test]
+    fn launchpads_take_their_commands_in_order() {
+        let now = Instant::now();
+        let mut queue = CommandQueue::default();
+        queue.push(boot_logo("AKP05-LEFT", "first.png"), [], now);
+        queue.push(boot_logo("AKP05-RIGHT", "right.png"), [], now);
+        queue.push(boot_logo("AKP05-LEFT", "second.png"), [], now);
+
+        assert_eq!(
+            boot_logos(queue.take_for("AKP05-LEFT", now)),
+            ["first.png", "second.png"]
+        );
+        assert!(queue.take_for("AKP05-LEFT", now).is_empty());
+        assert_eq!(
+            boot_logos(queue.take_for("AKP05-RIGHT", now)),
+            ["right.png"]
+        );
+    }
+
+    #[test]
+    fn commands_for_every_launchpad_reach_each_launchpad_once() {
+        let connected = ["AKP05-LEFT".to_string()];
+        let now = Instant::now();
+        let mut queue = CommandQueue::default();
+        queue.push(
+            boot_logo(DEFAULT_DEVICE_SERIAL, "every.png"),
+            &connected,
+            now,
+        );
+        queue.push(boot_logo("AKP05-MIDDLE", "middle.png"), [], now);
+
+        assert!(queue.take_for("AKP05-LEFT", now).is_empty());
+        assert_eq!(
+            boot_logos(queue.take_for("AKP05-MIDDLE", now)),
+            ["every.png", "middle.png"]
+        );
+        assert!(queue.take_for("AKP05-MIDDLE", now).is_empty());
+        assert_eq!(
+            boot_logos(queue.take_for("AKP05-RIGHT", now)),
+            ["every.png"]
+        );
+    }
+
+    #[test]
+    fn the_oldest_commands_are_dropped_when_the_queue_is_full() {
+        let now = Instant::now();
+        let mut queue = CommandQueue::default();
+        for index in 0..=MAX_QUEUED_COMMANDS {
+            queue.push(boot_logo("AKP05-LEFT", &format!("{index}.png")), [], now);
+        }
+
+        let taken = boot_logos(queue.take_for("AKP05-LEFT", now));
+
+        assert_eq!(taken.len(), MAX_QUEUED_COMMANDS);
+        assert_eq!(taken.first().unwrap(), "1.png");
+        assert_eq!(taken.last().unwrap(), &format!("{MAX_QUEUED_COMMANDS}.png"));
+    }
+
+    #[test]
+    fn commands_for_every_launchpad_expire() {
+        let now = Instant::now();
+        let later = now + DEFAULT_SERIAL_COMMAND_LIFETIME;
+        let mut queue = CommandQueue::default();
+        queue.push(boot_logo(DEFAULT_DEVICE_SERIAL, "every.png"), [], now);
+        queue.push(boot_logo("AKP05-LEFT", "left.png"), [], now);
+
+        assert_eq!(
+            boot_logos(queue.take_for("AKP05-RIGHT", now)),
+            ["every.png"]
+        );
+        assert!(queue.take_for("AKP05-MIDDLE", later).is_empty());
+        assert_eq!(
+            boot_logos(queue.take_for("AKP05-LEFT", later)),
+            ["left.png"]
+        );
+    }
 }
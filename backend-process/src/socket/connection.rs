@@ -1,14 +1,21 @@
 use crate::database::models::ImageMapping;
 use crate::database::operations::Operations;
 use crate::input_handler::InputMapping;
-use crate::protobuf_conversion::DisplayZoneWrapper;
-use crate::socket::commands::IncomingCommands;
+use crate::protobuf_conversion::{DisplayZoneWrapper, EasingWrapper};
+use crate::socket::commands::{DeviceCommand, IncomingCommands};
+use firmware_api::transfer::CancellationToken;
+use log::debug;
+use messaging::protos::server_message::ServerMessage;
+use messaging::protos::server_message::server_message::Message as ServerMessageKind;
 use messaging::protos::top_level::TopLevel;
 use messaging::protos::top_level::top_level::Command;
+use messaging::protos::upload_progress::UploadProgress;
 use messaging::socket;
-use messaging::socket::MessageReceiver;
+use messaging::socket::ClientId;
 use protobuf::Message;
+use std::collections::{HashMap, VecDeque};
 use std::io::{Error, ErrorKind};
+use std::time::Duration;
 
 /// Responsible for handling the database writes and reading from sockets
 ///
@@ -16,6 +23,10 @@ use std::io::{Error, ErrorKind};
 pub struct ServerHandler<'a> {
     server: socket::Server,
     operations: &'a Operations,
+    /// Cancels the upload going on to each launchpad (by serial)
+    uploads: HashMap<String, CancellationToken>,
+    /// Commands read while looking for cancellations, handed out before any new ones
+    deferred: VecDeque<DeviceCommand>,
 }
 
 impl<'a> ServerHandler<'a> {
@@ -23,21 +34,104 @@ impl<'a> ServerHandler<'a> {
         Ok(Self {
             server: socket::Server::new()?,
             operations,
+            uploads: HashMap::new(),
+            deferred: VecDeque::new(),
         })
     }
 
     /// Checks if there is a message from the connected clients.
     ///
     /// It will either:
-    /// - Return `Ok` with the successfully parsed command type and its data (see `IncomingCommands`)
+    /// - Return `Ok` with the successfully parsed command type, its data (see `IncomingCommands`),
+    ///   the serial of the launchpad it targets and the client that sent it
     ///   - The database is written to if the message contains data that should be persisted (i.e. key mappings)
     /// - An Error if there is no message or the received command could not be parsed
-    pub fn handle_command_and_persist_config(&mut self) -> Result<IncomingCommands, Error> {
-        let message = self.server.read_message()?;
+    pub fn handle_command_and_persist_config(&mut self) -> Result<DeviceCommand, Error> {
+        if let Some(device_command) = self.deferred.pop_front() {
+            return Ok(device_command);
+        }
+        self.read_command()
+    }
+
+    fn read_command(&mut self) -> Result<DeviceCommand, Error> {
+        let (client_id, message) = self.server.read_message_from()?;
 
         let top_level = TopLevel::parse_from_bytes(message.as_slice())?;
+        let device_serial = top_level.device_serial;
+
+        let command = self.persist_command(&device_serial, top_level.command)?;
+
+        Ok(DeviceCommand {
+            device_serial,
+            command,
+            client_id,
+        })
+    }
+
+    /// The token that `IncomingCommands::CancelUpload` cancels while an upload to the launchpad
+    /// goes on, until `finish_upload`
+    pub fn start_upload(&mut self, device_serial: &str) -> CancellationToken {
+        let cancellation = CancellationToken::new();
+        self.uploads
+            .insert(device_serial.to_string(), cancellation.clone());
+        cancellation
+    }
+
+    pub fn finish_upload(&mut self, device_serial: &str) {
+        self.uploads.remove(device_serial);
+    }
 
-        match top_level.command {
+    /// Reads the commands sent while uploads are going on and cancels the uploads they ask to.
+    /// Every other command is kept for `handle_command_and_persist_config`, in the order it
+    /// was sent.
+    pub fn check_for_cancellations(&mut self) {
+        loop {
+            match self.read_command() {
+                Ok(device_command) => match device_command.command {
+                    IncomingCommands::CancelUpload => {
+                        for (device_serial, cancellation) in &self.uploads {
+                            if device_command.targets(device_serial) {
+                                cancellation.cancel();
+                            }
+                        }
+                    }
+                    _ => self.deferred.push_back(device_command),
+                },
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+                Err(e) => debug!("No known command was handled during an upload: {}", e),
+            }
+        }
+    }
+
+    /// Lets the client that asked for an image upload know how far along it is. Fails with
+    /// `WouldBlock` when the client is not ready for it, that update is skipped.
+    pub fn send_upload_progress(
+        &mut self,
+        client_id: ClientId,
+        device_serial: &str,
+        bytes_sent: usize,
+        total_bytes: usize,
+    ) -> Result<(), Error> {
+        let message = ServerMessage {
+            message: Some(ServerMessageKind::UploadProgress(UploadProgress {
+                device_serial: device_serial.to_string(),
+                bytes_sent: bytes_sent as u64,
+                total_bytes: total_bytes as u64,
+                ..UploadProgress::default()
+            })),
+            ..ServerMessage::default()
+        };
+
+        self.server
+            .send_message_to(client_id, message.write_to_bytes()?.as_slice())
+    }
+
+    fn persist_command(
+        &self,
+        device_serial: &str,
+        command: Option<Command>,
+    ) -> Result<IncomingCommands, Error> {
+        match command {
             Some(command) => match command {
                 Command::KeyConfigCommand(command) => {
                     let key_config_model: crate::database::models::InputMapping =
@@ -51,19 +145,37 @@ impl<'a> ServerHandler<'a> {
                     let mappings: InputMapping = key_config_model.clone().into();
 
                     self.operations
-                        .set_mapping_for_input(key_config_model)
+                        .set_mapping_for_input(device_serial, key_config_model)
                         .map_err(|e| Error::other(format!("Database operation failed: {}", e)))?;
 
                     return Ok(IncomingCommands::SetKeyConfig(mappings));
                 }
+                Command::SetLongPressCommand(command) => {
+                    return match command.duration_ms {
+                        0 => Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "A long press has to last longer than 0 ms!",
+                        )),
+                        duration_ms => {
+                            let long_press = Duration::from_millis(duration_ms.into());
+                            self.operations
+                                .set_long_press(device_serial, long_press)
+                                .ok();
+                            Ok(IncomingCommands::SetLongPress(long_press))
+                        }
+                    };
+                }
                 Command::SetBootLogoCommand(command) => {
                     return Ok(IncomingCommands::SetBootLogo(command.image_path));
                 }
+                Command::CancelUploadCommand(_) => {
+                    return Ok(IncomingCommands::CancelUpload);
+                }
                 Command::SetBrightnessCommand(command) => {
                     return match command.brightness_value {
                         0..=100 => {
                             self.operations
-                                .set_brightness(command.brightness_value as u8)
+                                .set_brightness(device_serial, command.brightness_value as u8)
                                 .ok();
                             Ok(IncomingCommands::SetBrightness(
                                 command.brightness_value as u8,
@@ -76,11 +188,32 @@ impl<'a> ServerHandler<'a> {
                         )),
                     };
                 }
+                Command::FadeBrightnessCommand(command) => {
+                    return match command.brightness_value {
+                        0..=100 => {
+                            // Stored straight away, the fade itself is not worth restoring
+                            self.operations
+                                .set_brightness(device_serial, command.brightness_value as u8)
+                                .ok();
+                            Ok(IncomingCommands::FadeBrightness {
+                                brightness: command.brightness_value as u8,
+                                duration: Duration::from_millis(command.duration_ms.into()),
+                                easing: EasingWrapper::from(command.easing.enum_value_or_default())
+                                    .easing(),
+                            })
+                        }
+
+                        _ => Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "Brightness value was not in the range 0 to 100!",
+                        )),
+                    };
+                }
                 Command::SetDisplayZoneImageCommand(command) => {
                     if let Ok(display_zone_image_model) = command.try_into() {
                         let database_copy: ImageMapping = display_zone_image_model;
                         self.operations
-                            .set_image_for_display_zone(database_copy.clone())
+                            .set_image_for_display_zone(device_serial, database_copy.clone())
                             .map_err(Error::other)?;
 
                         return Ok(IncomingCommands::SetDisplayZoneImage(database_copy));
@@ -88,7 +221,9 @@ impl<'a> ServerHandler<'a> {
                 }
                 Command::ClearAllDisplayZoneImagesCommand(command) => {
                     if command.unpersist_images {
-                        self.operations.clear_all_display_zone_images().ok();
+                        self.operations
+                            .clear_all_display_zone_images(device_serial)
+                            .ok();
                     }
                     return Ok(IncomingCommands::ClearAllDisplayZoneImages);
                 }
@@ -98,7 +233,10 @@ impl<'a> ServerHandler<'a> {
                             DisplayZoneWrapper::try_from(protobuf_enum)
                     {
                         self.operations
-                            .clear_image_for_display_zone(display_zone_wrapper.display_zone())
+                            .clear_image_for_display_zone(
+                                device_serial,
+                                display_zone_wrapper.display_zone(),
+                            )
                             .ok();
                         return Ok(IncomingCommands::ClearDisplayZoneImage(
                             display_zone_wrapper.display_zone(),
@@ -0,0 +1,65 @@
+use firmware_api::capture::{Capture, Direction};
+use firmware_api::decoder::{DecodedCommand, Decoder, summarise};
+use firmware_api::inputs::InputActions;
+use firmware_api::inputs::input_buffer::BUFFER_SIZE_13;
+use std::env;
+use std::fs::File;
+
+const VENDOR_ID: u16 = 0x0300;
+const PRODUCT_ID: u16 = 0x3004;
+
+/// Prints an annotated timeline of the traffic between the host and the launchpad
+///
+/// Capture with USBPcap or usbmon, starting before the launchpad is plugged in so its
+/// device descriptor is part of the capture.
+fn main() {
+    let capture_path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| panic!("Usage: decode_capture <path to pcap or pcapng file>"));
+    let capture = Capture::read(
+        File::open(&capture_path).unwrap_or_else(|e| panic!("Failed to open capture: {}", e)),
+    )
+    .unwrap_or_else(|e| panic!("Failed to read capture: {}", e));
+
+    let transfers: Vec<_> = capture.interrupt_transfers(VENDOR_ID, PRODUCT_ID).collect();
+    if transfers.is_empty() {
+        panic!(
+            "No traffic for {:04x}:{:04x} found, was the capture started before the device was plugged in?",
+            VENDOR_ID, PRODUCT_ID
+        );
+    }
+
+    let start = transfers[0].timestamp;
+    let mut decoder = Decoder::new();
+
+    for transfer in transfers {
+        let elapsed = transfer.timestamp.saturating_sub(start).as_secs_f64();
+
+        match transfer.direction() {
+            Direction::HostToDevice => {
+                // hidapi strips the report id (always 0) before the frame goes on the wire
+                let frame = [&[0], transfer.data.as_slice()].concat();
+                let command = decoder.decode(&frame);
+
+                // Image data is not interesting to look at, only the transfers around it
+                if !matches!(command, DecodedCommand::ImageDataPacket(_)) {
+                    println!("[{:>12.6}] host -> device  {}", elapsed, command);
+                }
+            }
+            Direction::DeviceToHost => {
+                let mut report = [0; BUFFER_SIZE_13];
+                let length = transfer.data.len().min(BUFFER_SIZE_13);
+                report[..length].copy_from_slice(&transfer.data[..length]);
+
+                match InputActions::from(report) {
+                    InputActions::Unknown => println!(
+                        "[{:>12.6}] device -> host  Unknown({})",
+                        elapsed,
+                        summarise(&transfer.data)
+                    ),
+                    action => println!("[{:>12.6}] device -> host  {:?}", elapsed, action),
+                }
+            }
+        }
+    }
+}
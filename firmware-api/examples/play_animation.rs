@@ -0,0 +1,62 @@
+use firmware_api::animation::{Animation, Animator};
+use firmware_api::device::{Device, FunctionHandler, HidDeviceWrapper};
+use firmware_api::display_zones::DisplayZones;
+use hidapi::HidApi;
+use std::env;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+fn main() {
+    let gif_path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| panic!("Usage: play_animation <path to animated gif>"));
+    let animation = Animation::from_gif(
+        &std::fs::read(&gif_path).unwrap_or_else(|e| panic!("Failed to read GIF: {}", e)),
+    )
+    .unwrap_or_else(|e| panic!("Failed to decode GIF: {}", e));
+
+    let hid_api = HidApi::new().unwrap_or_else(|e| panic!("Failed to initialize HID API: {}", e));
+
+    let hid_device = hid_api
+        .open(0x0300, 0x3004)
+        .unwrap_or_else(|e| panic!("Failed to open device: {}", e));
+
+    let device = Device::new(
+        HidDeviceWrapper::new(hid_device, false),
+        FunctionHandler::new(|action| println!("{:?}", action)),
+    );
+
+    device
+        .refresh()
+        .unwrap_or_else(|e| panic!("Failed to refresh device: {}", e));
+
+    let _ = device.clear_all_images();
+
+    let mut animator = Animator::new();
+    animator
+        .play(
+            &[
+                DisplayZones::Button1,
+                DisplayZones::Button7,
+                DisplayZones::Touchscreen2,
+            ],
+            &animation,
+        )
+        .unwrap_or_else(|e| panic!("Failed to prepare animation: {}", e));
+
+    println!("Playing {} frames for 10 seconds", animation.frame_count());
+    let start = Instant::now();
+
+    while start.elapsed() < Duration::from_secs(10) {
+        device
+            .read_input()
+            .unwrap_or_else(|e| println!("Failed to read input: {}", e));
+        animator
+            .tick(&device, Instant::now())
+            .unwrap_or_else(|e| panic!("Failed to show frame: {}", e));
+
+        sleep(Duration::from_millis(10));
+    }
+
+    animator.stop_all();
+}
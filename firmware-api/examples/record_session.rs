@@ -0,0 +1,43 @@
+use firmware_api::device::{Device, FunctionHandler, HidDeviceWrapper};
+use firmware_api::profiles;
+use firmware_api::session::SessionRecorder;
+use hidapi::HidApi;
+use std::env;
+
+/// Prints the inputs of the first launchpad found while recording them to a session file,
+/// which `Session::open` plays back later
+fn main() {
+    let session_path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| panic!("Usage: record_session <path to write the session to>"));
+
+    let hid_api = HidApi::new().unwrap_or_else(|e| panic!("Failed to initialize HID API: {}", e));
+    let info = hid_api
+        .device_list()
+        .find(|info| profiles::find_profile(info.vendor_id(), info.product_id()).is_some())
+        .unwrap_or_else(|| panic!("No launchpad found"));
+    let profile = profiles::find_profile(info.vendor_id(), info.product_id()).unwrap();
+    let hid_device = info
+        .open_device(&hid_api)
+        .unwrap_or_else(|e| panic!("Failed to open device: {}", e));
+
+    let recorder = SessionRecorder::create(
+        &session_path,
+        FunctionHandler::new(|action| println!("{:?}", action)),
+    )
+    .unwrap_or_else(|e| panic!("Failed to create session file: {}", e));
+
+    let mut device = Device::new(HidDeviceWrapper::new(hid_device, false), recorder);
+    device.set_profile(profile);
+    device.set_serial_number(info.serial_number().unwrap_or_default());
+
+    loop {
+        device
+            .read_input()
+            .unwrap_or_else(|e| println!("Failed to read input: {}", e));
+        device
+            .handler()
+            .flush()
+            .unwrap_or_else(|e| panic!("Failed to record input: {}", e));
+    }
+}
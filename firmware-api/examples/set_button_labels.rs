@@ -0,0 +1,69 @@
+use firmware_api::device::{Device, FunctionHandler, HidDeviceWrapper};
+use firmware_api::display_zones::DisplayZones;
+use firmware_api::label::{Label, load_font};
+use hidapi::HidApi;
+use std::env;
+
+/// Any TrueType font will do, this one ships with most Linux distributions
+const DEFAULT_FONT_PATH: &str = "/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf";
+
+fn main() {
+    let font_path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_FONT_PATH.to_string());
+    let font = load_font(&font_path)
+        .unwrap_or_else(|e| panic!("Failed to load font {}: {}", font_path, e));
+
+    let hid_api = HidApi::new().unwrap_or_else(|e| panic!("Failed to initialize HID API: {}", e));
+
+    let hid_device = hid_api
+        .open(0x0300, 0x3004)
+        .unwrap_or_else(|e| panic!("Failed to open device: {}", e));
+
+    let device = Device::new(
+        HidDeviceWrapper::new(hid_device, false),
+        FunctionHandler::new(|action| println!("{:?}", action)),
+    );
+
+    device
+        .refresh()
+        .unwrap_or_else(|e| panic!("Failed to refresh device: {}", e));
+
+    let _ = device.clear_all_images();
+
+    let labels = [
+        (
+            DisplayZones::Button1,
+            "Deploy",
+            [255, 255, 255],
+            [0, 120, 60],
+        ),
+        (
+            DisplayZones::Button2,
+            "Mute",
+            [255, 255, 255],
+            [180, 20, 20],
+        ),
+        (
+            DisplayZones::Button3,
+            "Next track",
+            [0, 0, 0],
+            [240, 200, 0],
+        ),
+        (
+            DisplayZones::Touchscreen1,
+            "Volume",
+            [255, 255, 255],
+            [30, 30, 90],
+        ),
+    ];
+
+    for (display_zone, text, foreground, background) in labels {
+        println!("Setting {:?} to \"{}\"", display_zone, text);
+        let label = Label::new(text, font.clone()).with_colours(foreground, background);
+
+        device
+            .set_display_zone_label(display_zone, &label)
+            .unwrap_or_else(|e| panic!("Failed to set label: {}", e));
+    }
+}
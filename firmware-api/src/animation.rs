@@ -0,0 +1,431 @@
+//! Plays animations (animated GIFs or a sequence of frames) on the display zones.
+//!
+//! Animations are driven by an `Animator`, one of the [tick-driven helpers](crate#tick-driven-helpers):
+//! `Animator::tick` uploads every frame that is due.
+//!
+//! ```no_run
+//! # use firmware_api::animation::{Animation, Animator};
+//! # use firmware_api::device::{Device, FunctionHandler, HidDeviceWrapper};
+//! # use firmware_api::display_zones::DisplayZones;
+//! # use std::time::Instant;
+//! # fn run(device: Device<HidDeviceWrapper, FunctionHandler>) {
+//! let animation = Animation::from_gif(&std::fs::read("spinner.gif").unwrap()).unwrap();
+//! let mut animator = Animator::new();
+//! animator
+//!     .play(&[DisplayZones::Button1, DisplayZones::Button2], &animation)
+//!     .unwrap();
+//!
+//! loop {
+//!     device.read_input().ok();
+//!     animator.tick(&device, Instant::now()).ok();
+//! }
+//! # }
+//! ```
+use crate::device::{Device, HidDeviceOperations, InputHandler};
+use crate::display_zones::DisplayZones;
+use crate::error::Result;
+use crate::image_processing::{Resolution, ScalingMode, encode_jpeg, fit_image};
+use image::codecs::gif::GifDecoder;
+use image::{AnimationDecoder, DynamicImage, ImageResult};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Used when a GIF does not specify a delay, which is what browsers do too
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// A decoded sequence of frames, independent of the zone it will be shown on
+#[derive(Clone)]
+pub struct Animation {
+    frames: Vec<(DynamicImage, Duration)>,
+}
+
+impl Animation {
+    /// Decodes every frame of an animated GIF, keeping the delays stored in the file
+    pub fn from_gif(gif: &[u8]) -> ImageResult<Self> {
+        let frames = GifDecoder::new(Cursor::new(gif))?
+            .into_frames()
+            .map(|frame| {
+                let frame = frame?;
+                let delay = match Duration::from(frame.delay()) {
+                    Duration::ZERO => DEFAULT_FRAME_DELAY,
+                    delay => delay,
+                };
+                Ok((DynamicImage::ImageRgba8(frame.into_buffer()), delay))
+            })
+            .collect::<ImageResult<Vec<_>>>()?;
+
+        Ok(Self { frames })
+    }
+
+    /// Creates an animation from individually encoded images shown at a fixed rate
+    ///
+    /// * `frames` - encoded images (any format supported by `image_processing`)
+    /// * `frames_per_second` - how many frames to show every second
+    pub fn from_frames(frames: &[impl AsRef<[u8]>], frames_per_second: u32) -> ImageResult<Self> {
+        let delay = Duration::from_secs(1) / frames_per_second.max(1);
+
+        let frames = frames
+            .iter()
+            .map(|frame| Ok((image::load_from_memory(frame.as_ref())?, delay)))
+            .collect::<ImageResult<Vec<_>>>()?;
+
+        Ok(Self { frames })
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Fits and encodes every frame for the given resolution so they can be sent as-is
+    fn encode_for(
+        &self,
+        resolution: Resolution,
+        scaling_mode: ScalingMode,
+    ) -> ImageResult<Vec<EncodedFrame>> {
+        self.frames
+            .iter()
+            .map(|(image, delay)| {
+                Ok(EncodedFrame {
+                    image: encode_jpeg(&fit_image(image, resolution, scaling_mode))?,
+                    delay: *delay,
+                })
+            })
+            .collect()
+    }
+}
+
+struct EncodedFrame {
+    image: Vec<u8>,
+    delay: Duration,
+}
+
+struct Playback {
+    frames: Arc<Vec<EncodedFrame>>,
+    current_frame: usize,
+    /// `None` when the first frame has not been shown yet
+    next_frame_at: Option<Instant>,
+    /// Time left until the next frame when the playback was paused
+    paused_with: Option<Duration>,
+}
+
+/// Keeps track of which animation is playing on which display zone
+#[derive(Default)]
+pub struct Animator {
+    playbacks: HashMap<DisplayZones, Playback>,
+    scaling_mode: ScalingMode,
+}
+
+impl Animator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Controls how frames that do not match a zone's aspect ratio are fitted,
+    /// only applies to animations started afterward
+    pub fn set_scaling_mode(&mut self, scaling_mode: ScalingMode) {
+        self.scaling_mode = scaling_mode;
+    }
+
+    /// Starts playing `animation` on every zone in `display_zones` (in sync),
+    /// replacing anything that is already playing on those zones
+    pub fn play(
+        &mut self,
+        display_zones: &[DisplayZones],
+        animation: &Animation,
+    ) -> ImageResult<()> {
+        let mut encoded: HashMap<Resolution, Arc<Vec<EncodedFrame>>> = HashMap::new();
+
+        for display_zone in display_zones {
+            let resolution = display_zone.resolution();
+            let frames = match encoded.get(&resolution) {
+                Some(frames) => frames.clone(),
+                None => {
+                    let frames = Arc::new(animation.encode_for(resolution, self.scaling_mode)?);
+                    encoded.insert(resolution, frames.clone());
+                    frames
+                }
+            };
+
+            self.playbacks.insert(
+                *display_zone,
+                Playback {
+                    frames,
+                    current_frame: 0,
+                    next_frame_at: None,
+                    paused_with: None,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Stops the animation on the zone, leaving the last shown frame on screen
+    pub fn stop(&mut self, display_zone: DisplayZones) {
+        self.playbacks.remove(&display_zone);
+    }
+
+    pub fn stop_all(&mut self) {
+        self.playbacks.clear();
+    }
+
+    /// Freezes the animation on its current frame until `resume` is called
+    pub fn pause(&mut self, display_zone: DisplayZones, now: Instant) {
+        if let Some(playback) = self.playbacks.get_mut(&display_zone)
+            && playback.paused_with.is_none()
+        {
+            playback.paused_with = Some(
+                playback
+                    .next_frame_at
+                    .map_or(Duration::ZERO, |next| next.saturating_duration_since(now)),
+            );
+        }
+    }
+
+    pub fn resume(&mut self, display_zone: DisplayZones, now: Instant) {
+        if let Some(playback) = self.playbacks.get_mut(&display_zone)
+            && let Some(remaining) = playback.paused_with.take()
+            && playback.next_frame_at.is_some()
+        {
+            playback.next_frame_at = Some(now + remaining);
+        }
+    }
+
+    pub fn is_playing(&self, display_zone: DisplayZones) -> bool {
+        self.playbacks
+            .get(&display_zone)
+            .is_some_and(|playback| playback.paused_with.is_none())
+    }
+
+    /// The earliest time a frame needs to be shown, useful to know how long the caller can sleep
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.playbacks
+            .values()
+            .filter(|playback| playback.paused_with.is_none() && !playback.frames.is_empty())
+            .map(|playback| playback.next_frame_at.unwrap_or(Instant::now()))
+            .min()
+    }
+
+    /// Uploads every frame that is due at `now`, then refreshes the device once
+    /// so that all zones change at the same time.
+    ///
+    /// Returns whether any frame was sent.
+    pub fn tick<H: HidDeviceOperations, I: InputHandler>(
+        &mut self,
+        device: &Device<H, I>,
+        now: Instant,
+    ) -> Result<bool> {
+        let due_frames = self.advance(now);
+
+        for (display_zone, frames, frame) in due_frames.iter() {
+            device.set_display_zone_image_from_bytes(*display_zone, &frames[*frame].image)?;
+        }
+
+        if due_frames.is_empty() {
+            return Ok(false);
+        }
+
+        device.refresh()?;
+        Ok(true)
+    }
+
+    /// Moves every playback that is due at `now` on to its next frame,
+    /// returning the frames that need to be shown
+    fn advance(&mut self, now: Instant) -> Vec<(DisplayZones, Arc<Vec<EncodedFrame>>, usize)> {
+        let mut due_frames = Vec::new();
+
+        for (display_zone, playback) in self.playbacks.iter_mut() {
+            if playback.paused_with.is_some() || playback.frames.is_empty() {
+                continue;
+            }
+            if playback.next_frame_at.is_some_and(|next| next > now) {
+                continue;
+            }
+
+            let frame = playback.current_frame;
+            due_frames.push((*display_zone, playback.frames.clone(), frame));
+
+            let delay = playback.frames[frame].delay;
+            // Skip ahead instead of trying to catch up if the ticks were too far apart
+            playback.next_frame_at = match playback.next_frame_at {
+                Some(scheduled) if scheduled + delay > now => Some(scheduled + delay),
+                _ => Some(now + delay),
+            };
+            playback.current_frame = (frame + 1) % playback.frames.len();
+        }
+
+        due_frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, RgbImage};
+
+    fn two_frame_animation() -> Animation {
+        let frames: Vec<Vec<u8>> = (0..2)
+            .map(|_| {
+                let mut bytes = Vec::new();
+                DynamicImage::ImageRgb8(RgbImage::new(16, 16))
+                    .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+                    .unwrap();
+                bytes
+            })
+            .collect();
+
+        Animation::from_frames(&frames, 10).unwrap()
+    }
+
+    fn shown_frames(animator: &mut Animator, now: Instant) -> Vec<(DisplayZones, usize)> {
+        let mut frames: Vec<_> = animator
+            .advance(now)
+            .into_iter()
+            .map(|(display_zone, _, frame)| (display_zone, frame))
+            .collect();
+        frames.sort_by_key(|(display_zone, _)| u8::from(*display_zone));
+        frames
+    }
+
+    #[test]
+    fn animations_without_frames_have_no_deadline() {
+        let mut animator = Animator::new();
+        animator
+            .play(
+                &[DisplayZones::Button1],
+                &Animation::from_frames(&[] as &[Vec<u8>], 10).unwrap(),
+            )
+            .unwrap();
+
+        assert!(shown_frames(&mut animator, Instant::now()).is_empty());
+        assert_eq!(animator.next_deadline(), None);
+    }
+
+    #[test]
+    fn first_frame_is_shown_immediately() {
+        let mut animator = Animator::new();
+        animator
+            .play(&[DisplayZones::Button1], &two_frame_animation())
+            .unwrap();
+
+        assert_eq!(
+            shown_frames(&mut animator, Instant::now()),
+            vec![(DisplayZones::Button1, 0)]
+        );
+    }
+
+    #[test]
+    fn frames_advance_and_loop_with_the_frame_rate() {
+        let mut animator = Animator::new();
+        animator
+            .play(&[DisplayZones::Button1], &two_frame_animation())
+            .unwrap();
+        let start = Instant::now();
+
+        assert_eq!(shown_frames(&mut animator, start).len(), 1);
+        // Not due yet
+        assert!(shown_frames(&mut animator, start + Duration::from_millis(50)).is_empty());
+        assert_eq!(
+            shown_frames(&mut animator, start + Duration::from_millis(100)),
+            vec![(DisplayZones::Button1, 1)]
+        );
+        assert_eq!(
+            shown_frames(&mut animator, start + Duration::from_millis(200)),
+            vec![(DisplayZones::Button1, 0)]
+        );
+    }
+
+    #[test]
+    fn paused_zones_do_not_advance() {
+        let mut animator = Animator::new();
+        animator
+            .play(
+                &[DisplayZones::Button1, DisplayZones::Touchscreen1],
+                &two_frame_animation(),
+            )
+            .unwrap();
+        let start = Instant::now();
+        shown_frames(&mut animator, start);
+
+        animator.pause(DisplayZones::Button1, start);
+        assert!(!animator.is_playing(DisplayZones::Button1));
+        assert_eq!(
+            shown_frames(&mut animator, start + Duration::from_millis(100)),
+            vec![(DisplayZones::Touchscreen1, 1)]
+        );
+
+        animator.resume(DisplayZones::Button1, start + Duration::from_millis(500));
+        assert_eq!(
+            shown_frames(&mut animator, start + Duration::from_millis(600)),
+            vec![(DisplayZones::Touchscreen1, 0), (DisplayZones::Button1, 1)]
+        );
+    }
+
+    #[test]
+    fn playing_on_a_zone_replaces_the_current_animation() {
+        let mut animator = Animator::new();
+        animator
+            .play(&[DisplayZones::Button1], &two_frame_animation())
+            .unwrap();
+        let start = Instant::now();
+        shown_frames(&mut animator, start);
+
+        animator
+            .play(&[DisplayZones::Button1], &two_frame_animation())
+            .unwrap();
+
+        // Starts again from the first frame straight away
+        assert_eq!(
+            shown_frames(&mut animator, start),
+            vec![(DisplayZones::Button1, 0)]
+        );
+    }
+
+    #[test]
+    fn stopped_zones_are_no_longer_updated() {
+        let mut animator = Animator::new();
+        animator
+            .play(&[DisplayZones::Button1], &two_frame_animation())
+            .unwrap();
+
+        animator.stop(DisplayZones::Button1);
+
+        assert!(shown_frames(&mut animator, Instant::now()).is_empty());
+        assert_eq!(animator.next_deadline(), None);
+    }
+
+    #[test]
+    fn gif_frames_and_delays_are_decoded() {
+        use image::codecs::gif::GifEncoder;
+        use image::{Delay, Frame, RgbaImage};
+
+        let mut gif = Vec::new();
+        GifEncoder::new(&mut gif)
+            .encode_frames((0..3).map(|_| {
+                Frame::from_parts(
+                    RgbaImage::new(8, 8),
+                    0,
+                    0,
+                    Delay::from_numer_denom_ms(40, 1),
+                )
+            }))
+            .unwrap();
+
+        let animation = Animation::from_gif(&gif).unwrap();
+
+        assert_eq!(animation.frame_count(), 3);
+        assert_eq!(animation.frames[0].1, Duration::from_millis(40));
+    }
+
+    #[test]
+    fn frames_are_encoded_for_the_zone_resolution() {
+        let encoded = two_frame_animation()
+            .encode_for(DisplayZones::Touchscreen2.resolution(), ScalingMode::Crop)
+            .unwrap();
+
+        let frame = image::load_from_memory(&encoded[0].image).unwrap();
+        assert_eq!((frame.width(), frame.height()), (176, 112));
+    }
+}
@@ -0,0 +1,348 @@
+//! An async handle to the launchpad, enabled with the `async` feature.
+//!
+//! The `Device` is moved onto a thread of its own, which waits for input reports and runs
+//! the operations requested through the `AsyncDevice` in between. Input is delivered as a
+//! `Stream` of `InputEvent`s and every operation is a future that resolves once the device
+//! has been written to. Nothing here depends on a particular runtime.
+//!
+//! ```no_run
+//! # async fn example() -> firmware_api::error::Result<()> {
+//! use firmware_api::async_device::AsyncDevice;
+//! use firmware_api::device::HidDeviceWrapper;
+//! use firmware_api::profiles::Akp05;
+//!
+//! let hid_api = hidapi::HidApi::new().unwrap();
+//! let hid_device = HidDeviceWrapper::new(hid_api.open(0x0300, 0x3004).unwrap(), false);
+//! let (device, mut inputs) = AsyncDevice::spawn(hid_device, &Akp05);
+//!
+//! device.set_brightness(60).await?;
+//! while let Some(event) = inputs.recv().await {
+//!     println!("{:?} after {:?}", event.action, event.read_at.elapsed());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use crate::device::{Device, HidDeviceOperations, InputHandler};
+use crate::display_zones::DisplayZones;
+use crate::error::{Error, Result};
+use crate::image_processing::ScalingMode;
+use crate::inputs::{InputActions, InputEvent};
+use crate::keep_alive::KeepAlive;
+use crate::profiles::DeviceProfile;
+use crate::transfer::TransferMode;
+use futures_core::Stream;
+use std::pin::Pin;
+use std::sync::mpsc::{self as std_mpsc, TryRecvError};
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot};
+
+/// How long the device thread waits for input before checking for requested operations
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+type ThreadDevice = Device<Box<dyn HidDeviceOperations + Send>, InputSender>;
+type Operation = Box<dyn FnOnce(&mut DeviceThread) + Send>;
+
+/// Everything owned by the device thread
+struct DeviceThread {
+    device: ThreadDevice,
+    keep_alive: Option<KeepAlive>,
+}
+
+struct InputSender(mpsc::UnboundedSender<InputEvent>);
+
+impl InputHandler for InputSender {
+    fn handle(&self, action: InputActions) {
+        self.handle_event(InputEvent::unread(action));
+    }
+
+    fn handle_event(&self, event: InputEvent) {
+        // Nobody is listening once the stream is dropped, the event can be discarded
+        self.0.send(event).ok();
+    }
+}
+
+/// Operations on a launchpad owned by a background thread.
+///
+/// The thread stops once the launchpad is disconnected, after which every operation
+/// fails with `Error::Disconnected` and the `InputStream` ends. It also stops once both
+/// the `AsyncDevice` and the `InputStream` are dropped.
+pub struct AsyncDevice {
+    operations: std_mpsc::Sender<Operation>,
+}
+
+impl AsyncDevice {
+    /// Moves `hid_device` onto a new thread and starts reading input from it
+    ///
+    /// * `hid_device`: an opened HID device that is the launchpad
+    /// * `profile`: the model of the launchpad, see `profiles::find_profile`
+    pub fn spawn(
+        hid_device: impl HidDeviceOperations + Send + 'static,
+        profile: &'static dyn DeviceProfile,
+    ) -> (Self, InputStream) {
+        let (input_sender, input_receiver) = mpsc::unbounded_channel();
+        let (operation_sender, operation_receiver) = std_mpsc::channel();
+
+        let mut device: ThreadDevice = Device::new(Box::new(hid_device), InputSender(input_sender));
+        device.set_profile(profile);
+        let device_thread = DeviceThread {
+            device,
+            keep_alive: None,
+        };
+        thread::spawn(move || run_device(device_thread, operation_receiver));
+
+        (
+            Self {
+                operations: operation_sender,
+            },
+            InputStream {
+                receiver: input_receiver,
+            },
+        )
+    }
+
+    /// Sets the brightness of the launchpad displays to a percentage (0 - 100)
+    pub async fn set_brightness(&self, brightness: u8) -> Result<usize> {
+        self.run(move |device| device.set_brightness(brightness))
+            .await
+    }
+
+    /// Will wake up the device from its sleep state, otherwise does nothing
+    pub async fn wake_screen(&self) -> Result<usize> {
+        self.run(|device| device.wake_screen()).await
+    }
+
+    /// Turns the displays off (standby) until `wake_screen` is called
+    pub async fn sleep_screen(&self) -> Result<usize> {
+        self.run(|device| device.sleep_screen()).await
+    }
+
+    /// Tells the device the host is done with it, call this before dropping the handle
+    pub async fn disconnect(&self) -> Result<usize> {
+        self.run(|device| device.disconnect()).await
+    }
+
+    /// Has the device thread send a heartbeat every `interval`, starting straight away,
+    /// or stops sending them when `None`. Heartbeats are off by default.
+    pub async fn set_keep_alive(&self, interval: Option<Duration>) -> Result<()> {
+        self.run_on_thread(move |device_thread| {
+            let mut keep_alive = interval.map(KeepAlive::new);
+            // The first heartbeat is due now, sending it here means it went out once this resolves
+            if let Some(keep_alive) = &mut keep_alive {
+                keep_alive.tick(&device_thread.device, Instant::now())?;
+            }
+            device_thread.keep_alive = keep_alive;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn refresh(&self) -> Result<usize> {
+        self.run(|device| device.refresh()).await
+    }
+
+    pub async fn clear_all_images(&self) -> Result<usize> {
+        self.run(|device| device.clear_all_images()).await
+    }
+
+    pub async fn clear_display_zone_image(&self, display_zone: DisplayZones) -> Result<usize> {
+        self.run(move |device| device.clear_display_zone_image(display_zone))
+            .await
+    }
+
+    /// See `Device::set_background_image_from_bytes`, the image is converted on the device thread
+    pub async fn set_background_image_from_bytes(
+        &self,
+        image: impl Into<Vec<u8>>,
+    ) -> Result<usize> {
+        let image = image.into();
+        self.run(move |device| device.set_background_image_from_bytes(image))
+            .await
+    }
+
+    /// See `Device::set_display_zone_image_from_bytes`, the image is converted on the device thread
+    pub async fn set_display_zone_image_from_bytes(
+        &self,
+        display_zone: DisplayZones,
+        image: impl Into<Vec<u8>>,
+    ) -> Result<usize> {
+        let image = image.into();
+        self.run(move |device| device.set_display_zone_image_from_bytes(display_zone, image))
+            .await
+    }
+
+    /// Controls how images that do not match a display zone's aspect ratio are fitted
+    pub async fn set_scaling_mode(&self, scaling_mode: ScalingMode) -> Result<()> {
+        self.run(move |device| {
+            device.set_scaling_mode(scaling_mode);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Controls whether image uploads wait for the device to acknowledge them
+    pub async fn set_transfer_mode(&self, transfer_mode: TransferMode) -> Result<()> {
+        self.run(move |device| {
+            device.set_transfer_mode(transfer_mode);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Queues `operation` for the device thread and waits for its result
+    async fn run<R: Send + 'static>(
+        &self,
+        operation: impl FnOnce(&mut ThreadDevice) -> Result<R> + Send + 'static,
+    ) -> Result<R> {
+        self.run_on_thread(move |device_thread| operation(&mut device_thread.device))
+            .await
+    }
+
+    async fn run_on_thread<R: Send + 'static>(
+        &self,
+        operation: impl FnOnce(&mut DeviceThread) -> Result<R> + Send + 'static,
+    ) -> Result<R> {
+        let (result_sender, result_receiver) = oneshot::channel();
+
+        self.operations
+            .send(Box::new(move |device_thread| {
+                result_sender.send(operation(device_thread)).ok();
+            }))
+            .map_err(|_| Error::Disconnected)?;
+
+        // The thread only drops an operation without running it once the device is gone
+        result_receiver.await.unwrap_or(Err(Error::Disconnected))
+    }
+}
+
+/// Every input read from the launchpad, in order. Ends once the launchpad is disconnected.
+///
+/// Events are buffered until read, so the stream should be consumed for as long as it is held.
+pub struct InputStream {
+    receiver: mpsc::UnboundedReceiver<InputEvent>,
+}
+
+impl InputStream {
+    /// The next event, `None` once the launchpad is disconnected
+    pub async fn recv(&mut self) -> Option<InputEvent> {
+        self.receiver.recv().await
+    }
+}
+
+impl Stream for InputStream {
+    type Item = InputEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+fn run_device(mut device_thread: DeviceThread, operations: std_mpsc::Receiver<Operation>) {
+    let mut handle_dropped = false;
+
+    loop {
+        while !handle_dropped {
+            match operations.try_recv() {
+                Ok(operation) => operation(&mut device_thread),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => handle_dropped = true,
+            }
+        }
+
+        let DeviceThread { device, keep_alive } = &mut device_thread;
+        if handle_dropped && device.handler().0.is_closed() {
+            return;
+        }
+
+        // A missed heartbeat is not fatal, a disconnect shows up on the next read anyway
+        if let Some(keep_alive) = keep_alive {
+            keep_alive.tick(device, Instant::now()).ok();
+        }
+
+        // Other read errors are not fatal, the next read may well succeed
+        if let Err(Error::Disconnected) = device.read_input_timeout(INPUT_POLL_INTERVAL) {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::buttons::{BUTTON_3_PRESSED, ButtonActions};
+    use crate::mock::MockHidDevice;
+    use crate::profiles::Akp05;
+    use hidapi::HidError;
+    use image::{DynamicImage, ImageFormat, RgbImage};
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn inputs_are_streamed() {
+        let mock = Arc::new(MockHidDevice::new());
+        mock.queue_input(BUTTON_3_PRESSED);
+
+        let (_device, mut inputs) = AsyncDevice::spawn(mock.clone(), &Akp05);
+
+        let event = inputs.recv().await.unwrap();
+        assert_eq!(
+            event.action,
+            InputActions::Button(ButtonActions::Button3Pressed)
+        );
+        assert_eq!(event.report, BUTTON_3_PRESSED);
+        assert_eq!(event.device.model, "AKP05");
+    }
+
+    #[tokio::test]
+    async fn operations_resolve_once_written() {
+        let mock = Arc::new(MockHidDevice::new());
+        let (device, _inputs) = AsyncDevice::spawn(mock.clone(), &Akp05);
+
+        let mut image = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(112, 112))
+            .write_to(&mut Cursor::new(&mut image), ImageFormat::Jpeg)
+            .unwrap();
+
+        device.set_brightness(40).await.unwrap();
+        device
+            .set_display_zone_image_from_bytes(DisplayZones::Button4, image.clone())
+            .await
+            .unwrap();
+
+        mock.assert_brightness_set(40);
+        assert_eq!(
+            mock.assert_display_zone_image_uploaded(DisplayZones::Button4),
+            image
+        );
+    }
+
+    #[tokio::test]
+    async fn keep_alive_is_sent_from_the_device_thread() {
+        let mock = Arc::new(MockHidDevice::new());
+        let (device, _inputs) = AsyncDevice::spawn(mock.clone(), &Akp05);
+
+        device
+            .set_keep_alive(Some(Duration::from_secs(60)))
+            .await
+            .unwrap();
+
+        mock.assert_heartbeat_sent();
+    }
+
+    #[tokio::test]
+    async fn disconnecting_ends_the_stream_and_fails_operations() {
+        let mock = Arc::new(MockHidDevice::new());
+        mock.fail_next_read(HidError::HidApiError {
+            message: "hid_read_timeout: device disconnected".to_string(),
+        });
+
+        let (device, mut inputs) = AsyncDevice::spawn(mock.clone(), &Akp05);
+
+        assert_eq!(inputs.recv().await, None);
+        assert!(matches!(
+            device.set_brightness(40).await,
+            Err(Error::Disconnected)
+        ));
+    }
+}
@@ -0,0 +1,610 @@
+//! Reads USB captures (pcap or pcapng) so the traffic of the launchpad can be inspected.
+//!
+//! Captures from [`USBPcap`] (Windows) and usbmon (Linux, e.g. through Wireshark or
+//! `tcpdump -i usbmon1`) are supported. Packets from other link types are skipped.
+//!
+//! Devices are identified through the device descriptors exchanged while they are
+//! enumerated, so to filter by vendor and product id the capture has to be started
+//! before the launchpad is plugged in.
+//!
+//! See `examples/decode_capture.rs` for an annotated timeline built on top of this.
+//!
+//! [`USBPcap`]: https://desowin.org/usbpcap/
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind, Read};
+use std::time::Duration;
+
+const PCAP_MAGIC_MICROSECONDS: u32 = 0xA1B2_C3D4;
+const PCAP_MAGIC_NANOSECONDS: u32 = 0xA1B2_3C4D;
+
+const PCAPNG_SECTION_HEADER_BLOCK: u32 = 0x0A0D_0D0A;
+const PCAPNG_BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+const PCAPNG_INTERFACE_DESCRIPTION_BLOCK: u32 = 1;
+const PCAPNG_SIMPLE_PACKET_BLOCK: u32 = 3;
+const PCAPNG_ENHANCED_PACKET_BLOCK: u32 = 6;
+const PCAPNG_OPTION_END: u16 = 0;
+const PCAPNG_OPTION_TIMESTAMP_RESOLUTION: u16 = 9;
+
+const LINKTYPE_USB_LINUX: u32 = 189;
+const LINKTYPE_USB_LINUX_MMAPPED: u32 = 220;
+const LINKTYPE_USBPCAP: u32 = 249;
+
+const USB_LINUX_HEADER_LENGTH: usize = 48;
+const USB_LINUX_MMAPPED_HEADER_LENGTH: usize = 64;
+const USBPCAP_MINIMUM_HEADER_LENGTH: usize = 27;
+
+/// A device descriptor is 18 bytes long and has a descriptor type of 1
+const DEVICE_DESCRIPTOR_LENGTH: u8 = 18;
+const DEVICE_DESCRIPTOR_TYPE: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferType {
+    Isochronous,
+    Interrupt,
+    Control,
+    Bulk,
+    Other(u8),
+}
+
+impl From<u8> for TransferType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => TransferType::Isochronous,
+            1 => TransferType::Interrupt,
+            2 => TransferType::Control,
+            3 => TransferType::Bulk,
+            other => TransferType::Other(other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    HostToDevice,
+    DeviceToHost,
+}
+
+/// Where a device sits in the capture, a device keeps its address until it is unplugged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UsbAddress {
+    pub bus: u16,
+    pub device: u16,
+}
+
+/// A USB transfer that carried data, either the request (host to device) or the
+/// completion (device to host) depending on the direction of the endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsbPacket {
+    /// Time since the UNIX epoch as recorded by the capture
+    pub timestamp: Duration,
+    pub address: UsbAddress,
+    /// Endpoint number including the direction bit (0x80 for IN endpoints)
+    pub endpoint: u8,
+    pub transfer_type: TransferType,
+    pub data: Vec<u8>,
+}
+
+impl UsbPacket {
+    pub fn direction(&self) -> Direction {
+        match self.endpoint & 0x80 {
+            0 => Direction::HostToDevice,
+            _ => Direction::DeviceToHost,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Capture {
+    pub packets: Vec<UsbPacket>,
+}
+
+impl Capture {
+    /// Reads a whole pcap or pcapng file, the format is detected automatically
+    pub fn read(mut reader: impl Read) -> std::io::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Self::parse(&bytes)
+    }
+
+    pub fn parse(bytes: &[u8]) -> std::io::Result<Self> {
+        let magic = bytes
+            .get(..4)
+            .ok_or_else(|| invalid_data("file is too short to be a capture"))?;
+
+        let packets = match u32::from_le_bytes(magic.try_into().unwrap()) {
+            PCAPNG_SECTION_HEADER_BLOCK => parse_pcapng(bytes)?,
+            _ => parse_pcap(bytes)?,
+        };
+
+        Ok(Self { packets })
+    }
+
+    /// The vendor and product ids of every device whose device descriptor was captured
+    pub fn devices(&self) -> HashMap<UsbAddress, (u16, u16)> {
+        self.packets
+            .iter()
+            .filter(|packet| {
+                packet.transfer_type == TransferType::Control
+                    && packet.direction() == Direction::DeviceToHost
+            })
+            .filter_map(|packet| {
+                let descriptor = packet.data.as_slice();
+                if descriptor.len() < DEVICE_DESCRIPTOR_LENGTH as usize
+                    || descriptor[0] != DEVICE_DESCRIPTOR_LENGTH
+                    || descriptor[1] != DEVICE_DESCRIPTOR_TYPE
+                {
+                    return None;
+                }
+
+                let vendor_id = u16::from_le_bytes([descriptor[8], descriptor[9]]);
+                let product_id = u16::from_le_bytes([descriptor[10], descriptor[11]]);
+                Some((packet.address, (vendor_id, product_id)))
+            })
+            .collect()
+    }
+
+    /// Every interrupt transfer (the HID reports) to or from the given device, in capture order
+    pub fn interrupt_transfers(
+        &self,
+        vendor_id: u16,
+        product_id: u16,
+    ) -> impl Iterator<Item = &UsbPacket> {
+        let devices = self.devices();
+
+        self.packets.iter().filter(move |packet| {
+            packet.transfer_type == TransferType::Interrupt
+                && devices.get(&packet.address) == Some(&(vendor_id, product_id))
+        })
+    }
+}
+
+fn invalid_data(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+/// Reads integers in the byte order of the section they come from
+#[derive(Clone, Copy)]
+struct Endianness {
+    big: bool,
+}
+
+impl Endianness {
+    fn u16(self, bytes: &[u8], offset: usize) -> std::io::Result<u16> {
+        let value: [u8; 2] = bytes
+            .get(offset..offset + 2)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or_else(|| invalid_data("unexpected end of capture"))?;
+        Ok(match self.big {
+            true => u16::from_be_bytes(value),
+            false => u16::from_le_bytes(value),
+        })
+    }
+
+    fn u32(self, bytes: &[u8], offset: usize) -> std::io::Result<u32> {
+        let value: [u8; 4] = bytes
+            .get(offset..offset + 4)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or_else(|| invalid_data("unexpected end of capture"))?;
+        Ok(match self.big {
+            true => u32::from_be_bytes(value),
+            false => u32::from_le_bytes(value),
+        })
+    }
+}
+
+fn parse_pcap(bytes: &[u8]) -> std::io::Result<Vec<UsbPacket>> {
+    let (endianness, nanoseconds) = match (
+        u32::from_le_bytes(bytes[..4].try_into().unwrap()),
+        u32::from_be_bytes(bytes[..4].try_into().unwrap()),
+    ) {
+        (PCAP_MAGIC_MICROSECONDS, _) => (Endianness { big: false }, false),
+        (PCAP_MAGIC_NANOSECONDS, _) => (Endianness { big: false }, true),
+        (_, PCAP_MAGIC_MICROSECONDS) => (Endianness { big: true }, false),
+        (_, PCAP_MAGIC_NANOSECONDS) => (Endianness { big: true }, true),
+        _ => return Err(invalid_data("not a pcap or pcapng file")),
+    };
+
+    let link_type = endianness.u32(bytes, 20)?;
+    let mut packets = Vec::new();
+    let mut offset = 24;
+
+    while offset < bytes.len() {
+        let seconds = endianness.u32(bytes, offset)?;
+        let fraction = endianness.u32(bytes, offset + 4)?;
+        let captured_length = endianness.u32(bytes, offset + 8)? as usize;
+        let data = bytes
+            .get(offset + 16..offset + 16 + captured_length)
+            .ok_or_else(|| invalid_data("truncated packet record"))?;
+
+        let timestamp = Duration::from_secs(seconds.into())
+            + match nanoseconds {
+                true => Duration::from_nanos(fraction.into()),
+                false => Duration::from_micros(fraction.into()),
+            };
+        packets.extend(parse_usb_packet(link_type, timestamp, data));
+
+        offset += 16 + captured_length;
+    }
+
+    Ok(packets)
+}
+
+struct Interface {
+    link_type: u32,
+    resolution: Resolution,
+}
+
+/// How many timestamp units make up a second
+#[derive(Clone, Copy)]
+enum Resolution {
+    PowerOfTen(u32),
+    PowerOfTwo(u32),
+}
+
+impl Resolution {
+    fn to_duration(self, ticks: u64) -> Duration {
+        let units_per_second = match self {
+            Resolution::PowerOfTen(exponent) => 10u64.saturating_pow(exponent),
+            Resolution::PowerOfTwo(exponent) => 1u64 << exponent.min(63),
+        };
+
+        let seconds = ticks / units_per_second;
+        let remainder = ticks % units_per_second;
+        let nanos = u128::from(remainder) * 1_000_000_000 / u128::from(units_per_second);
+        Duration::new(seconds, nanos as u32)
+    }
+}
+
+fn parse_pcapng(bytes: &[u8]) -> std::io::Result<Vec<UsbPacket>> {
+    let mut packets = Vec::new();
+    let mut interfaces: Vec<Interface> = Vec::new();
+    let mut endianness = Endianness { big: false };
+    let mut offset = 0;
+
+    while offset + 12 <= bytes.len() {
+        // The section header block type reads the same in both byte orders, its
+        // byte order magic decides how the rest of the section is read
+        if endianness.u32(bytes, offset)? == PCAPNG_SECTION_HEADER_BLOCK {
+            let magic = u32::from_le_bytes(bytes[offset + 8..offset + 12].try_into().unwrap());
+            endianness = match magic {
+                PCAPNG_BYTE_ORDER_MAGIC => Endianness { big: false },
+                _ if magic.swap_bytes() == PCAPNG_BYTE_ORDER_MAGIC => Endianness { big: true },
+                _ => return Err(invalid_data("invalid pcapng byte order magic")),
+            };
+            interfaces.clear();
+        }
+
+        let block_type = endianness.u32(bytes, offset)?;
+        let block_length = endianness.u32(bytes, offset + 4)? as usize;
+        if block_length < 12 {
+            return Err(invalid_data("invalid pcapng block length"));
+        }
+        let body = bytes
+            .get(offset + 8..offset + block_length - 4)
+            .ok_or_else(|| invalid_data("truncated pcapng block"))?;
+
+        match block_type {
+            PCAPNG_INTERFACE_DESCRIPTION_BLOCK => interfaces.push(Interface {
+                link_type: endianness.u16(body, 0)?.into(),
+                resolution: timestamp_resolution(endianness, body.get(8..).unwrap_or_default())?,
+            }),
+            PCAPNG_ENHANCED_PACKET_BLOCK => {
+                let interface = interfaces
+                    .get(endianness.u32(body, 0)? as usize)
+                    .ok_or_else(|| invalid_data("packet refers to an unknown interface"))?;
+                let ticks = (u64::from(endianness.u32(body, 4)?) << 32)
+                    | u64::from(endianness.u32(body, 8)?);
+                let captured_length = endianness.u32(body, 12)? as usize;
+                let data = body
+                    .get(20..20 + captured_length)
+                    .ok_or_else(|| invalid_data("truncated enhanced packet block"))?;
+
+                packets.extend(parse_usb_packet(
+                    interface.link_type,
+                    interface.resolution.to_duration(ticks),
+                    data,
+                ));
+            }
+            PCAPNG_SIMPLE_PACKET_BLOCK => {
+                // Simple packets have no timestamp and always belong to the first interface
+                if let Some(interface) = interfaces.first() {
+                    let original_length = endianness.u32(body, 0)? as usize;
+                    let data = &body[4..(4 + original_length).min(body.len())];
+                    packets.extend(parse_usb_packet(interface.link_type, Duration::ZERO, data));
+                }
+            }
+            _ => {}
+        }
+
+        offset += block_length;
+    }
+
+    Ok(packets)
+}
+
+/// Reads the `if_tsresol` option of an interface, defaulting to microseconds
+fn timestamp_resolution(endianness: Endianness, options: &[u8]) -> std::io::Result<Resolution> {
+    let mut offset = 0;
+
+    while offset + 4 <= options.len() {
+        let code = endianness.u16(options, offset)?;
+        let length = endianness.u16(options, offset + 2)? as usize;
+
+        if code == PCAPNG_OPTION_END {
+            break;
+        }
+        if code == PCAPNG_OPTION_TIMESTAMP_RESOLUTION && length >= 1 {
+            let value = *options
+                .get(offset + 4)
+                .ok_or_else(|| invalid_data("unexpected end of capture"))?;
+            return Ok(match value & 0x80 {
+                0 => Resolution::PowerOfTen(value.into()),
+                _ => Resolution::PowerOfTwo((value & 0x7F).into()),
+            });
+        }
+
+        // Option values are padded to 32 bits
+        offset += 4 + length.div_ceil(4) * 4;
+    }
+
+    Ok(Resolution::PowerOfTen(6))
+}
+
+/// Extracts the transfer from a single captured packet, `None` if it carried no data
+/// or is not a USB packet
+fn parse_usb_packet(link_type: u32, timestamp: Duration, data: &[u8]) -> Option<UsbPacket> {
+    let (address, endpoint, transfer_type, payload) = match link_type {
+        LINKTYPE_USBPCAP => {
+            if data.len() < USBPCAP_MINIMUM_HEADER_LENGTH {
+                return None;
+            }
+            let header_length = u16::from_le_bytes([data[0], data[1]]) as usize;
+            let address = UsbAddress {
+                bus: u16::from_le_bytes([data[17], data[18]]),
+                device: u16::from_le_bytes([data[19], data[20]]),
+            };
+            (address, data[21], data[22], data.get(header_length..)?)
+        }
+        LINKTYPE_USB_LINUX | LINKTYPE_USB_LINUX_MMAPPED => {
+            let header_length = match link_type {
+                LINKTYPE_USB_LINUX => USB_LINUX_HEADER_LENGTH,
+                _ => USB_LINUX_MMAPPED_HEADER_LENGTH,
+            };
+            if data.len() < header_length {
+                return None;
+            }
+            // usbmon headers are in the byte order of the capturing host, which is little endian
+            // for every platform the vendor software and this crate run on
+            let captured_length = u32::from_le_bytes(data[36..40].try_into().unwrap()) as usize;
+            let address = UsbAddress {
+                bus: u16::from_le_bytes([data[12], data[13]]),
+                device: data[11].into(),
+            };
+            let payload = &data[header_length..(header_length + captured_length).min(data.len())];
+            (address, data[10], data[9], payload)
+        }
+        _ => return None,
+    };
+
+    if payload.is_empty() {
+        return None;
+    }
+
+    Some(UsbPacket {
+        timestamp,
+        address,
+        endpoint,
+        transfer_type: transfer_type.into(),
+        data: payload.to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncated_timestamp_resolution_is_an_error() {
+        let little = Endianness { big: false };
+        // if_tsresol (9) with a one byte value, cut off before the value
+        let options = [9, 0, 1, 0];
+
+        let Err(error) = timestamp_resolution(little, &options) else {
+            panic!("a truncated option was accepted");
+        };
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(matches!(
+            timestamp_resolution(little, &[9, 0, 1, 0, 0x83, 0, 0, 0]),
+            Ok(Resolution::PowerOfTwo(3))
+        ));
+    }
+
+    const VENDOR_ID: u16 = 0x0300;
+    const PRODUCT_ID: u16 = 0x3004;
+
+    fn device_descriptor(vendor_id: u16, product_id: u16) -> Vec<u8> {
+        let mut descriptor = vec![0; 18];
+        descriptor[0] = DEVICE_DESCRIPTOR_LENGTH;
+        descriptor[1] = DEVICE_DESCRIPTOR_TYPE;
+        descriptor[8..10].copy_from_slice(&vendor_id.to_le_bytes());
+        descriptor[10..12].copy_from_slice(&product_id.to_le_bytes());
+        descriptor
+    }
+
+    fn usbpcap_packet(device: u16, endpoint: u8, transfer_type: u8, data: &[u8]) -> Vec<u8> {
+        let mut packet = vec![0; USBPCAP_MINIMUM_HEADER_LENGTH];
+        packet[0..2].copy_from_slice(&(USBPCAP_MINIMUM_HEADER_LENGTH as u16).to_le_bytes());
+        packet[17..19].copy_from_slice(&1u16.to_le_bytes());
+        packet[19..21].copy_from_slice(&device.to_le_bytes());
+        packet[21] = endpoint;
+        packet[22] = transfer_type;
+        packet[23..27].copy_from_slice(&(data.len() as u32).to_le_bytes());
+        packet.extend_from_slice(data);
+        packet
+    }
+
+    fn usbmon_packet(device: u8, endpoint: u8, transfer_type: u8, data: &[u8]) -> Vec<u8> {
+        let mut packet = vec![0; USB_LINUX_MMAPPED_HEADER_LENGTH];
+        packet[9] = transfer_type;
+        packet[10] = endpoint;
+        packet[11] = device;
+        packet[12..14].copy_from_slice(&3u16.to_le_bytes());
+        packet[36..40].copy_from_slice(&(data.len() as u32).to_le_bytes());
+        packet.extend_from_slice(data);
+        packet
+    }
+
+    fn pcapng_block(block_type: u32, body: &[u8]) -> Vec<u8> {
+        let mut body = body.to_vec();
+        body.resize(body.len().div_ceil(4) * 4, 0);
+        let length = (body.len() + 12) as u32;
+
+        let mut block = Vec::new();
+        block.extend_from_slice(&block_type.to_le_bytes());
+        block.extend_from_slice(&length.to_le_bytes());
+        block.extend_from_slice(&body);
+        block.extend_from_slice(&length.to_le_bytes());
+        block
+    }
+
+    /// A little endian pcapng file with one USBPcap interface using nanosecond timestamps
+    fn pcapng(packets: &[(u64, Vec<u8>)]) -> Vec<u8> {
+        let mut section_header = Vec::new();
+        section_header.extend_from_slice(&PCAPNG_BYTE_ORDER_MAGIC.to_le_bytes());
+        section_header.extend_from_slice(&[1, 0, 0, 0]);
+        section_header.extend_from_slice(&(-1i64).to_le_bytes());
+
+        let mut interface = Vec::new();
+        interface.extend_from_slice(&(LINKTYPE_USBPCAP as u16).to_le_bytes());
+        interface.extend_from_slice(&[0, 0]);
+        interface.extend_from_slice(&0u32.to_le_bytes());
+        interface.extend_from_slice(&PCAPNG_OPTION_TIMESTAMP_RESOLUTION.to_le_bytes());
+        interface.extend_from_slice(&1u16.to_le_bytes());
+        interface.extend_from_slice(&[9, 0, 0, 0]);
+        interface.extend_from_slice(&[0, 0, 0, 0]);
+
+        let mut file = pcapng_block(PCAPNG_SECTION_HEADER_BLOCK, &section_header);
+        file.extend(pcapng_block(PCAPNG_INTERFACE_DESCRIPTION_BLOCK, &interface));
+
+        for (ticks, data) in packets {
+            let mut body = Vec::new();
+            body.extend_from_slice(&0u32.to_le_bytes());
+            body.extend_from_slice(&((ticks >> 32) as u32).to_le_bytes());
+            body.extend_from_slice(&(*ticks as u32).to_le_bytes());
+            body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            body.extend_from_slice(data);
+            file.extend(pcapng_block(PCAPNG_ENHANCED_PACKET_BLOCK, &body));
+        }
+
+        file
+    }
+
+    /// A big endian pcap file with microsecond timestamps, written by usbmon
+    fn pcap(packets: &[(u32, u32, Vec<u8>)]) -> Vec<u8> {
+        let mut file = Vec::new();
+        file.extend_from_slice(&PCAP_MAGIC_MICROSECONDS.to_be_bytes());
+        file.extend_from_slice(&[0, 2, 0, 4]);
+        file.extend_from_slice(&[0; 8]);
+        file.extend_from_slice(&65535u32.to_be_bytes());
+        file.extend_from_slice(&LINKTYPE_USB_LINUX_MMAPPED.to_be_bytes());
+
+        for (seconds, microseconds, data) in packets {
+            file.extend_from_slice(&seconds.to_be_bytes());
+            file.extend_from_slice(&microseconds.to_be_bytes());
+            file.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            file.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            file.extend_from_slice(data);
+        }
+
+        file
+    }
+
+    #[test]
+    fn reads_usbpcap_from_pcapng() {
+        let file = pcapng(&[
+            (
+                1_000_000_000,
+                usbpcap_packet(7, 0x80, 2, &device_descriptor(VENDOR_ID, PRODUCT_ID)),
+            ),
+            (2_500_000_000, usbpcap_packet(7, 0x01, 1, b"CRT\0\0STP")),
+            // Requests for IN transfers carry no data
+            (2_600_000_000, usbpcap_packet(7, 0x82, 1, &[])),
+            (
+                2_700_000_000,
+                usbpcap_packet(7, 0x82, 1, b"ACK\0\0OK\0\0\x03\x01"),
+            ),
+        ]);
+
+        let capture = Capture::parse(&file).unwrap();
+        let transfers: Vec<_> = capture.interrupt_transfers(VENDOR_ID, PRODUCT_ID).collect();
+
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].timestamp, Duration::from_millis(2500));
+        assert_eq!(transfers[0].direction(), Direction::HostToDevice);
+        assert_eq!(transfers[0].data, b"CRT\0\0STP");
+        assert_eq!(transfers[1].direction(), Direction::DeviceToHost);
+    }
+
+    #[test]
+    fn reads_big_endian_usbmon_pcap() {
+        let file = pcap(&[
+            (
+                10,
+                0,
+                usbmon_packet(4, 0x80, 2, &device_descriptor(VENDOR_ID, PRODUCT_ID)),
+            ),
+            (11, 250, usbmon_packet(4, 0x01, 1, b"CRT\0\0DIS")),
+        ]);
+
+        let capture = Capture::parse(&file).unwrap();
+
+        assert_eq!(
+            capture.devices(),
+            HashMap::from([(UsbAddress { bus: 3, device: 4 }, (VENDOR_ID, PRODUCT_ID))])
+        );
+        let transfer = capture
+            .interrupt_transfers(VENDOR_ID, PRODUCT_ID)
+            .next()
+            .unwrap();
+        assert_eq!(transfer.timestamp, Duration::new(11, 250_000));
+        assert_eq!(transfer.data, b"CRT\0\0DIS");
+    }
+
+    #[test]
+    fn other_devices_are_filtered_out() {
+        let file = pcapng(&[
+            (
+                0,
+                usbpcap_packet(7, 0x80, 2, &device_descriptor(VENDOR_ID, PRODUCT_ID)),
+            ),
+            (
+                0,
+                usbpcap_packet(8, 0x80, 2, &device_descriptor(0x046D, 0xC52B)),
+            ),
+            (1, usbpcap_packet(8, 0x81, 1, &[1, 2, 3])),
+        ]);
+
+        let capture = Capture::parse(&file).unwrap();
+
+        assert_eq!(
+            capture.interrupt_transfers(VENDOR_ID, PRODUCT_ID).count(),
+            0
+        );
+        assert_eq!(capture.interrupt_transfers(0x046D, 0xC52B).count(), 1);
+    }
+
+    #[test]
+    fn truncated_file_is_an_error() {
+        let mut file = pcapng(&[(0, usbpcap_packet(7, 0x01, 1, b"CRT"))]);
+        file.truncate(file.len() - 8);
+
+        assert!(Capture::parse(&file).is_err());
+    }
+
+    #[test]
+    fn other_formats_are_rejected() {
+        assert!(Capture::parse(b"GIF89a").is_err());
+    }
+}
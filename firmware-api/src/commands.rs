@@ -1,7 +1,6 @@
 use crate::commands::payloads::Payload;
 use crate::common::{ByteArray, IMAGE_DATA_PACKET_LENGTH};
-use crate::display_zones::DisplayZones;
-use hidapi::HidResult;
+use crate::error::Result;
 
 pub mod messages;
 pub mod output_buffer;
@@ -18,39 +17,60 @@ struct PayloadCommand<const N: usize, P: Payload<N>> {
     payload: P,
 }
 
-impl<const N: usize, P: Payload<N>> Command<N, HidResult<usize>> for PayloadCommand<N, P> {
-    fn execute<F>(&self, write_callback: F) -> HidResult<usize>
+impl<const N: usize, P: Payload<N>> Command<N, Result<usize>> for PayloadCommand<N, P> {
+    fn execute<F>(&self, write_callback: F) -> Result<usize>
     where
-        F: Fn(&ByteArray<N>) -> HidResult<usize>,
+        F: Fn(&ByteArray<N>) -> Result<usize>,
     {
         write_callback(&self.payload.generate())
     }
 }
 
 pub fn wake_screen_command_factory()
--> impl Command<{ output_buffer::BUFFER_SIZE_513 }, HidResult<usize>> {
+-> impl Command<{ output_buffer::BUFFER_SIZE_513 }, Result<usize>> {
     PayloadCommand {
         payload: payloads::WakeScreen,
     }
 }
 
-pub fn refresh_command_factory()
--> impl Command<{ output_buffer::BUFFER_SIZE_513 }, HidResult<usize>> {
+pub fn refresh_command_factory() -> impl Command<{ output_buffer::BUFFER_SIZE_513 }, Result<usize>>
+{
     PayloadCommand {
         payload: payloads::Refresh,
     }
 }
 
+pub fn heartbeat_command_factory() -> impl Command<{ output_buffer::BUFFER_SIZE_513 }, Result<usize>>
+{
+    PayloadCommand {
+        payload: payloads::Heartbeat,
+    }
+}
+
+pub fn sleep_screen_command_factory()
+-> impl Command<{ output_buffer::BUFFER_SIZE_513 }, Result<usize>> {
+    PayloadCommand {
+        payload: payloads::SleepScreen,
+    }
+}
+
+pub fn disconnect_command_factory()
+-> impl Command<{ output_buffer::BUFFER_SIZE_513 }, Result<usize>> {
+    PayloadCommand {
+        payload: payloads::Disconnect,
+    }
+}
+
 pub fn set_brightness_command_factory(
     brightness: u8,
-) -> impl Command<{ output_buffer::BUFFER_SIZE_513 }, HidResult<usize>> {
+) -> impl Command<{ output_buffer::BUFFER_SIZE_513 }, Result<usize>> {
     PayloadCommand {
         payload: payloads::SetBrightness::new(brightness),
     }
 }
 
 pub fn clear_all_images_command_factory()
--> impl Command<{ output_buffer::BUFFER_SIZE_513 }, HidResult<usize>> {
+-> impl Command<{ output_buffer::BUFFER_SIZE_513 }, Result<usize>> {
     PayloadCommand {
         payload: payloads::ClearAllImages,
     }
@@ -58,7 +78,7 @@ pub fn clear_all_images_command_factory()
 
 pub fn initiate_set_background_command_factory(
     image_size_bytes: u32,
-) -> impl Command<{ output_buffer::BUFFER_SIZE_1025 }, HidResult<usize>> {
+) -> impl Command<{ output_buffer::BUFFER_SIZE_1025 }, Result<usize>> {
     PayloadCommand {
         payload: payloads::InitiateSetBackgroundImage::new(image_size_bytes),
     }
@@ -66,25 +86,25 @@ pub fn initiate_set_background_command_factory(
 
 pub fn initiate_set_display_zone_image_command_factory(
     image_size_bytes: u32,
-    display_zone: DisplayZones,
-) -> impl Command<{ output_buffer::BUFFER_SIZE_1025 }, HidResult<usize>> {
+    display_zone_id: u8,
+) -> impl Command<{ output_buffer::BUFFER_SIZE_1025 }, Result<usize>> {
     PayloadCommand {
-        payload: payloads::InitiateDisplayZoneImage::new(image_size_bytes, display_zone),
+        payload: payloads::InitiateDisplayZoneImage::new(image_size_bytes, display_zone_id),
     }
 }
 
 pub fn send_image_data_packet_command_factory(
     packet: ByteArray<IMAGE_DATA_PACKET_LENGTH>,
-) -> impl Command<{ output_buffer::BUFFER_SIZE_1025 }, HidResult<usize>> {
+) -> impl Command<{ output_buffer::BUFFER_SIZE_1025 }, Result<usize>> {
     PayloadCommand {
         payload: payloads::SendImageDataPacket::new(packet),
     }
 }
 
 pub fn clear_display_zone_image_command_factory(
-    display_zone: DisplayZones,
-) -> impl Command<{ output_buffer::BUFFER_SIZE_513 }, HidResult<usize>> {
+    display_zone_id: u8,
+) -> impl Command<{ output_buffer::BUFFER_SIZE_513 }, Result<usize>> {
     PayloadCommand {
-        payload: payloads::ClearDisplayZoneImage::new(display_zone),
+        payload: payloads::ClearDisplayZoneImage::new(display_zone_id),
     }
 }
@@ -15,3 +15,12 @@ pub const CLEAR_ALL_IMAGES: ByteArray<13> = *b"\0CRT\0\0CLE\0\0\0\xFF";
 
 pub const INITIATE_SET_BACKGROUND_IMAGE: ByteArray<9> = *b"\0CRT\0\0LOG";
 pub const INITIATE_SET_DISPLAY_ZONE_IMAGE: ByteArray<9> = *b"\0CRT\0\0BAT";
+
+/// Heartbeat telling the device the host is still connected
+pub const HEARTBEAT: ByteArray<13> = *b"\0CRT\0\0CONNECT";
+
+/// Turns the displays off until the device is woken up again
+pub const SLEEP_SCREEN: ByteArray<9> = *b"\0CRT\0\0HAN";
+
+/// Tells the device the host is done with it, sent before closing the connection
+pub const DISCONNECT: ByteArray<13> = *b"\0CRT\0\0CLE\0\0DC";
@@ -3,7 +3,6 @@ use crate::commands::output_buffer::{
     BUFFER_SIZE_513, BUFFER_SIZE_1025, create_output_buffer_with_lsbs_filled,
 };
 use crate::common::{ByteArray, IMAGE_DATA_PACKET_LENGTH, IMAGE_SIZE_LENGTH_IN_BYTES};
-use crate::display_zones::DisplayZones;
 
 pub trait Payload<const N: usize> {
     fn generate(&self) -> ByteArray<N>;
@@ -23,6 +22,27 @@ impl Payload<BUFFER_SIZE_513> for Refresh {
     }
 }
 
+pub struct Heartbeat;
+impl Payload<BUFFER_SIZE_513> for Heartbeat {
+    fn generate(&self) -> ByteArray<BUFFER_SIZE_513> {
+        create_output_buffer_with_lsbs_filled(&messages::HEARTBEAT)
+    }
+}
+
+pub struct SleepScreen;
+impl Payload<BUFFER_SIZE_513> for SleepScreen {
+    fn generate(&self) -> ByteArray<BUFFER_SIZE_513> {
+        create_output_buffer_with_lsbs_filled(&messages::SLEEP_SCREEN)
+    }
+}
+
+pub struct Disconnect;
+impl Payload<BUFFER_SIZE_513> for Disconnect {
+    fn generate(&self) -> ByteArray<BUFFER_SIZE_513> {
+        create_output_buffer_with_lsbs_filled(&messages::DISCONNECT)
+    }
+}
+
 pub struct SetBrightness {
     brightness: u8,
 }
@@ -49,13 +69,14 @@ impl Payload<BUFFER_SIZE_513> for ClearAllImages {
     }
 }
 
+/// Display zones are addressed by the id the device uses for them, which differs between models
 pub struct ClearDisplayZoneImage {
-    display_zone: DisplayZones,
+    display_zone_id: u8,
 }
 
 impl ClearDisplayZoneImage {
-    pub fn new(display_zone: DisplayZones) -> Self {
-        Self { display_zone }
+    pub fn new(display_zone_id: u8) -> Self {
+        Self { display_zone_id }
     }
 }
 
@@ -63,8 +84,7 @@ impl Payload<BUFFER_SIZE_513> for ClearDisplayZoneImage {
     fn generate(&self) -> ByteArray<BUFFER_SIZE_513> {
         let mut message_buffer =
             create_output_buffer_with_lsbs_filled(&messages::CLEAR_DISPLAY_ZONE_IMAGE);
-        message_buffer[messages::CLEAR_DISPLAY_ZONE_IMAGE.len()] =
-            DisplayZones::into(self.display_zone);
+        message_buffer[messages::CLEAR_DISPLAY_ZONE_IMAGE.len()] = self.display_zone_id;
         message_buffer
     }
 }
@@ -95,14 +115,14 @@ impl Payload<BUFFER_SIZE_1025> for InitiateSetBackgroundImage {
 
 pub struct InitiateDisplayZoneImage {
     image_size_bytes: u32,
-    display_zone: DisplayZones,
+    display_zone_id: u8,
 }
 
 impl InitiateDisplayZoneImage {
-    pub fn new(image_size_bytes: u32, display_zone: DisplayZones) -> Self {
+    pub fn new(image_size_bytes: u32, display_zone_id: u8) -> Self {
         Self {
             image_size_bytes,
-            display_zone,
+            display_zone_id,
         }
     }
 }
@@ -116,8 +136,7 @@ impl Payload<BUFFER_SIZE_1025> for InitiateDisplayZoneImage {
         message_buffer[last_index..last_index + IMAGE_SIZE_LENGTH_IN_BYTES]
             .clone_from_slice(&self.image_size_bytes.to_be_bytes());
 
-        message_buffer[last_index + IMAGE_SIZE_LENGTH_IN_BYTES] =
-            DisplayZones::into(self.display_zone);
+        message_buffer[last_index + IMAGE_SIZE_LENGTH_IN_BYTES] = self.display_zone_id;
 
         message_buffer
     }
@@ -143,9 +162,10 @@ impl Payload<BUFFER_SIZE_1025> for SendImageDataPacket {
 mod tests {
     use super::*;
     use crate::commands::messages::{
-        CLEAR_DISPLAY_ZONE_IMAGE, INITIATE_SET_BACKGROUND_IMAGE, INITIATE_SET_DISPLAY_ZONE_IMAGE,
-        REFRESH, SET_BRIGHTNESS, WAKE_SCREEN,
+        CLEAR_DISPLAY_ZONE_IMAGE, DISCONNECT, HEARTBEAT, INITIATE_SET_BACKGROUND_IMAGE,
+        INITIATE_SET_DISPLAY_ZONE_IMAGE, REFRESH, SET_BRIGHTNESS, SLEEP_SCREEN, WAKE_SCREEN,
     };
+    use crate::display_zones::DisplayZones;
 
     #[test]
     fn correct_refresh_payload() {
@@ -159,6 +179,22 @@ mod tests {
         assert_eq!(payload, create_output_buffer_with_lsbs_filled(&WAKE_SCREEN))
     }
 
+    #[test]
+    fn correct_connection_payloads() {
+        assert_eq!(
+            Heartbeat::generate(&Heartbeat),
+            create_output_buffer_with_lsbs_filled(&HEARTBEAT)
+        );
+        assert_eq!(
+            SleepScreen::generate(&SleepScreen),
+            create_output_buffer_with_lsbs_filled(&SLEEP_SCREEN)
+        );
+        assert_eq!(
+            Disconnect::generate(&Disconnect),
+            create_output_buffer_with_lsbs_filled(&DISCONNECT)
+        );
+    }
+
     #[test]
     fn correct_set_brightness_payload() {
         let payload = SetBrightness::generate(&SetBrightness::new(30));
@@ -197,7 +233,7 @@ mod tests {
     fn correct_initiate_set_display_zone_image_payload() {
         let payload = InitiateDisplayZoneImage::generate(&InitiateDisplayZoneImage::new(
             0x20u32,
-            DisplayZones::Button7,
+            DisplayZones::Button7.into(),
         ));
 
         let mut message_buffer = [0; 14];
@@ -217,7 +253,7 @@ mod tests {
     #[test]
     fn correct_clear_display_zone_image_payload() {
         let payload = ClearDisplayZoneImage::generate(&ClearDisplayZoneImage::new(
-            DisplayZones::Touchscreen3,
+            DisplayZones::Touchscreen3.into(),
         ));
 
         let mut message_buffer = [0; 14];
@@ -0,0 +1,370 @@
+//! Turns frames written to the launchpad back into typed commands.
+//!
+//! This is the inverse of the payloads the `Device` sends, useful for logging
+//! outbound traffic or comparing it against captures of the vendor software.
+//!
+//! Image data packets carry no header, so a lone frame can only be recognised as one
+//! by its size. `Decoder` follows the transfer started by the initiating `LOG`/`BAT`
+//! frame and should be preferred when decoding a sequence of frames.
+//!
+//! ```
+//! use firmware_api::decoder::{DecodedCommand, decode};
+//!
+//! let mut frame = [0u8; 513];
+//! frame[..12].copy_from_slice(b"\0CRT\0\0LIG\0\0\x28");
+//!
+//! assert_eq!(decode(&frame), DecodedCommand::SetBrightness(40));
+//! ```
+use crate::commands::messages;
+use crate::commands::output_buffer::BUFFER_SIZE_1025;
+use crate::common::{IMAGE_DATA_PACKET_LENGTH, IMAGE_SIZE_LENGTH_IN_BYTES};
+use crate::display_zones::DisplayZones;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedCommand {
+    WakeScreen,
+    Refresh,
+    Heartbeat,
+    SleepScreen,
+    Disconnect,
+    /// Brightness as sent, the device expects 0-100
+    SetBrightness(u8),
+    ClearAllImages,
+    ClearDisplayZoneImage(DisplayZones),
+    InitiateSetBackgroundImage {
+        image_size_bytes: u32,
+    },
+    InitiateSetDisplayZoneImage {
+        image_size_bytes: u32,
+        display_zone: DisplayZones,
+    },
+    /// Part of an image, without the leading report id.
+    ///
+    /// When decoded through a `Decoder` the padding after the end of the image is removed.
+    ImageDataPacket(Vec<u8>),
+    /// Anything not recognised (including known prefixes with invalid arguments), as written
+    Unknown(Vec<u8>),
+}
+
+/// Decodes a single frame (including the leading report id byte) without any context
+///
+/// Frames that do not match a known command but have the size of an image data packet
+/// are assumed to be one.
+pub fn decode(frame: &[u8]) -> DecodedCommand {
+    decode_command(frame).unwrap_or_else(|| match frame.len() {
+        BUFFER_SIZE_1025 if frame[0] == 0 => DecodedCommand::ImageDataPacket(frame[1..].to_vec()),
+        _ => DecodedCommand::Unknown(frame.to_vec()),
+    })
+}
+
+fn decode_command(frame: &[u8]) -> Option<DecodedCommand> {
+    let byte_after = |prefix: &[u8]| frame.get(prefix.len()).copied();
+    let image_size_after = |prefix: &[u8]| {
+        let size = frame.get(prefix.len()..prefix.len() + IMAGE_SIZE_LENGTH_IN_BYTES)?;
+        Some(u32::from_be_bytes(size.try_into().ok()?))
+    };
+
+    let command = if frame.starts_with(&messages::WAKE_SCREEN) {
+        DecodedCommand::WakeScreen
+    } else if frame.starts_with(&messages::REFRESH) {
+        DecodedCommand::Refresh
+    } else if frame.starts_with(&messages::HEARTBEAT) {
+        DecodedCommand::Heartbeat
+    } else if frame.starts_with(&messages::SLEEP_SCREEN) {
+        DecodedCommand::SleepScreen
+    } else if frame.starts_with(&messages::DISCONNECT) {
+        DecodedCommand::Disconnect
+    } else if frame.starts_with(&messages::SET_BRIGHTNESS) {
+        DecodedCommand::SetBrightness(byte_after(&messages::SET_BRIGHTNESS)?)
+    } else if frame.starts_with(&messages::CLEAR_ALL_IMAGES) {
+        DecodedCommand::ClearAllImages
+    } else if frame.starts_with(&messages::CLEAR_DISPLAY_ZONE_IMAGE) {
+        let display_zone = byte_after(&messages::CLEAR_DISPLAY_ZONE_IMAGE)?;
+        DecodedCommand::ClearDisplayZoneImage(display_zone.try_into().ok()?)
+    } else if frame.starts_with(&messages::INITIATE_SET_BACKGROUND_IMAGE) {
+        DecodedCommand::InitiateSetBackgroundImage {
+            image_size_bytes: image_size_after(&messages::INITIATE_SET_BACKGROUND_IMAGE)?,
+        }
+    } else if frame.starts_with(&messages::INITIATE_SET_DISPLAY_ZONE_IMAGE) {
+        let prefix = messages::INITIATE_SET_DISPLAY_ZONE_IMAGE;
+        let display_zone = frame.get(prefix.len() + IMAGE_SIZE_LENGTH_IN_BYTES)?;
+        DecodedCommand::InitiateSetDisplayZoneImage {
+            image_size_bytes: image_size_after(&prefix)?,
+            display_zone: (*display_zone).try_into().ok()?,
+        }
+    } else {
+        return None;
+    };
+
+    Some(command)
+}
+
+/// Decodes a sequence of frames, keeping track of image transfers in progress
+///
+/// While an image transfer is in progress every frame is treated as a data packet,
+/// the same way the device does.
+#[derive(Debug, Default)]
+pub struct Decoder {
+    remaining_image_bytes: usize,
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn decode(&mut self, frame: &[u8]) -> DecodedCommand {
+        if self.remaining_image_bytes > 0 {
+            let data = frame.get(1..).unwrap_or_default();
+            let length = data
+                .len()
+                .min(IMAGE_DATA_PACKET_LENGTH)
+                .min(self.remaining_image_bytes);
+            self.remaining_image_bytes -= length;
+
+            return DecodedCommand::ImageDataPacket(data[..length].to_vec());
+        }
+
+        let command = decode(frame);
+        match command {
+            DecodedCommand::InitiateSetBackgroundImage { image_size_bytes }
+            | DecodedCommand::InitiateSetDisplayZoneImage {
+                image_size_bytes, ..
+            } => self.remaining_image_bytes = image_size_bytes as usize,
+            _ => {}
+        }
+        command
+    }
+
+    /// Number of image bytes the current transfer still expects, 0 when idle
+    pub fn remaining_image_bytes(&self) -> usize {
+        self.remaining_image_bytes
+    }
+}
+
+impl fmt::Display for DecodedCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodedCommand::WakeScreen => write!(f, "WakeScreen"),
+            DecodedCommand::Refresh => write!(f, "Refresh"),
+            DecodedCommand::Heartbeat => write!(f, "Heartbeat"),
+            DecodedCommand::SleepScreen => write!(f, "SleepScreen"),
+            DecodedCommand::Disconnect => write!(f, "Disconnect"),
+            DecodedCommand::SetBrightness(brightness) => write!(f, "SetBrightness({})", brightness),
+            DecodedCommand::ClearAllImages => write!(f, "ClearAllImages"),
+            DecodedCommand::ClearDisplayZoneImage(display_zone) => {
+                write!(f, "ClearDisplayZoneImage({:?})", display_zone)
+            }
+            DecodedCommand::InitiateSetBackgroundImage { image_size_bytes } => {
+                write!(f, "InitiateSetBackgroundImage({} bytes)", image_size_bytes)
+            }
+            DecodedCommand::InitiateSetDisplayZoneImage {
+                image_size_bytes,
+                display_zone,
+            } => write!(
+                f,
+                "InitiateSetDisplayZoneImage({:?}, {} bytes)",
+                display_zone, image_size_bytes
+            ),
+            DecodedCommand::ImageDataPacket(data) => {
+                write!(f, "ImageDataPacket({} bytes)", data.len())
+            }
+            DecodedCommand::Unknown(raw) => {
+                write!(f, "Unknown({} bytes: {})", raw.len(), summarise(raw))
+            }
+        }
+    }
+}
+
+/// Shows the printable prefix of a frame (e.g. `\0CRT\0\0LIG\0\0(`), ignoring trailing padding
+pub fn summarise(frame: &[u8]) -> String {
+    let used = frame
+        .iter()
+        .rposition(|&byte| byte != 0)
+        .map_or(0, |last| last + 1);
+    let shown = used.min(32);
+
+    let mut summary: String = frame[..shown]
+        .iter()
+        .flat_map(|&byte| std::ascii::escape_default(byte))
+        .map(char::from)
+        .collect();
+
+    if used > shown {
+        summary.push_str(&format!("... ({} more bytes)", used - shown));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{
+        Command, clear_all_images_command_factory, clear_display_zone_image_command_factory,
+        disconnect_command_factory, heartbeat_command_factory,
+        initiate_set_background_command_factory, initiate_set_display_zone_image_command_factory,
+        refresh_command_factory, send_image_data_packet_command_factory,
+        set_brightness_command_factory, sleep_screen_command_factory, wake_screen_command_factory,
+    };
+    use crate::error::Result;
+    use std::cell::RefCell;
+
+    /// Runs a command and returns the frame it would have written
+    fn frame_of<const N: usize>(command: impl Command<N, Result<usize>>) -> Vec<u8> {
+        let written = RefCell::new(Vec::new());
+        command
+            .execute(|frame| {
+                written.replace(frame.to_vec());
+                Ok(N)
+            })
+            .unwrap();
+        written.into_inner()
+    }
+
+    #[test]
+    fn decodes_simple_commands() {
+        assert_eq!(
+            decode(&frame_of(wake_screen_command_factory())),
+            DecodedCommand::WakeScreen
+        );
+        assert_eq!(
+            decode(&frame_of(refresh_command_factory())),
+            DecodedCommand::Refresh
+        );
+        assert_eq!(
+            decode(&frame_of(set_brightness_command_factory(73))),
+            DecodedCommand::SetBrightness(73)
+        );
+        assert_eq!(
+            decode(&frame_of(clear_all_images_command_factory())),
+            DecodedCommand::ClearAllImages
+        );
+        assert_eq!(
+            decode(&frame_of(heartbeat_command_factory())),
+            DecodedCommand::Heartbeat
+        );
+        assert_eq!(
+            decode(&frame_of(sleep_screen_command_factory())),
+            DecodedCommand::SleepScreen
+        );
+        assert_eq!(
+            decode(&frame_of(disconnect_command_factory())),
+            DecodedCommand::Disconnect
+        );
+    }
+
+    #[test]
+    fn decodes_every_display_zone() {
+        let zones = [
+            DisplayZones::Button1,
+            DisplayZones::Button2,
+            DisplayZones::Button3,
+            DisplayZones::Button4,
+            DisplayZones::Button5,
+            DisplayZones::Button6,
+            DisplayZones::Button7,
+            DisplayZones::Button8,
+            DisplayZones::Button9,
+            DisplayZones::Button10,
+            DisplayZones::Button11,
+            DisplayZones::Button12,
+            DisplayZones::Button13,
+            DisplayZones::Button14,
+            DisplayZones::Button15,
+            DisplayZones::Touchscreen1,
+            DisplayZones::Touchscreen2,
+            DisplayZones::Touchscreen3,
+            DisplayZones::Touchscreen4,
+        ];
+
+        for zone in zones {
+            assert_eq!(
+                decode(&frame_of(clear_display_zone_image_command_factory(
+                    zone.into()
+                ))),
+                DecodedCommand::ClearDisplayZoneImage(zone)
+            );
+            assert_eq!(
+                decode(&frame_of(initiate_set_display_zone_image_command_factory(
+                    4242,
+                    zone.into()
+                ))),
+                DecodedCommand::InitiateSetDisplayZoneImage {
+                    image_size_bytes: 4242,
+                    display_zone: zone
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn decoder_follows_image_transfer() {
+        let mut decoder = Decoder::new();
+        let image: Vec<u8> = (0..1500).map(|i| i as u8).collect();
+
+        assert_eq!(
+            decoder.decode(&frame_of(initiate_set_background_command_factory(1500))),
+            DecodedCommand::InitiateSetBackgroundImage {
+                image_size_bytes: 1500
+            }
+        );
+
+        let mut received = Vec::new();
+        for chunk in image.chunks(IMAGE_DATA_PACKET_LENGTH) {
+            let mut packet = [0; IMAGE_DATA_PACKET_LENGTH];
+            packet[..chunk.len()].copy_from_slice(chunk);
+
+            match decoder.decode(&frame_of(send_image_data_packet_command_factory(packet))) {
+                DecodedCommand::ImageDataPacket(data) => received.extend(data),
+                other => panic!("expected a data packet, got {}", other),
+            }
+        }
+
+        assert_eq!(received, image);
+        assert_eq!(decoder.remaining_image_bytes(), 0);
+        assert_eq!(
+            decoder.decode(&frame_of(refresh_command_factory())),
+            DecodedCommand::Refresh
+        );
+    }
+
+    #[test]
+    fn data_that_looks_like_a_command_is_still_data_mid_transfer() {
+        let mut decoder = Decoder::new();
+        decoder.decode(&frame_of(initiate_set_display_zone_image_command_factory(
+            9,
+            DisplayZones::Button1.into(),
+        )));
+
+        assert_eq!(
+            decoder.decode(&messages::REFRESH),
+            DecodedCommand::ImageDataPacket(b"CRT\0\0STP".to_vec())
+        );
+    }
+
+    #[test]
+    fn unknown_prefix_keeps_raw_bytes() {
+        let frame = b"\0CRT\0\0XYZ".to_vec();
+
+        assert_eq!(decode(&frame), DecodedCommand::Unknown(frame));
+    }
+
+    #[test]
+    fn invalid_display_zone_is_unknown() {
+        let frame = [messages::CLEAR_DISPLAY_ZONE_IMAGE.as_slice(), &[42]].concat();
+
+        assert_eq!(decode(&frame), DecodedCommand::Unknown(frame));
+    }
+
+    #[test]
+    fn displays_unknown_frames_readably() {
+        let mut frame = [0u8; 513];
+        frame[..9].copy_from_slice(b"\0CRT\0\0XYZ");
+
+        assert_eq!(
+            decode(&frame).to_string(),
+            "Unknown(513 bytes: \\x00CRT\\x00\\x00XYZ)"
+        );
+    }
+}
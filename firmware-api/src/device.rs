@@ -1,21 +1,89 @@
 use crate::commands::{
-    Command, clear_display_zone_image_command_factory, initiate_set_background_command_factory,
+    Command, clear_display_zone_image_command_factory, disconnect_command_factory,
+    heartbeat_command_factory, initiate_set_background_command_factory,
     initiate_set_display_zone_image_command_factory, output_buffer, refresh_command_factory,
-    send_image_data_packet_command_factory, wake_screen_command_factory,
+    send_image_data_packet_command_factory, sleep_screen_command_factory,
+    wake_screen_command_factory,
 };
 use crate::commands::{clear_all_images_command_factory, set_brightness_command_factory};
 use crate::common::{ByteArray, IMAGE_DATA_PACKET_LENGTH};
 use crate::display_zones::DisplayZones;
-use crate::inputs::InputActions;
+use crate::error::{Error, Result};
+use crate::image_processing::{Resolution, ScalingMode, prepare_image};
 use crate::inputs::input_buffer::BUFFER_SIZE_13;
+use crate::inputs::{DeviceIdentity, InputActions, InputEvent};
+use crate::label::Label;
+use crate::profiles::{Akp05, DeviceProfile, Panel, ZoneLayout};
+use crate::span::{SpanLayout, span_image};
+#[cfg(feature = "acknowledged-transfer")]
+use crate::transfer::is_acknowledgement;
+use crate::transfer::{CancellationToken, TransferMode, TransferObserver, TransferStage};
 use hidapi::HidResult;
-use std::error::Error;
+use std::cell::Cell;
 use std::fs::File;
 use std::io::Read;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+pub mod batch;
+
+use batch::Batch;
 
 pub trait HidDeviceOperations {
     fn read(&self, buffer: &mut [u8]) -> HidResult<usize>;
     fn write(&self, data: &[u8]) -> HidResult<usize>;
+
+    /// Waits up to `timeout_ms` milliseconds for a report (-1 to wait indefinitely),
+    /// devices that cannot wait just read
+    fn read_timeout(&self, buffer: &mut [u8], _timeout_ms: i32) -> HidResult<usize> {
+        self.read(buffer)
+    }
+}
+
+/// Allows a device to be shared, e.g. to inspect a `MockHidDevice` after handing it to a `Device`
+impl<T: HidDeviceOperations + ?Sized> HidDeviceOperations for &T {
+    fn read(&self, buffer: &mut [u8]) -> HidResult<usize> {
+        (**self).read(buffer)
+    }
+
+    fn write(&self, data: &[u8]) -> HidResult<usize> {
+        (**self).write(data)
+    }
+
+    fn read_timeout(&self, buffer: &mut [u8], timeout_ms: i32) -> HidResult<usize> {
+        (**self).read_timeout(buffer, timeout_ms)
+    }
+}
+
+/// Same as for `&T`, for devices that are shared with another thread
+impl<T: HidDeviceOperations + ?Sized> HidDeviceOperations for Arc<T> {
+    fn read(&self, buffer: &mut [u8]) -> HidResult<usize> {
+        (**self).read(buffer)
+    }
+
+    fn write(&self, data: &[u8]) -> HidResult<usize> {
+        (**self).write(data)
+    }
+
+    fn read_timeout(&self, buffer: &mut [u8], timeout_ms: i32) -> HidResult<usize> {
+        (**self).read_timeout(buffer, timeout_ms)
+    }
+}
+
+/// Allows devices of different types to be used through the same `Device` type
+impl<T: HidDeviceOperations + ?Sized> HidDeviceOperations for Box<T> {
+    fn read(&self, buffer: &mut [u8]) -> HidResult<usize> {
+        (**self).read(buffer)
+    }
+
+    fn write(&self, data: &[u8]) -> HidResult<usize> {
+        (**self).write(data)
+    }
+
+    fn read_timeout(&self, buffer: &mut [u8], timeout_ms: i32) -> HidResult<usize> {
+        (**self).read_timeout(buffer, timeout_ms)
+    }
 }
 
 pub struct HidDeviceWrapper {
@@ -38,10 +106,21 @@ impl HidDeviceOperations for HidDeviceWrapper {
     fn write(&self, data: &[u8]) -> HidResult<usize> {
         self.device.write(data)
     }
+
+    fn read_timeout(&self, buffer: &mut [u8], timeout_ms: i32) -> HidResult<usize> {
+        self.device.read_timeout(buffer, timeout_ms)
+    }
 }
 
 pub trait InputHandler {
     fn handle(&self, action: InputActions);
+
+    /// Called by the `Device` for every input, implement it instead of `handle` to know
+    /// when (and from which device) the input was read. Passes the action on to `handle`
+    /// by default.
+    fn handle_event(&self, event: InputEvent) {
+        self.handle(event.action);
+    }
 }
 
 pub struct FunctionHandler {
@@ -60,9 +139,93 @@ impl InputHandler for FunctionHandler {
     }
 }
 
+/// Like `FunctionHandler`, but the closure can capture state
+pub struct ClosureHandler<F: Fn(InputActions) + Send> {
+    handler: F,
+}
+
+impl<F: Fn(InputActions) + Send> ClosureHandler<F> {
+    pub fn new(handler: F) -> Self {
+        Self { handler }
+    }
+}
+
+impl<F: Fn(InputActions) + Send> InputHandler for ClosureHandler<F> {
+    fn handle(&self, action: InputActions) {
+        (self.handler)(action);
+    }
+}
+
+/// Sends every event down a channel, so they can be processed on another thread.
+/// Events are discarded once the receiving end is dropped.
+pub struct ChannelHandler {
+    sender: Sender<InputEvent>,
+}
+
+impl ChannelHandler {
+    pub fn new(sender: Sender<InputEvent>) -> Self {
+        Self { sender }
+    }
+
+    /// Creates the channel as well, returning the handler along with its receiving end
+    pub fn channel() -> (Self, Receiver<InputEvent>) {
+        let (sender, receiver) = mpsc::channel();
+        (Self::new(sender), receiver)
+    }
+}
+
+impl InputHandler for ChannelHandler {
+    /// Sent as `InputEvent::unread`
+    fn handle(&self, action: InputActions) {
+        self.handle_event(InputEvent::unread(action));
+    }
+
+    fn handle_event(&self, event: InputEvent) {
+        self.sender.send(event).ok();
+    }
+}
+
+/// Passes every action on to each of its handlers, in the order they were added
+#[derive(Default)]
+pub struct FanOutHandler {
+    handlers: Vec<Box<dyn InputHandler + Send>>,
+}
+
+impl FanOutHandler {
+    pub fn new(handlers: Vec<Box<dyn InputHandler + Send>>) -> Self {
+        Self { handlers }
+    }
+
+    pub fn add(&mut self, handler: impl InputHandler + Send + 'static) {
+        self.handlers.push(Box::new(handler));
+    }
+}
+
+impl InputHandler for FanOutHandler {
+    fn handle(&self, action: InputActions) {
+        for handler in &self.handlers {
+            handler.handle(action.clone());
+        }
+    }
+
+    fn handle_event(&self, event: InputEvent) {
+        for handler in &self.handlers {
+            handler.handle_event(event.clone());
+        }
+    }
+}
+
 pub struct Device<H: HidDeviceOperations, I: InputHandler> {
     hid_device: H,
     handler: I,
+    scaling_mode: ScalingMode,
+    transfer_mode: TransferMode,
+    profile: &'static dyn DeviceProfile,
+    /// Last brightness written, the device cannot be asked for it
+    brightness: Cell<Option<u8>>,
+    serial_number: Arc<str>,
+    /// Sequence number of the next input
+    next_sequence: Cell<u64>,
 }
 
 impl<H: HidDeviceOperations, I: InputHandler> Device<H, I> {
@@ -72,6 +235,12 @@ impl<H: HidDeviceOperations, I: InputHandler> Device<H, I> {
         Self {
             hid_device,
             handler,
+            scaling_mode: ScalingMode::default(),
+            transfer_mode: TransferMode::default(),
+            profile: &Akp05,
+            brightness: Cell::new(None),
+            serial_number: Arc::from(""),
+            next_sequence: Cell::new(0),
         }
     }
 
@@ -83,93 +252,433 @@ impl<H: HidDeviceOperations, I: InputHandler> Device<H, I> {
         self.handler = handler;
     }
 
+    /// Controls how images that do not match a display zone's aspect ratio are fitted
+    /// before being uploaded (letterboxed by default)
+    pub fn set_scaling_mode(&mut self, scaling_mode: ScalingMode) {
+        self.scaling_mode = scaling_mode;
+    }
+
+    /// Controls how often image uploads are sent again when they fail (and whether they wait
+    /// for the device to acknowledge them), see the `transfer` module
+    pub fn set_transfer_mode(&mut self, transfer_mode: TransferMode) {
+        self.transfer_mode = transfer_mode;
+    }
+
+    /// The model the device is driven as, an AKP05 unless changed with `set_profile`
+    pub fn profile(&self) -> &'static dyn DeviceProfile {
+        self.profile
+    }
+
+    /// Sets the model of the device, which decides the zone ids, image resolutions and
+    /// how input reports are decoded (see `profiles::find_profile`)
+    pub fn set_profile(&mut self, profile: &'static dyn DeviceProfile) {
+        self.profile = profile;
+    }
+
+    /// Tells which launchpad the inputs of this device came from, see `InputEvent::device`.
+    /// The device cannot be asked for it, open it with `HidDeviceWrapper` and ask hidapi.
+    pub fn set_serial_number(&mut self, serial_number: impl Into<Arc<str>>) {
+        self.serial_number = serial_number.into();
+    }
+
+    /// Identifies this device in the `InputEvent`s it hands to its handler
+    pub fn identity(&self) -> DeviceIdentity {
+        DeviceIdentity {
+            serial_number: self.serial_number.clone(),
+            model: self.profile.name(),
+        }
+    }
+
     /// Processes a hardware action performed by the launchpad.
     /// This will return an `InputAction` that is to be handled by a callback
-    pub fn read_input(&self) -> Result<(), Box<dyn Error>> {
+    pub fn read_input(&self) -> Result<()> {
         let mut buffer: ByteArray<BUFFER_SIZE_13> = [0; BUFFER_SIZE_13];
         self.hid_device.read(&mut buffer)?;
+        self.handle_report(buffer);
+        Ok(())
+    }
 
+    /// Same as `read_input`, but waits up to `timeout` for an action instead of
+    /// returning straight away (or blocking indefinitely)
+    pub fn read_input_timeout(&self, timeout: Duration) -> Result<()> {
+        let mut buffer: ByteArray<BUFFER_SIZE_13> = [0; BUFFER_SIZE_13];
+        self.hid_device
+            .read_timeout(&mut buffer, timeout_millis(timeout))?;
+        self.handle_report(buffer);
+        Ok(())
+    }
+
+    fn handle_report(&self, buffer: ByteArray<BUFFER_SIZE_13>) {
         // If we have empty buffer means that no available message was there
         if !buffer.iter().all(|&bit| bit == 0) {
-            let action = InputActions::from(buffer);
-            self.handler.handle(action);
+            let read_at = Instant::now();
+            let sequence = self.next_sequence.replace(self.next_sequence.get() + 1);
+
+            self.handler.handle_event(InputEvent {
+                action: self.profile.decode_input(buffer),
+                read_at,
+                sequence,
+                device: self.identity(),
+                report: buffer,
+            });
         }
+    }
 
-        Ok(())
+    /// Sets the brightness of the launchpad displays to a percentage (0 - 100),
+    /// see `fade::BrightnessFade` to change it gradually
+    /// * `brightness` - any value between 0 and 100, anything else is an `Error::InvalidBrightness`
+    pub fn set_brightness(&self, brightness: u8) -> Result<usize> {
+        let set_brightness_command =
+            set_brightness_command_factory(validate_brightness(brightness)?);
+        let written = set_brightness_command.execute(|buf| self.write(buf))?;
+        self.brightness.set(Some(brightness));
+        Ok(written)
     }
 
-    /// Sets the brightness of the launchpad displays to a percentage (0 - 100)
-    /// * `brightness` - any value between 0 and 100
-    pub fn set_brightness(&self, brightness: u8) -> HidResult<usize> {
-        let set_brightness_command = set_brightness_command_factory(match brightness {
-            0..=100 => brightness,
-            _ => 0,
-        });
-        set_brightness_command.execute(|buf| self.hid_device.write(buf))
+    /// The brightness last set through this `Device`, `None` until one is set
+    pub fn brightness(&self) -> Option<u8> {
+        self.brightness.get()
     }
 
     /// Will wake up the device from its sleep state, otherwise does nothing
-    pub fn wake_screen(&self) -> HidResult<usize> {
+    pub fn wake_screen(&self) -> Result<usize> {
         let wake_screen_command = wake_screen_command_factory();
-        wake_screen_command.execute(|buf| self.hid_device.write(buf))
+        wake_screen_command.execute(|buf| self.write(buf))
+    }
+
+    /// Lets the device know the host is still there, see `keep_alive::KeepAlive` to send
+    /// these periodically
+    pub fn send_heartbeat(&self) -> Result<usize> {
+        let heartbeat_command = heartbeat_command_factory();
+        heartbeat_command.execute(|buf| self.write(buf))
     }
 
-    pub fn refresh(&self) -> HidResult<usize> {
+    /// Turns the displays off (standby) until `wake_screen` is called
+    pub fn sleep_screen(&self) -> Result<usize> {
+        let sleep_screen_command = sleep_screen_command_factory();
+        sleep_screen_command.execute(|buf| self.write(buf))
+    }
+
+    /// Tells the device the host is done with it, call this before closing the device
+    pub fn disconnect(&self) -> Result<usize> {
+        let disconnect_command = disconnect_command_factory();
+        disconnect_command.execute(|buf| self.write(buf))
+    }
+
+    /// Stages several display changes to be sent at once, followed by a single refresh
+    pub fn batch(&self) -> Batch<'_, H, I> {
+        Batch::new(self)
+    }
+
+    pub fn refresh(&self) -> Result<usize> {
         let refresh_command = refresh_command_factory();
-        refresh_command.execute(|buf| self.hid_device.write(buf))
+        refresh_command.execute(|buf| self.write(buf))
     }
 
-    pub fn clear_all_images(&self) -> HidResult<usize> {
+    pub fn clear_all_images(&self) -> Result<usize> {
         let clear_all_images_command = clear_all_images_command_factory();
-        clear_all_images_command.execute(|buf| self.hid_device.write(buf))
+        clear_all_images_command.execute(|buf| self.write(buf))
     }
 
-    pub fn clear_display_zone_image(&self, display_zone: DisplayZones) -> HidResult<usize> {
+    pub fn clear_display_zone_image(&self, display_zone: DisplayZones) -> Result<usize> {
+        let zone = self.zone_layout(display_zone)?;
         let clear_display_zone_image_command =
-            clear_display_zone_image_command_factory(display_zone);
-        clear_display_zone_image_command.execute(|buf| self.hid_device.write(buf))
+            clear_display_zone_image_command_factory(zone.wire_id);
+        clear_display_zone_image_command.execute(|buf| self.write(buf))
+    }
+
+    /// Sets the image shown across the whole display when no zone images are set.
+    ///
+    /// The image can be in any supported format and size, it will be converted to a
+    /// JPEG matching the display resolution before being sent.
+    pub fn set_background_image(&self, file: File) -> Result<usize> {
+        self.set_background_image_from_reader(file)
     }
 
-    pub fn set_background_image(&self, file: File) -> HidResult<usize> {
+    /// Same as `set_background_image`, but reads the encoded image from any source
+    /// (e.g. a network stream or a `Cursor` over generated data)
+    pub fn set_background_image_from_reader(&self, reader: impl Read) -> Result<usize> {
+        self.set_background_image_from_bytes(read_image(reader)?)
+    }
+
+    /// Same as `set_background_image`, but takes the encoded image from memory
+    /// (`&[u8]`, `Vec<u8>`, ...) so that no file is needed
+    pub fn set_background_image_from_bytes(&self, image: impl AsRef<[u8]>) -> Result<usize> {
+        self.set_background_image_with_progress(image, |_, _| {}, &CancellationToken::default())
+    }
+
+    /// Same as `set_background_image_from_bytes`, reporting every packet sent to `progress`.
+    /// Fails with `Error::Cancelled` once `cancellation` is cancelled.
+    pub fn set_background_image_with_progress(
+        &self,
+        image: impl AsRef<[u8]>,
+        mut progress: impl TransferObserver,
+        cancellation: &CancellationToken,
+    ) -> Result<usize> {
+        let image = self.prepare_image(image.as_ref(), self.profile.background_resolution())?;
+
         // Let the device know to prepare
-        let init_command = initiate_set_background_command_factory(file.metadata()?.len() as u32);
-        self.write_image_to_device_command(init_command, file)
+        let init_command = initiate_set_background_command_factory(image.len() as u32);
+        self.write_image_to_device_command(
+            init_command,
+            image.as_slice(),
+            &mut progress,
+            cancellation,
+        )
     }
 
-    pub fn set_display_zone_image(
+    /// Sets the image of a single display zone.
+    ///
+    /// The image can be in any supported format and size, it will be converted to a
+    /// JPEG matching the zone's resolution before being sent.
+    pub fn set_display_zone_image(&self, display_zone: DisplayZones, file: File) -> Result<usize> {
+        self.set_display_zone_image_from_reader(display_zone, file)
+    }
+
+    /// Same as `set_display_zone_image`, but reads the encoded image from any source
+    /// (e.g. a network stream or a `Cursor` over generated data)
+    pub fn set_display_zone_image_from_reader(
         &self,
         display_zone: DisplayZones,
-        file: File,
-    ) -> HidResult<usize> {
-        let init_command = initiate_set_display_zone_image_command_factory(
-            file.metadata()?.len() as u32,
+        reader: impl Read,
+    ) -> Result<usize> {
+        self.set_display_zone_image_from_bytes(display_zone, read_image(reader)?)
+    }
+
+    /// Same as `set_display_zone_image`, but takes the encoded image from memory
+    /// (`&[u8]`, `Vec<u8>`, ...) so that no file is needed
+    pub fn set_display_zone_image_from_bytes(
+        &self,
+        display_zone: DisplayZones,
+        image: impl AsRef<[u8]>,
+    ) -> Result<usize> {
+        self.set_display_zone_image_with_progress(
             display_zone,
-        );
-        self.write_image_to_device_command(init_command, file)
+            image,
+            |_, _| {},
+            &CancellationToken::default(),
+        )
+    }
+
+    /// Same as `set_display_zone_image_from_bytes`, reporting every packet sent to `progress`.
+    /// Fails with `Error::Cancelled` once `cancellation` is cancelled.
+    pub fn set_display_zone_image_with_progress(
+        &self,
+        display_zone: DisplayZones,
+        image: impl AsRef<[u8]>,
+        mut progress: impl TransferObserver,
+        cancellation: &CancellationToken,
+    ) -> Result<usize> {
+        let zone = self.zone_layout(display_zone)?;
+        let image = self.prepare_image(image.as_ref(), zone.resolution)?;
+
+        let init_command =
+            initiate_set_display_zone_image_command_factory(image.len() as u32, zone.wire_id);
+        self.write_image_to_device_command(
+            init_command,
+            image.as_slice(),
+            &mut progress,
+            cancellation,
+        )
     }
 
-    /// Generic factory to deal with image writing operations
+    /// Renders `label` at the zone's resolution and shows it on the zone
+    pub fn set_display_zone_label(
+        &self,
+        display_zone: DisplayZones,
+        label: &Label,
+    ) -> Result<usize> {
+        let image = label.render_jpeg(self.zone_layout(display_zone)?.resolution)?;
+
+        self.set_display_zone_image_from_bytes(display_zone, image)
+    }
+
+    /// Spreads one image across every zone of `panels`, as if they were a single screen
+    /// (see the `span` module). The zones are updated as one batch.
+    pub fn set_spanned_image(
+        &self,
+        panels: &[Panel],
+        image: impl AsRef<[u8]>,
+        layout: &SpanLayout,
+    ) -> Result<usize> {
+        let image = image::load_from_memory(image.as_ref())?;
+        let slices = span_image(
+            &image,
+            self.profile.display_zones(),
+            panels,
+            layout,
+            self.scaling_mode,
+        )?;
+
+        let mut batch = self.batch();
+        for (display_zone, slice) in slices {
+            batch.set_display_zone_image_from_bytes(display_zone, slice)?;
+        }
+        batch.commit()
+    }
+
+    fn zone_layout(&self, display_zone: DisplayZones) -> Result<&'static ZoneLayout> {
+        self.profile
+            .zone_layout(display_zone)
+            .ok_or(Error::UnsupportedDisplayZone(display_zone))
+    }
+
+    /// Converts the image into a format the device can display
+    fn prepare_image(&self, source: &[u8], resolution: Resolution) -> Result<Vec<u8>> {
+        Ok(prepare_image(source, resolution, self.scaling_mode)?)
+    }
+
+    /// Writes a whole frame, a device that accepts only part of it is treated as an error
+    fn write(&self, frame: &[u8]) -> Result<usize> {
+        let written = self.hid_device.write(frame)?;
+        if written < frame.len() {
+            return Err(Error::WriteShort {
+                written,
+                expected: frame.len(),
+            });
+        }
+        Ok(written)
+    }
+
+    /// Generic factory to deal with image writing operations, sends the image again
+    /// according to the `TransferMode`
     fn write_image_to_device_command(
         &self,
-        init_command: impl Command<{ output_buffer::BUFFER_SIZE_1025 }, HidResult<usize>>,
-        mut file: File,
-    ) -> HidResult<usize> {
+        init_command: impl Command<{ output_buffer::BUFFER_SIZE_1025 }, Result<usize>>,
+        image: &[u8],
+        progress: &mut dyn TransferObserver,
+        cancellation: &CancellationToken,
+    ) -> Result<usize> {
+        let retries = match self.transfer_mode {
+            TransferMode::Unacknowledged { retries } => retries,
+            #[cfg(feature = "acknowledged-transfer")]
+            TransferMode::Acknowledged { retries, .. } => retries,
+        };
+
+        let attempts = retries.saturating_add(1);
+        let mut attempt = 1;
+        loop {
+            let result = self
+                .send_image(&init_command, image, progress, cancellation)
+                .and_then(|written| {
+                    self.confirm_image()?;
+                    Ok(written)
+                });
+
+            match result {
+                Ok(written) => return Ok(written),
+                // Either there is nothing to send the image to anymore, or nobody wants it
+                Err((_, error @ (Error::Disconnected | Error::Cancelled))) => return Err(error),
+                Err((_, error)) if attempts == 1 => return Err(error),
+                Err((stage, source)) if attempt == attempts => {
+                    return Err(Error::TransferFailed {
+                        stage,
+                        attempts,
+                        source: Box::new(source),
+                    });
+                }
+                Err(_) => attempt += 1,
+            }
+        }
+    }
+
+    /// Writes the initiating frame followed by the image data, returns the stage that
+    /// failed along with the error
+    fn send_image(
+        &self,
+        init_command: &impl Command<{ output_buffer::BUFFER_SIZE_1025 }, Result<usize>>,
+        image: &[u8],
+        progress: &mut dyn TransferObserver,
+        cancellation: &CancellationToken,
+    ) -> std::result::Result<usize, (TransferStage, Error)> {
+        if cancellation.is_cancelled() {
+            return Err((TransferStage::Initiate, Error::Cancelled));
+        }
+
         // Let the device know to prepare
-        init_command.execute(|buf| self.hid_device.write(buf))?;
+        init_command
+            .execute(|buf| self.write(buf))
+            .map_err(|error| (TransferStage::Initiate, error))?;
+
+        let mut written = 0;
+
+        // There is no point continuing once a packet was lost
+        for (index, chunk) in image.chunks(IMAGE_DATA_PACKET_LENGTH).enumerate() {
+            if cancellation.is_cancelled() {
+                return Err((TransferStage::Chunk(index), Error::Cancelled));
+            }
 
-        let mut buffer: ByteArray<IMAGE_DATA_PACKET_LENGTH> = [0; IMAGE_DATA_PACKET_LENGTH];
-        let mut last_result = Ok(0);
+            let mut buffer: ByteArray<IMAGE_DATA_PACKET_LENGTH> = [0; IMAGE_DATA_PACKET_LENGTH];
+            buffer[..chunk.len()].copy_from_slice(chunk);
 
-        // Send image data until EOF
-        while file.read(&mut buffer)? > 0 {
             let command = send_image_data_packet_command_factory(buffer);
-            last_result = command.execute(|buf| self.hid_device.write(buf));
+            written = command
+                .execute(|buf| self.write(buf))
+                .map_err(|error| (TransferStage::Chunk(index), error))?;
+
+            let sent = index * IMAGE_DATA_PACKET_LENGTH + chunk.len();
+            progress.on_progress(sent, image.len());
         }
 
-        last_result
+        Ok(written)
+    }
+
+    /// Waits for the device to acknowledge the image that was just sent, if the `TransferMode`
+    /// asks for it
+    fn confirm_image(&self) -> std::result::Result<(), (TransferStage, Error)> {
+        match self.transfer_mode {
+            TransferMode::Unacknowledged { .. } => Ok(()),
+            #[cfg(feature = "acknowledged-transfer")]
+            TransferMode::Acknowledged { timeout, .. } => self
+                .wait_for_acknowledgement(timeout)
+                .map_err(|error| (TransferStage::Acknowledgement, error)),
+        }
+    }
+
+    /// Reads reports until the device acknowledges an image, input arriving in the
+    /// meantime is passed on to the handler as usual
+    #[cfg(feature = "acknowledged-transfer")]
+    fn wait_for_acknowledgement(&self, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(Error::Timeout);
+            }
+
+            let mut buffer: ByteArray<BUFFER_SIZE_13> = [0; BUFFER_SIZE_13];
+            self.hid_device
+                .read_timeout(&mut buffer, timeout_millis(remaining))?;
+
+            if is_acknowledgement(&buffer) {
+                return Ok(());
+            }
+            self.handle_report(buffer);
+        }
+    }
+}
+
+pub(crate) fn validate_brightness(brightness: u8) -> Result<u8> {
+    match brightness {
+        0..=100 => Ok(brightness),
+        _ => Err(Error::InvalidBrightness(brightness)),
     }
 }
 
+/// hidapi takes timeouts in milliseconds, rounded up so that short waits do not become polls
+fn timeout_millis(timeout: Duration) -> i32 {
+    i32::try_from(timeout.as_nanos().div_ceil(1_000_000)).unwrap_or(i32::MAX)
+}
+
+/// The whole image is needed up front, both for conversion and because the device
+/// has to be told the size of the image before any data is sent
+fn read_image(mut reader: impl Read) -> Result<Vec<u8>> {
+    let mut image = Vec::new();
+    reader.read_to_end(&mut image)?;
+    Ok(image)
+}
+
 impl Device<HidDeviceWrapper, FunctionHandler> {
     pub fn from_hid_device(
         hid_device: hidapi::HidDevice,
@@ -179,3 +688,662 @@ impl Device<HidDeviceWrapper, FunctionHandler> {
         Self::new(HidDeviceWrapper::new(hid_device, blocking_read), handler)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::messages;
+    use crate::decoder::DecodedCommand;
+    use crate::inputs::buttons::{BUTTON_3_PRESSED, BUTTON_14_PRESSED, ButtonActions};
+    use crate::inputs::knobs::{KNOB_2_CLOCKWISE, KnobActions};
+    use crate::mock::MockHidDevice;
+    use crate::profiles::Akp153;
+    use hidapi::HidError;
+    use image::{DynamicImage, ImageFormat, RgbImage};
+    use std::cell::{Cell, RefCell};
+    use std::io::Cursor;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct RecordingHandler {
+        actions: RefCell<Vec<InputActions>>,
+    }
+
+    impl InputHandler for &RecordingHandler {
+        fn handle(&self, action: InputActions) {
+            self.actions.borrow_mut().push(action);
+        }
+    }
+
+    fn encoded_image(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(width, height))
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn input_reports_are_passed_to_the_handler() {
+        let mock = MockHidDevice::new();
+        let handler = RecordingHandler::default();
+        let device = Device::new(&mock, &handler);
+
+        mock.queue_input(BUTTON_3_PRESSED);
+        mock.queue_input(KNOB_2_CLOCKWISE);
+
+        device.read_input().unwrap();
+        device.read_input().unwrap();
+        // Nothing queued, so nothing should be handled
+        device.read_input().unwrap();
+
+        assert_eq!(
+            *handler.actions.borrow(),
+            vec![
+                InputActions::Button(ButtonActions::Button3Pressed),
+                InputActions::Knob(KnobActions::Knob2Clockwise)
+            ]
+        );
+    }
+
+    #[derive(Default)]
+    struct EventRecorder {
+        events: Mutex<Vec<InputEvent>>,
+    }
+
+    impl InputHandler for Arc<EventRecorder> {
+        fn handle(&self, _action: InputActions) {
+            unreachable!("the device hands out events")
+        }
+
+        fn handle_event(&self, event: InputEvent) {
+            self.events.lock().unwrap().push(event);
+        }
+    }
+
+    #[test]
+    fn inputs_are_numbered_and_timestamped() {
+        let mock = MockHidDevice::new();
+        let recorder = Arc::new(EventRecorder::default());
+        let mut device = Device::new(&mock, recorder.clone());
+        device.set_serial_number("AKP05-LEFT");
+
+        let before = Instant::now();
+        mock.queue_input(BUTTON_3_PRESSED);
+        mock.queue_input(KNOB_2_CLOCKWISE);
+        device.read_input().unwrap();
+        device.read_input().unwrap();
+        device.read_input().unwrap();
+
+        let events = recorder.events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[1].action,
+            InputActions::Knob(KnobActions::Knob2Clockwise)
+        );
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| event.sequence)
+                .collect::<Vec<_>>(),
+            [0, 1]
+        );
+        assert!(before <= events[0].read_at && events[0].read_at <= events[1].read_at);
+        assert_eq!(&*events[0].device.serial_number, "AKP05-LEFT");
+        assert_eq!(events[0].device.model, "AKP05");
+    }
+
+    #[test]
+    fn fan_out_handlers_pass_events_on() {
+        let mock = MockHidDevice::new();
+        let recorder = Arc::new(EventRecorder::default());
+        let mut handler = FanOutHandler::default();
+        handler.add(recorder.clone());
+        let device = Device::new(&mock, handler);
+
+        mock.queue_input(BUTTON_3_PRESSED);
+        device.read_input().unwrap();
+
+        assert_eq!(recorder.events.lock().unwrap().len(), 1);
+    }
+
+    /// Only ever accepts half of what is written
+    struct ShortWriter;
+
+    impl HidDeviceOperations for ShortWriter {
+        fn read(&self, _buffer: &mut [u8]) -> HidResult<usize> {
+            Ok(0)
+        }
+
+        fn write(&self, data: &[u8]) -> HidResult<usize> {
+            Ok(data.len() / 2)
+        }
+    }
+
+    #[test]
+    fn closures_can_capture_state() {
+        let mock = MockHidDevice::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        let counted = count.clone();
+        let device = Device::new(
+            &mock,
+            ClosureHandler::new(move |_| {
+                counted.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+
+        mock.queue_input(BUTTON_3_PRESSED);
+        mock.queue_input(KNOB_2_CLOCKWISE);
+        device.read_input().unwrap();
+        device.read_input().unwrap();
+
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn actions_are_fanned_out_to_every_handler() {
+        let mock = MockHidDevice::new();
+        let (first, first_actions) = ChannelHandler::channel();
+        let (second, second_actions) = ChannelHandler::channel();
+        let mut handler = FanOutHandler::default();
+        handler.add(first);
+        handler.add(second);
+        let device = Device::new(&mock, handler);
+
+        mock.queue_input(BUTTON_3_PRESSED);
+        device.read_input().unwrap();
+
+        let event = first_actions.try_recv().unwrap();
+        assert_eq!(
+            event.action,
+            InputActions::Button(ButtonActions::Button3Pressed)
+        );
+        assert_eq!(event.report, BUTTON_3_PRESSED);
+        assert_eq!(second_actions.try_recv(), Ok(event));
+    }
+
+    #[test]
+    fn channel_handler_outlives_its_receiver() {
+        let (handler, receiver) = ChannelHandler::channel();
+        drop(receiver);
+
+        handler.handle(InputActions::Unknown);
+    }
+
+    #[test]
+    fn channel_handler_sends_actions_without_an_event_as_unread() {
+        let (handler, receiver) = ChannelHandler::channel();
+
+        handler.handle(InputActions::Unknown);
+
+        let event = receiver.try_recv().unwrap();
+        assert_eq!(event.action, InputActions::Unknown);
+        assert_eq!(event.sequence, 0);
+        assert_eq!(event.device.serial_number.as_ref(), "");
+    }
+
+    #[test]
+    fn disconnects_are_reported_as_such() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        mock.fail_next_read(HidError::HidApiError {
+            message: "hid_read_timeout: device disconnected".to_string(),
+        });
+
+        assert!(matches!(device.read_input(), Err(Error::Disconnected)));
+    }
+
+    #[test]
+    fn partial_writes_are_an_error() {
+        let device = Device::new(ShortWriter, FunctionHandler::new(|_| {}));
+
+        assert!(matches!(
+            device.refresh(),
+            Err(Error::WriteShort {
+                written: 256,
+                expected: 513
+            })
+        ));
+    }
+
+    #[test]
+    fn read_errors_are_returned() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        mock.fail_next_read(HidError::HidApiErrorEmpty);
+
+        assert!(device.read_input().is_err());
+    }
+
+    #[test]
+    fn sends_simple_commands() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        device.wake_screen().unwrap();
+        device.refresh().unwrap();
+        device.set_brightness(40).unwrap();
+        device
+            .clear_display_zone_image(DisplayZones::Button2)
+            .unwrap();
+        device.clear_all_images().unwrap();
+
+        mock.assert_screen_woken();
+        mock.assert_refreshed();
+        mock.assert_brightness_set(40);
+        mock.assert_display_zone_cleared(DisplayZones::Button2);
+        mock.assert_all_images_cleared();
+        assert!(mock.written_frames().iter().all(|frame| frame.len() == 513));
+    }
+
+    #[test]
+    fn brightness_above_100_is_rejected() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        device.set_brightness(40).unwrap();
+        assert!(matches!(
+            device.set_brightness(101),
+            Err(Error::InvalidBrightness(101))
+        ));
+
+        assert_eq!(mock.written_frames().len(), 1);
+        assert_eq!(device.brightness(), Some(40));
+    }
+
+    #[test]
+    fn sends_connection_commands() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        device.send_heartbeat().unwrap();
+        device.sleep_screen().unwrap();
+        device.disconnect().unwrap();
+
+        mock.assert_heartbeat_sent();
+        mock.assert_screen_slept();
+        mock.assert_disconnect_sent();
+    }
+
+    #[test]
+    fn correctly_sized_jpeg_is_uploaded_unchanged() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        let image = encoded_image(112, 112, ImageFormat::Jpeg);
+
+        device
+            .set_display_zone_image_from_bytes(DisplayZones::Button9, &image)
+            .unwrap();
+
+        assert_eq!(
+            mock.assert_display_zone_image_uploaded(DisplayZones::Button9),
+            image
+        );
+        assert!(
+            mock.written_frames()
+                .iter()
+                .all(|frame| frame.len() == 1025)
+        );
+    }
+
+    #[test]
+    fn other_images_are_converted_before_upload() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        device
+            .set_background_image_from_reader(Cursor::new(encoded_image(
+                1024,
+                768,
+                ImageFormat::Png,
+            )))
+            .unwrap();
+
+        let uploaded = image::load_from_memory_with_format(
+            &mock.assert_background_image_uploaded(),
+            ImageFormat::Jpeg,
+        )
+        .unwrap();
+        assert_eq!((uploaded.width(), uploaded.height()), (800, 480));
+    }
+
+    #[test]
+    fn readers_upload_the_same_frames_as_buffers() {
+        let image = encoded_image(300, 200, ImageFormat::Png);
+        let from_bytes = MockHidDevice::new();
+        let from_reader = MockHidDevice::new();
+
+        Device::new(&from_bytes, FunctionHandler::new(|_| {}))
+            .set_display_zone_image_from_bytes(DisplayZones::Button4, &image)
+            .unwrap();
+        Device::new(&from_reader, FunctionHandler::new(|_| {}))
+            .set_display_zone_image_from_reader(DisplayZones::Button4, image.as_slice())
+            .unwrap();
+
+        assert!(!from_bytes.written_frames().is_empty());
+        assert_eq!(from_bytes.written_frames(), from_reader.written_frames());
+    }
+
+    /// Reads part of an image, then fails
+    struct FailingReader(bool);
+
+    impl Read for FailingReader {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            match std::mem::replace(&mut self.0, true) {
+                false => {
+                    buffer[0] = 0xff;
+                    Ok(1)
+                }
+                true => Err(std::io::Error::other("connection reset")),
+            }
+        }
+    }
+
+    #[test]
+    fn read_errors_are_returned_before_anything_is_sent() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        assert!(matches!(
+            device.set_background_image_from_reader(FailingReader(false)),
+            Err(Error::Io(_))
+        ));
+        assert!(matches!(
+            device.set_display_zone_image_from_reader(DisplayZones::Button1, FailingReader(false)),
+            Err(Error::Io(_))
+        ));
+        assert!(mock.written_frames().is_empty());
+    }
+
+    #[test]
+    fn write_errors_abort_the_upload() {
+        let mock = MockHidDevice::new();
+        let mut device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        device.set_transfer_mode(TransferMode::Unacknowledged { retries: 0 });
+
+        mock.fail_next_write(HidError::HidApiErrorEmpty);
+
+        assert!(
+            device
+                .set_display_zone_image_from_bytes(
+                    DisplayZones::Touchscreen1,
+                    encoded_image(176, 112, ImageFormat::Jpeg)
+                )
+                .is_err()
+        );
+        assert!(mock.written_frames().is_empty());
+    }
+
+    /// Fails the second data packet of every image, counting from each initiating frame
+    struct SecondChunkFails {
+        mock: MockHidDevice,
+        packets: Cell<usize>,
+    }
+
+    impl HidDeviceOperations for SecondChunkFails {
+        fn read(&self, buffer: &mut [u8]) -> HidResult<usize> {
+            self.mock.read(buffer)
+        }
+
+        fn write(&self, data: &[u8]) -> HidResult<usize> {
+            if data[1..].starts_with(b"CRT") {
+                self.packets.set(0);
+            } else {
+                self.packets.set(self.packets.get() + 1);
+                if self.packets.get() == 2 {
+                    return Err(HidError::HidApiErrorEmpty);
+                }
+            }
+            self.mock.write(data)
+        }
+    }
+
+    fn initiated_uploads(mock: &MockHidDevice) -> usize {
+        mock.written_frames()
+            .iter()
+            .filter(|frame| {
+                matches!(
+                    crate::decoder::decode(frame),
+                    DecodedCommand::InitiateSetDisplayZoneImage { .. }
+                        | DecodedCommand::InitiateSetBackgroundImage { .. }
+                )
+            })
+            .count()
+    }
+
+    #[cfg(feature = "acknowledged-transfer")]
+    #[test]
+    fn acknowledged_uploads_wait_for_the_device() {
+        let mock = MockHidDevice::new();
+        let handler = RecordingHandler::default();
+        let mut device = Device::new(&mock, &handler);
+        device.set_transfer_mode(TransferMode::acknowledged());
+        let image = encoded_image(112, 112, ImageFormat::Jpeg);
+
+        mock.queue_input(BUTTON_3_PRESSED);
+        mock.queue_acknowledgement();
+
+        device
+            .set_display_zone_image_from_bytes(DisplayZones::Button1, &image)
+            .unwrap();
+
+        assert_eq!(
+            mock.assert_display_zone_image_uploaded(DisplayZones::Button1),
+            image
+        );
+        // Input read while waiting must not be lost
+        assert_eq!(
+            *handler.actions.borrow(),
+            vec![InputActions::Button(ButtonActions::Button3Pressed)]
+        );
+    }
+
+    #[test]
+    fn failed_uploads_are_sent_again() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        let image = encoded_image(112, 112, ImageFormat::Jpeg);
+
+        mock.fail_next_write(HidError::HidApiErrorEmpty);
+
+        device
+            .set_display_zone_image_from_bytes(DisplayZones::Button1, &image)
+            .unwrap();
+
+        assert_eq!(
+            mock.assert_display_zone_image_uploaded(DisplayZones::Button1),
+            image
+        );
+    }
+
+    #[cfg(feature = "acknowledged-transfer")]
+    #[test]
+    fn transfers_fail_once_retries_are_exhausted() {
+        let mock = MockHidDevice::new();
+        let mut device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        device.set_transfer_mode(TransferMode::Acknowledged {
+            retries: 1,
+            timeout: Duration::from_millis(1),
+        });
+
+        let result = device.set_display_zone_image_from_bytes(
+            DisplayZones::Button1,
+            encoded_image(112, 112, ImageFormat::Jpeg),
+        );
+
+        match result {
+            Err(Error::TransferFailed {
+                stage: TransferStage::Acknowledgement,
+                attempts: 2,
+                source,
+            }) => assert!(matches!(*source, Error::Timeout)),
+            other => panic!("expected a failed transfer, got {:?}", other),
+        }
+        assert_eq!(initiated_uploads(&mock), 2);
+    }
+
+    #[test]
+    fn failed_transfers_report_the_chunk() {
+        let hid_device = SecondChunkFails {
+            mock: MockHidDevice::new(),
+            packets: Cell::new(0),
+        };
+        let device = Device::new(&hid_device, FunctionHandler::new(|_| {}));
+
+        let result =
+            device.set_background_image_from_bytes(encoded_image(800, 480, ImageFormat::Jpeg));
+
+        assert!(matches!(
+            result,
+            Err(Error::TransferFailed {
+                stage: TransferStage::Chunk(1),
+                attempts: 3,
+                ..
+            })
+        ));
+        assert_eq!(initiated_uploads(&hid_device.mock), 3);
+    }
+
+    #[test]
+    fn disconnected_uploads_are_not_retried() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        mock.fail_next_write(HidError::HidApiError {
+            message: "hid_write: device disconnected".to_string(),
+        });
+
+        assert!(matches!(
+            device.set_display_zone_image_from_bytes(
+                DisplayZones::Button1,
+                encoded_image(112, 112, ImageFormat::Jpeg)
+            ),
+            Err(Error::Disconnected)
+        ));
+        assert!(mock.written_frames().is_empty());
+    }
+
+    #[test]
+    fn upload_progress_is_reported_per_packet() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        let mut reports = Vec::new();
+
+        device
+            .set_background_image_with_progress(
+                encoded_image(800, 480, ImageFormat::Jpeg),
+                |sent, total| reports.push((sent, total)),
+                &CancellationToken::new(),
+            )
+            .unwrap();
+
+        let total = mock.assert_background_image_uploaded().len();
+        assert_eq!(reports.len(), total.div_ceil(IMAGE_DATA_PACKET_LENGTH));
+        assert_eq!(reports[0], (IMAGE_DATA_PACKET_LENGTH, total));
+        assert_eq!(reports.last(), Some(&(total, total)));
+    }
+
+    #[test]
+    fn cancelled_uploads_stop_between_packets() {
+        let mock = MockHidDevice::new();
+        // Cancelling must not count as a failure to retry
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        let cancellation = CancellationToken::new();
+
+        let result = device.set_display_zone_image_with_progress(
+            DisplayZones::Touchscreen2,
+            encoded_image(800, 480, ImageFormat::Png),
+            |_, _| cancellation.cancel(),
+            &cancellation,
+        );
+
+        assert!(matches!(result, Err(Error::Cancelled)));
+        // The initiating frame and the first packet
+        assert_eq!(mock.written_frames().len(), 2);
+    }
+
+    #[test]
+    fn invalid_images_are_rejected_before_anything_is_sent() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        assert!(matches!(
+            device.set_display_zone_image_from_bytes(DisplayZones::Button1, [1, 2, 3]),
+            Err(Error::InvalidImage(_))
+        ));
+        assert!(mock.written_frames().is_empty());
+    }
+
+    #[test]
+    fn spanned_images_cover_every_zone_of_the_panel() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        device
+            .set_spanned_image(
+                &[Panel::Touchscreen],
+                encoded_image(800, 200, ImageFormat::Png),
+                &SpanLayout::default(),
+            )
+            .unwrap();
+
+        for display_zone in [
+            DisplayZones::Touchscreen1,
+            DisplayZones::Touchscreen2,
+            DisplayZones::Touchscreen3,
+            DisplayZones::Touchscreen4,
+        ] {
+            mock.assert_display_zone_image_uploaded(display_zone);
+        }
+        mock.assert_refreshed();
+    }
+
+    #[test]
+    fn zones_are_addressed_as_the_profile_describes() {
+        let mock = MockHidDevice::with_profile(&Akp153);
+        let mut device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        assert!(matches!(
+            device.clear_display_zone_image(DisplayZones::Button13),
+            Err(Error::UnsupportedDisplayZone(DisplayZones::Button13))
+        ));
+
+        device.set_profile(&Akp153);
+        device
+            .clear_display_zone_image(DisplayZones::Button13)
+            .unwrap();
+        device
+            .set_display_zone_image_from_bytes(
+                DisplayZones::Button1,
+                encoded_image(112, 112, ImageFormat::Png),
+            )
+            .unwrap();
+
+        // The AKP153 numbers its keys from 1, the AKP05 would have used 11 for button 1
+        mock.assert_frame_written(&[messages::CLEAR_DISPLAY_ZONE_IMAGE.as_slice(), &[13]].concat());
+        mock.assert_display_zone_cleared(DisplayZones::Button13);
+        assert_eq!(mock.written_frames()[1][13], 1);
+        let uploaded = mock.assert_display_zone_image_uploaded(DisplayZones::Button1);
+        let uploaded = image::load_from_memory_with_format(&uploaded, ImageFormat::Jpeg).unwrap();
+        assert_eq!((uploaded.width(), uploaded.height()), (85, 85));
+    }
+
+    #[test]
+    fn inputs_are_decoded_with_the_profile() {
+        let mock = MockHidDevice::new();
+        let handler = RecordingHandler::default();
+        let mut device = Device::new(&mock, &handler);
+        device.set_profile(&Akp153);
+
+        mock.queue_input(BUTTON_14_PRESSED);
+        device.read_input().unwrap();
+
+        assert_eq!(
+            *handler.actions.borrow(),
+            vec![InputActions::Button(ButtonActions::Button14Pressed)]
+        );
+    }
+}
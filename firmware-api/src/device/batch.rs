@@ -0,0 +1,283 @@
+//! Groups display changes so the whole panel switches at once.
+//!
+//! Every upload on the `Device` is written straight away, so changing many zones ripples
+//! across the panel one zone at a time. A `Batch` stages the changes instead, sends them
+//! back to back on `commit` and finishes with a single refresh (`STP`).
+//!
+//! ```no_run
+//! # use firmware_api::device::{Device, FunctionHandler, HidDeviceWrapper};
+//! # use firmware_api::display_zones::DisplayZones;
+//! # fn run(
+//! #     device: Device<HidDeviceWrapper, FunctionHandler>,
+//! #     play: Vec<u8>,
+//! #     stop: Vec<u8>,
+//! # ) -> firmware_api::error::Result<()> {
+//! let mut batch = device.batch();
+//! batch.set_display_zone_image_from_bytes(DisplayZones::Button1, play)?;
+//! batch.set_display_zone_image_from_bytes(DisplayZones::Button2, stop)?;
+//! batch.clear_display_zone_image(DisplayZones::Button3)?;
+//! batch.set_brightness(80)?;
+//! batch.commit()?;
+//! # Ok(())
+//! # }
+//! ```
+use super::{Device, HidDeviceOperations, InputHandler, validate_brightness};
+use crate::commands::{
+    initiate_set_background_command_factory, initiate_set_display_zone_image_command_factory,
+};
+use crate::display_zones::DisplayZones;
+use crate::error::Result;
+use crate::label::Label;
+use crate::transfer::CancellationToken;
+
+/// A change waiting for `Batch::commit`, images are already converted for their zone
+enum StagedOperation {
+    DisplayZoneImage { wire_id: u8, image: Vec<u8> },
+    BackgroundImage(Vec<u8>),
+    ClearDisplayZoneImage(DisplayZones),
+    ClearAllImages,
+    Brightness(u8),
+}
+
+/// Display changes staged on a `Device`, created with `Device::batch`.
+///
+/// Images are converted as they are staged, so a bad image is reported by the call that
+/// staged it and nothing is written to the device before `commit`. Dropping the batch
+/// discards everything staged.
+#[must_use = "nothing is sent to the device until the batch is committed"]
+pub struct Batch<'a, H: HidDeviceOperations, I: InputHandler> {
+    device: &'a Device<H, I>,
+    operations: Vec<StagedOperation>,
+}
+
+impl<'a, H: HidDeviceOperations, I: InputHandler> Batch<'a, H, I> {
+    pub(super) fn new(device: &'a Device<H, I>) -> Self {
+        Self {
+            device,
+            operations: Vec::new(),
+        }
+    }
+
+    /// Stages `Device::set_display_zone_image_from_bytes`
+    pub fn set_display_zone_image_from_bytes(
+        &mut self,
+        display_zone: DisplayZones,
+        image: impl AsRef<[u8]>,
+    ) -> Result<&mut Self> {
+        let zone = self.device.zone_layout(display_zone)?;
+        let image = self.device.prepare_image(image.as_ref(), zone.resolution)?;
+
+        self.operations.push(StagedOperation::DisplayZoneImage {
+            wire_id: zone.wire_id,
+            image,
+        });
+        Ok(self)
+    }
+
+    /// Stages `Device::set_display_zone_label`
+    pub fn set_display_zone_label(
+        &mut self,
+        display_zone: DisplayZones,
+        label: &Label,
+    ) -> Result<&mut Self> {
+        let image = label.render_jpeg(self.device.zone_layout(display_zone)?.resolution)?;
+
+        self.set_display_zone_image_from_bytes(display_zone, image)
+    }
+
+    /// Stages `Device::set_background_image_from_bytes`
+    pub fn set_background_image_from_bytes(
+        &mut self,
+        image: impl AsRef<[u8]>,
+    ) -> Result<&mut Self> {
+        let image = self
+            .device
+            .prepare_image(image.as_ref(), self.device.profile.background_resolution())?;
+
+        self.operations
+            .push(StagedOperation::BackgroundImage(image));
+        Ok(self)
+    }
+
+    /// Stages `Device::clear_display_zone_image`
+    pub fn clear_display_zone_image(&mut self, display_zone: DisplayZones) -> Result<&mut Self> {
+        self.device.zone_layout(display_zone)?;
+
+        self.operations
+            .push(StagedOperation::ClearDisplayZoneImage(display_zone));
+        Ok(self)
+    }
+
+    /// Stages `Device::clear_all_images`
+    pub fn clear_all_images(&mut self) -> &mut Self {
+        self.operations.push(StagedOperation::ClearAllImages);
+        self
+    }
+
+    /// Stages `Device::set_brightness`
+    pub fn set_brightness(&mut self, brightness: u8) -> Result<&mut Self> {
+        self.operations
+            .push(StagedOperation::Brightness(validate_brightness(
+                brightness,
+            )?));
+        Ok(self)
+    }
+
+    /// How many changes are staged
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Sends every staged change in the order it was staged, followed by a single refresh.
+    ///
+    /// Stops at the first change that fails, neither the remaining changes nor the
+    /// refresh are sent in that case.
+    pub fn commit(self) -> Result<usize> {
+        let device = self.device;
+        let cancellation = CancellationToken::default();
+
+        for operation in self.operations {
+            match operation {
+                StagedOperation::DisplayZoneImage { wire_id, image } => {
+                    let init_command = initiate_set_display_zone_image_command_factory(
+                        image.len() as u32,
+                        wire_id,
+                    );
+                    device.write_image_to_device_command(
+                        init_command,
+                        &image,
+                        &mut |_, _| {},
+                        &cancellation,
+                    )?;
+                }
+                StagedOperation::BackgroundImage(image) => {
+                    let init_command = initiate_set_background_command_factory(image.len() as u32);
+                    device.write_image_to_device_command(
+                        init_command,
+                        &image,
+                        &mut |_, _| {},
+                        &cancellation,
+                    )?;
+                }
+                StagedOperation::ClearDisplayZoneImage(display_zone) => {
+                    device.clear_display_zone_image(display_zone)?;
+                }
+                StagedOperation::ClearAllImages => {
+                    device.clear_all_images()?;
+                }
+                StagedOperation::Brightness(brightness) => {
+                    device.set_brightness(brightness)?;
+                }
+            }
+        }
+
+        device.refresh()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::{self, DecodedCommand};
+    use crate::device::FunctionHandler;
+    use crate::error::Error;
+    use crate::mock::MockHidDevice;
+    use image::{DynamicImage, ImageFormat, RgbImage};
+    use std::io::Cursor;
+
+    fn encoded_image(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(width, height))
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    fn commands_sent(mock: &MockHidDevice) -> Vec<DecodedCommand> {
+        mock.written_frames()
+            .iter()
+            .map(|frame| decoder::decode(frame))
+            .filter(|command| !matches!(command, DecodedCommand::ImageDataPacket(_)))
+            .collect()
+    }
+
+    #[test]
+    fn nothing_is_sent_before_commit() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        let mut batch = device.batch();
+        batch
+            .set_display_zone_image_from_bytes(DisplayZones::Button1, encoded_image(50, 50))
+            .unwrap()
+            .set_brightness(30)
+            .unwrap();
+        assert_eq!(batch.len(), 2);
+        drop(batch);
+
+        assert!(mock.written_frames().is_empty());
+    }
+
+    #[test]
+    fn changes_are_sent_in_order_with_a_single_refresh() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        let mut batch = device.batch();
+        batch.clear_all_images();
+        batch
+            .set_display_zone_image_from_bytes(DisplayZones::Button1, encoded_image(50, 50))
+            .unwrap();
+        batch
+            .clear_display_zone_image(DisplayZones::Touchscreen2)
+            .unwrap();
+        batch.set_brightness(30).unwrap();
+        batch.commit().unwrap();
+
+        let commands = commands_sent(&mock);
+        assert_eq!(commands.len(), 5);
+        assert_eq!(commands[0], DecodedCommand::ClearAllImages);
+        assert!(matches!(
+            commands[1],
+            DecodedCommand::InitiateSetDisplayZoneImage {
+                display_zone: DisplayZones::Button1,
+                ..
+            }
+        ));
+        assert_eq!(
+            commands[2..],
+            [
+                DecodedCommand::ClearDisplayZoneImage(DisplayZones::Touchscreen2),
+                DecodedCommand::SetBrightness(30),
+                DecodedCommand::Refresh,
+            ]
+        );
+        mock.assert_display_zone_image_uploaded(DisplayZones::Button1);
+    }
+
+    #[test]
+    fn invalid_changes_are_rejected_when_staged() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+
+        let mut batch = device.batch();
+        assert!(matches!(
+            batch.set_display_zone_image_from_bytes(DisplayZones::Button2, [1, 2, 3]),
+            Err(Error::InvalidImage(_))
+        ));
+        assert!(matches!(
+            batch.clear_display_zone_image(DisplayZones::Button12),
+            Err(Error::UnsupportedDisplayZone(DisplayZones::Button12))
+        ));
+        assert!(matches!(
+            batch.set_brightness(101),
+            Err(Error::InvalidBrightness(101))
+        ));
+
+        assert!(batch.is_empty());
+    }
+}
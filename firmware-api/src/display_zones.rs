@@ -1,5 +1,15 @@
+use crate::image_processing::Resolution;
 use std::io::{Error, ErrorKind};
 
+/// Native resolution of each of the buttons in the main button panel
+pub const BUTTON_RESOLUTION: Resolution = Resolution::new(112, 112);
+
+/// Native resolution of each of the zones in the touchscreen panel
+pub const TOUCHSCREEN_RESOLUTION: Resolution = Resolution::new(176, 112);
+
+/// Native resolution of the background (boot logo) image, which spans the whole display
+pub const BACKGROUND_RESOLUTION: Resolution = Resolution::new(800, 480);
+
 /// Used when trying to clear or set an image that was set on one of the
 /// smaller display zones, this does **not** include the background image.
 ///
@@ -11,7 +21,10 @@ use std::io::{Error, ErrorKind};
 ///  ### Touchscreen Panel (4×1)
 ///  | touchscreen1 | touchscreen2 | touchscreen3 | touchscreen4 |
 ///  |--------------|--------------|--------------|--------------|
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// This is the layout of the AKP05, `Button11` to `Button15` only exist on models with a
+/// third row of keys. The `DeviceProfile` of each model lists the zones it has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DisplayZones {
     Button1,
     Button2,
@@ -23,12 +36,33 @@ pub enum DisplayZones {
     Button8,
     Button9,
     Button10,
+    Button11,
+    Button12,
+    Button13,
+    Button14,
+    Button15,
     Touchscreen1,
     Touchscreen2,
     Touchscreen3,
     Touchscreen4,
 }
 
+impl DisplayZones {
+    /// The resolution the AKP05 expects images for this zone to be in,
+    /// see `DeviceProfile::zone_layout` for other models
+    pub fn resolution(&self) -> Resolution {
+        match self {
+            DisplayZones::Touchscreen1
+            | DisplayZones::Touchscreen2
+            | DisplayZones::Touchscreen3
+            | DisplayZones::Touchscreen4 => TOUCHSCREEN_RESOLUTION,
+            _ => BUTTON_RESOLUTION,
+        }
+    }
+}
+
+/// The id the AKP05 uses for each zone, this is also how zones are stored and exchanged
+/// between processes. Other models map zones to their own ids through their `DeviceProfile`.
 impl From<DisplayZones> for u8 {
     fn from(display: DisplayZones) -> Self {
         match display {
@@ -42,6 +76,11 @@ impl From<DisplayZones> for u8 {
             DisplayZones::Button8 => 8,
             DisplayZones::Button9 => 9,
             DisplayZones::Button10 => 10,
+            DisplayZones::Button11 => 16,
+            DisplayZones::Button12 => 17,
+            DisplayZones::Button13 => 18,
+            DisplayZones::Button14 => 19,
+            DisplayZones::Button15 => 20,
             DisplayZones::Touchscreen1 => 1,
             DisplayZones::Touchscreen2 => 2,
             DisplayZones::Touchscreen3 => 3,
@@ -60,11 +99,18 @@ impl TryFrom<u8> for DisplayZones {
             13 => DisplayZones::Button3,
             14 => DisplayZones::Button4,
             15 => DisplayZones::Button5,
-            16 => DisplayZones::Button6,
-            17 => DisplayZones::Button7,
-            18 => DisplayZones::Button8,
-            19 => DisplayZones::Button9,
-            20 => DisplayZones::Button10,
+
+            6 => DisplayZones::Button6,
+            7 => DisplayZones::Button7,
+            8 => DisplayZones::Button8,
+            9 => DisplayZones::Button9,
+            10 => DisplayZones::Button10,
+
+            16 => DisplayZones::Button11,
+            17 => DisplayZones::Button12,
+            18 => DisplayZones::Button13,
+            19 => DisplayZones::Button14,
+            20 => DisplayZones::Button15,
 
             1 => DisplayZones::Touchscreen1,
             2 => DisplayZones::Touchscreen2,
@@ -80,3 +126,42 @@ impl TryFrom<u8> for DisplayZones {
         Ok(display_zone)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wire_ids_round_trip() {
+        let zones = [
+            DisplayZones::Button1,
+            DisplayZones::Button2,
+            DisplayZones::Button3,
+            DisplayZones::Button4,
+            DisplayZones::Button5,
+            DisplayZones::Button6,
+            DisplayZones::Button7,
+            DisplayZones::Button8,
+            DisplayZones::Button9,
+            DisplayZones::Button10,
+            DisplayZones::Button11,
+            DisplayZones::Button12,
+            DisplayZones::Button13,
+            DisplayZones::Button14,
+            DisplayZones::Button15,
+            DisplayZones::Touchscreen1,
+            DisplayZones::Touchscreen2,
+            DisplayZones::Touchscreen3,
+            DisplayZones::Touchscreen4,
+        ];
+
+        for zone in zones {
+            assert_eq!(DisplayZones::try_from(u8::from(zone)).unwrap(), zone);
+        }
+    }
+
+    #[test]
+    fn unknown_wire_id_is_an_error() {
+        assert!(DisplayZones::try_from(21).is_err());
+    }
+}
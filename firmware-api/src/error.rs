@@ -0,0 +1,152 @@
+//! The error returned by every `Device` operation.
+//!
+//! hidapi reports most failures as a message, those that callers need to react to
+//! (like the launchpad being unplugged) are turned into their own variant here so
+//! nobody has to compare error messages.
+use crate::display_zones::DisplayZones;
+use crate::transfer::TransferStage;
+use hidapi::HidError;
+use image::ImageError;
+use std::fmt;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The launchpad is no longer available (e.g. it was unplugged), it needs to be opened again
+    Disconnected,
+    /// The device accepted only part of a frame
+    WriteShort { written: usize, expected: usize },
+    /// Brightness is a percentage, anything above 100 is rejected
+    InvalidBrightness(u8),
+    /// The model of the device has no such display zone
+    UnsupportedDisplayZone(DisplayZones),
+    /// The image could not be decoded, converted or rendered
+    InvalidImage(ImageError),
+    /// The device did not respond in time
+    Timeout,
+    /// An image transfer still failed after `attempts` tries, `source` is
+    /// what went wrong in the last one
+    TransferFailed {
+        stage: TransferStage,
+        attempts: u32,
+        source: Box<Error>,
+    },
+    /// The upload was stopped through its `CancellationToken`
+    Cancelled,
+    /// Any other failure reported by hidapi
+    Hid(HidError),
+    /// Reading the image (or other input) from its source failed
+    Io(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Disconnected => write!(f, "device disconnected"),
+            Error::WriteShort { written, expected } => write!(
+                f,
+                "device accepted {} of {} bytes written",
+                written, expected
+            ),
+            Error::InvalidBrightness(brightness) => {
+                write!(f, "brightness {} is not a percentage (0 - 100)", brightness)
+            }
+            Error::UnsupportedDisplayZone(display_zone) => {
+                write!(f, "the device has no display zone {:?}", display_zone)
+            }
+            Error::InvalidImage(error) => write!(f, "invalid image: {}", error),
+            Error::Timeout => write!(f, "timed out waiting for the device"),
+            Error::TransferFailed {
+                stage,
+                attempts,
+                source,
+            } => write!(
+                f,
+                "image transfer failed at {} after {} attempts: {}",
+                stage, attempts, source
+            ),
+            Error::Cancelled => write!(f, "the upload was cancelled"),
+            Error::Hid(error) => write!(f, "{}", error),
+            Error::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::InvalidImage(error) => Some(error),
+            Error::Hid(error) => Some(error),
+            Error::Io(error) => Some(error),
+            Error::TransferFailed { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// `ENODEV`, returned by the kernel once a device has been removed
+const NO_SUCH_DEVICE: i32 = 19;
+
+impl From<HidError> for Error {
+    fn from(error: HidError) -> Self {
+        if is_disconnect(&error) {
+            return Error::Disconnected;
+        }
+        Error::Hid(error)
+    }
+}
+
+impl From<ImageError> for Error {
+    fn from(error: ImageError) -> Self {
+        Error::InvalidImage(error)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+/// hidapi has no dedicated error for a removed device, each backend words it differently
+fn is_disconnect(error: &HidError) -> bool {
+    match error {
+        HidError::HidApiError { message } => {
+            let message = message.to_lowercase();
+            message.contains("disconnected") || message.contains("no such device")
+        }
+        HidError::IoError { error } => {
+            error.kind() == std::io::ErrorKind::NotConnected
+                || error.raw_os_error() == Some(NO_SUCH_DEVICE)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disconnect_messages_become_disconnected() {
+        let linux = HidError::HidApiError {
+            message: "hid_read_timeout: device disconnected".to_string(),
+        };
+        let removed = HidError::IoError {
+            error: std::io::Error::from_raw_os_error(NO_SUCH_DEVICE),
+        };
+
+        assert!(matches!(Error::from(linux), Error::Disconnected));
+        assert!(matches!(Error::from(removed), Error::Disconnected));
+    }
+
+    #[test]
+    fn other_hid_errors_are_kept() {
+        let error = HidError::HidApiError {
+            message: "hid_write: Broken pipe".to_string(),
+        };
+
+        assert!(matches!(Error::from(error), Error::Hid(_)));
+    }
+}
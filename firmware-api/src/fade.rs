@@ -0,0 +1,218 @@
+//! Changes the brightness gradually instead of in one jump.
+//!
+//! A `BrightnessFade` works out the brightness for any point in time along an `Easing`
+//! curve and sends it whenever it changes. It is [tick-driven](crate#tick-driven-helpers),
+//! call `BrightnessFade::tick` until the fade `is_finished`.
+//!
+//! ```no_run
+//! # use firmware_api::device::{Device, FunctionHandler, HidDeviceWrapper};
+//! # use firmware_api::fade::{BrightnessFade, Easing};
+//! # use std::time::{Duration, Instant};
+//! # fn run(device: Device<HidDeviceWrapper, FunctionHandler>) {
+//! let from = device.brightness().unwrap_or(100);
+//! let mut fade =
+//!     BrightnessFade::new(from, 10, Duration::from_secs(2), Easing::EaseOut, Instant::now())
+//!         .unwrap();
+//!
+//! while !fade.is_finished() {
+//!     device.read_input().ok();
+//!     fade.tick(&device, Instant::now()).ok();
+//! }
+//! # }
+//! ```
+use crate::device::{Device, HidDeviceOperations, InputHandler, validate_brightness};
+use crate::error::Result;
+use std::time::{Duration, Instant};
+
+/// How the brightness moves from the start to the end of a fade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// At a constant rate
+    #[default]
+    Linear,
+    /// Slowly at first, speeding up towards the end
+    EaseIn,
+    /// Quickly at first, slowing down towards the end
+    EaseOut,
+    /// Slowly at both ends, quickest halfway through
+    EaseInOut,
+}
+
+impl Easing {
+    /// How far along the fade is (0 to 1) once `progress` (0 to 1) of its duration passed
+    pub fn apply(&self, progress: f64) -> f64 {
+        let t = progress.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut if t < 0.5 => 2.0 * t * t,
+            Easing::EaseInOut => 1.0 - (2.0 - 2.0 * t).powi(2) / 2.0,
+        }
+    }
+}
+
+/// A brightness change spread over a duration, see the module documentation
+#[derive(Debug, Clone)]
+pub struct BrightnessFade {
+    from: u8,
+    to: u8,
+    duration: Duration,
+    easing: Easing,
+    start: Instant,
+    last_sent: Option<u8>,
+    finished: bool,
+}
+
+impl BrightnessFade {
+    /// Fails with `Error::InvalidBrightness` if `from` or `to` is above 100
+    ///
+    /// * `from` - the brightness the device is at, e.g. `Device::brightness`
+    /// * `to` - the brightness the device ends up at once `duration` has passed
+    /// * `start` - when the fade starts
+    pub fn new(
+        from: u8,
+        to: u8,
+        duration: Duration,
+        easing: Easing,
+        start: Instant,
+    ) -> Result<Self> {
+        Ok(Self {
+            from: validate_brightness(from)?,
+            to: validate_brightness(to)?,
+            duration,
+            easing,
+            start,
+            last_sent: None,
+            finished: false,
+        })
+    }
+
+    /// The brightness the device should be at `now`
+    pub fn brightness_at(&self, now: Instant) -> u8 {
+        let elapsed = now.saturating_duration_since(self.start);
+        if elapsed >= self.duration {
+            return self.to;
+        }
+
+        let progress = self
+            .easing
+            .apply(elapsed.as_secs_f64() / self.duration.as_secs_f64());
+        let from = f64::from(self.from);
+        (from + (f64::from(self.to) - from) * progress).round() as u8
+    }
+
+    /// The brightness the fade ends at
+    pub fn target(&self) -> u8 {
+        self.to
+    }
+
+    /// Whether the final brightness was sent, after which `tick` does nothing
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Sends the brightness for `now` if it differs from the last one sent, returns
+    /// whether it was sent
+    pub fn tick<H: HidDeviceOperations, I: InputHandler>(
+        &mut self,
+        device: &Device<H, I>,
+        now: Instant,
+    ) -> Result<bool> {
+        if self.finished {
+            return Ok(false);
+        }
+
+        let brightness = self.brightness_at(now);
+        let sent = match self.last_sent == Some(brightness) {
+            true => false,
+            false => {
+                device.set_brightness(brightness)?;
+                self.last_sent = Some(brightness);
+                true
+            }
+        };
+
+        self.finished = now >= self.start + self.duration;
+        Ok(sent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::DecodedCommand;
+    use crate::device::FunctionHandler;
+    use crate::error::Error;
+    use crate::mock::MockHidDevice;
+
+    #[test]
+    fn easing_curves_start_and_end_at_the_same_place() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+
+        assert_eq!(Easing::EaseIn.apply(0.5), 0.25);
+        assert_eq!(Easing::EaseOut.apply(0.5), 0.75);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn brightness_follows_the_curve() {
+        let start = Instant::now();
+        let fade =
+            BrightnessFade::new(100, 0, Duration::from_secs(4), Easing::Linear, start).unwrap();
+
+        assert_eq!(fade.brightness_at(start), 100);
+        assert_eq!(fade.brightness_at(start + Duration::from_secs(1)), 75);
+        assert_eq!(fade.brightness_at(start + Duration::from_secs(10)), 0);
+    }
+
+    #[test]
+    fn only_changes_are_sent_until_the_fade_is_finished() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        let start = Instant::now();
+        let mut fade =
+            BrightnessFade::new(20, 60, Duration::from_secs(1), Easing::EaseInOut, start).unwrap();
+
+        assert!(fade.tick(&device, start).unwrap());
+        assert!(!fade.tick(&device, start).unwrap());
+        fade.tick(&device, start + Duration::from_millis(500))
+            .unwrap();
+        assert!(!fade.is_finished());
+        fade.tick(&device, start + Duration::from_secs(1)).unwrap();
+        assert!(fade.is_finished());
+        assert!(!fade.tick(&device, start + Duration::from_secs(2)).unwrap());
+
+        let sent: Vec<DecodedCommand> = mock
+            .written_frames()
+            .iter()
+            .map(|frame| crate::decoder::decode(frame))
+            .collect();
+        assert_eq!(
+            sent,
+            [
+                DecodedCommand::SetBrightness(20),
+                DecodedCommand::SetBrightness(40),
+                DecodedCommand::SetBrightness(60),
+            ]
+        );
+        assert_eq!(device.brightness(), Some(60));
+    }
+
+    #[test]
+    fn brightness_above_100_is_rejected() {
+        assert!(matches!(
+            BrightnessFade::new(50, 150, Duration::ZERO, Easing::Linear, Instant::now()),
+            Err(Error::InvalidBrightness(150))
+        ));
+    }
+}
@@ -0,0 +1,223 @@
+//! Converts arbitrary images into something the launchpad can display.
+//!
+//! The device only renders baseline JPEGs that exactly match the resolution of
+//! the display zone being written to. Anything else (PNG, WebP, BMP, GIF or an
+//! oversized JPEG) is decoded, fitted to the target resolution and re-encoded.
+//!
+//! Note for GIFs: only the first frame is used.
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, ImageReader, ImageResult, Rgba, RgbaImage, imageops};
+use std::io::Cursor;
+
+/// Quality used when re-encoding images, high enough that icons do not show artifacts
+const JPEG_QUALITY: u8 = 90;
+
+/// Colour used to fill the empty space when letterboxing
+const LETTERBOX_FILL: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Resolution {
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+}
+
+/// How an image should be fitted to a target with a different aspect ratio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScalingMode {
+    /// Scales the image to fit inside the target, padding the remaining space with black
+    #[default]
+    Letterbox,
+    /// Scales the image to cover the target, cropping whatever overflows (centered)
+    Crop,
+    /// Scales each axis independently, distorting the image if the aspect ratios differ
+    Stretch,
+}
+
+/// Produces a JPEG of exactly `resolution` from the encoded image in `source`.
+///
+/// If `source` is already a baseline JPEG of the correct size it is returned
+/// untouched to avoid losing quality through re-encoding.
+///
+/// * `source` - the raw bytes of an encoded image (any supported format)
+/// * `resolution` - the resolution of the display zone the image is intended for
+/// * `scaling_mode` - how to handle an aspect ratio that does not match the target
+pub fn prepare_image(
+    source: &[u8],
+    resolution: Resolution,
+    scaling_mode: ScalingMode,
+) -> ImageResult<Vec<u8>> {
+    if image::guess_format(source)? == ImageFormat::Jpeg && is_baseline_jpeg(source) {
+        let (width, height) =
+            ImageReader::with_format(Cursor::new(source), ImageFormat::Jpeg).into_dimensions()?;
+        if Resolution::new(width, height) == resolution {
+            return Ok(source.to_vec());
+        }
+    }
+
+    let image = image::load_from_memory(source)?;
+    encode_jpeg(&fit_image(&image, resolution, scaling_mode))
+}
+
+/// Whether the first frame header (SOFn marker) of the JPEG in `source` is baseline (SOF0)
+fn is_baseline_jpeg(source: &[u8]) -> bool {
+    // Skips the SOI marker, every segment up to the frame header has a length
+    let mut offset = 2;
+    while let [0xFF, marker, high, low, ..] = source[offset.min(source.len())..] {
+        match marker {
+            // Fill bytes in front of a marker
+            0xFF => offset += 1,
+            0xC0 => return true,
+            // Every other SOFn, DHT (0xC4), JPG (0xC8) and DAC (0xCC) are not frame headers
+            0xC1..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => return false,
+            _ => offset += 2 + usize::from(u16::from_be_bytes([high, low])),
+        }
+    }
+
+    false
+}
+
+/// Resizes `image` so that it is exactly `resolution` using the given `scaling_mode`
+pub fn fit_image(
+    image: &DynamicImage,
+    resolution: Resolution,
+    scaling_mode: ScalingMode,
+) -> DynamicImage {
+    let Resolution { width, height } = resolution;
+
+    match scaling_mode {
+        ScalingMode::Stretch => image.resize_exact(width, height, FilterType::CatmullRom),
+        ScalingMode::Crop => image.resize_to_fill(width, height, FilterType::CatmullRom),
+        ScalingMode::Letterbox => {
+            let scaled = image.resize(width, height, FilterType::CatmullRom);
+            let mut canvas = RgbaImage::from_pixel(width, height, LETTERBOX_FILL);
+
+            let x = (width - scaled.width()) / 2;
+            let y = (height - scaled.height()) / 2;
+            imageops::overlay(&mut canvas, &scaled.to_rgba8(), x.into(), y.into());
+
+            DynamicImage::ImageRgba8(canvas)
+        }
+    }
+}
+
+/// Encodes `image` as a JPEG the device can render (no alpha channel)
+pub fn encode_jpeg(image: &DynamicImage) -> ImageResult<Vec<u8>> {
+    let mut output = Vec::new();
+
+    // Transparent areas would otherwise end up as whatever colour the pixel data holds
+    let mut flattened = RgbaImage::from_pixel(image.width(), image.height(), LETTERBOX_FILL);
+    imageops::overlay(&mut flattened, &image.to_rgba8(), 0, 0);
+
+    JpegEncoder::new_with_quality(&mut output, JPEG_QUALITY)
+        .encode_image(&DynamicImage::ImageRgba8(flattened).to_rgb8())?;
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GenericImageView, Rgb, RgbImage};
+
+    fn encode(image: DynamicImage, format: ImageFormat) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .unwrap();
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> DynamicImage {
+        image::load_from_memory_with_format(bytes, ImageFormat::Jpeg).unwrap()
+    }
+
+    #[test]
+    fn correctly_sized_jpeg_is_passed_through() {
+        let source = encode(
+            DynamicImage::ImageRgb8(RgbImage::new(112, 112)),
+            ImageFormat::Jpeg,
+        );
+
+        let output =
+            prepare_image(&source, Resolution::new(112, 112), ScalingMode::default()).unwrap();
+
+        assert_eq!(output, source);
+    }
+
+    #[test]
+    fn only_baseline_jpegs_are_passed_through() {
+        let baseline = encode(
+            DynamicImage::ImageRgb8(RgbImage::new(112, 112)),
+            ImageFormat::Jpeg,
+        );
+        let sof0 = baseline
+            .windows(2)
+            .position(|marker| marker == [0xFF, 0xC0])
+            .unwrap();
+        let mut progressive = baseline.clone();
+        progressive[sof0 + 1] = 0xC2;
+
+        assert!(is_baseline_jpeg(&baseline));
+        assert!(!is_baseline_jpeg(&progressive));
+        assert!(!is_baseline_jpeg(&baseline[..sof0]));
+    }
+
+    #[test]
+    fn png_is_transcoded_to_jpeg_of_target_size() {
+        let source = encode(
+            DynamicImage::ImageRgba8(RgbaImage::new(300, 200)),
+            ImageFormat::Png,
+        );
+
+        let output = prepare_image(&source, Resolution::new(176, 112), ScalingMode::Crop).unwrap();
+
+        assert_eq!(image::guess_format(&output).unwrap(), ImageFormat::Jpeg);
+        assert_eq!(decode(&output).dimensions(), (176, 112));
+    }
+
+    #[test]
+    fn oversized_jpeg_is_resized() {
+        let source = encode(
+            DynamicImage::ImageRgb8(RgbImage::new(512, 512)),
+            ImageFormat::Jpeg,
+        );
+
+        let output =
+            prepare_image(&source, Resolution::new(112, 112), ScalingMode::Stretch).unwrap();
+
+        assert_eq!(decode(&output).dimensions(), (112, 112));
+    }
+
+    #[test]
+    fn letterbox_pads_with_black() {
+        let white = DynamicImage::ImageRgb8(RgbImage::from_pixel(100, 50, Rgb([255, 255, 255])));
+
+        let output = fit_image(&white, Resolution::new(100, 100), ScalingMode::Letterbox);
+
+        assert_eq!(output.dimensions(), (100, 100));
+        assert_eq!(output.get_pixel(50, 5), Rgba([0, 0, 0, 255]));
+        assert_eq!(output.get_pixel(50, 50), Rgba([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn crop_fills_the_whole_target() {
+        let white = DynamicImage::ImageRgb8(RgbImage::from_pixel(100, 50, Rgb([255, 255, 255])));
+
+        let output = fit_image(&white, Resolution::new(100, 100), ScalingMode::Crop);
+
+        assert_eq!(output.dimensions(), (100, 100));
+        assert_eq!(output.get_pixel(50, 5), Rgba([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn invalid_data_is_an_error() {
+        assert!(prepare_image(&[0x13, 0x37], Resolution::new(1, 1), ScalingMode::Crop).is_err());
+    }
+}
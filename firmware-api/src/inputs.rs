@@ -2,13 +2,18 @@ use crate::common::ByteArray;
 use crate::inputs::InputActions::Knob;
 use crate::inputs::InputActions::{Button, Touchscreen};
 use crate::inputs::buttons::ButtonActions;
+use crate::inputs::gestures::Gesture;
 use crate::inputs::input_buffer::BUFFER_SIZE_13;
 use crate::inputs::knobs::KnobActions;
 use crate::inputs::touchscreen::TouchscreenAction;
+use std::sync::Arc;
+use std::time::Instant;
 
 pub mod buttons;
+pub mod gestures;
 pub mod input_buffer;
 pub mod knobs;
+pub mod rotation;
 pub mod touchscreen;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -16,9 +21,55 @@ pub enum InputActions {
     Button(ButtonActions),
     Knob(KnobActions),
     Touchscreen(TouchscreenAction),
+    /// Recognised from the presses and releases by a `gestures::GestureRecognizer`,
+    /// never decoded from a report
+    Gesture(Gesture),
+    /// A report with the expected header but an unrecognised code or state
+    Raw(RawInput),
+    /// Anything that is not an input report at all
     Unknown,
 }
 
+/// Which launchpad an input came from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeviceIdentity {
+    /// Empty unless set with `Device::set_serial_number`
+    pub serial_number: Arc<str>,
+    /// See `DeviceProfile::name`
+    pub model: &'static str,
+}
+
+/// An input along with when and where it was read, see `InputHandler::handle_event`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputEvent {
+    pub action: InputActions,
+    /// When the report was read from the device, as soon as `Device::read_input` got it
+    pub read_at: Instant,
+    /// Counts the inputs of a device from 0, in the order they were read
+    pub sequence: u64,
+    pub device: DeviceIdentity,
+    /// The report `action` was decoded from
+    pub report: ByteArray<BUFFER_SIZE_13>,
+}
+
+impl InputEvent {
+    /// An action that was not read by a `Device` (e.g. handed to `InputHandler::handle`
+    /// directly): read now, as the first input of no device in particular and with an
+    /// empty report
+    pub fn unread(action: InputActions) -> Self {
+        Self {
+            action,
+            read_at: Instant::now(),
+            sequence: 0,
+            device: DeviceIdentity {
+                serial_number: Arc::from(""),
+                model: "",
+            },
+            report: [0; BUFFER_SIZE_13],
+        }
+    }
+}
+
 use ButtonActions::*;
 use KnobActions::*;
 use TouchscreenAction::*;
@@ -69,6 +120,17 @@ const INPUT_ACTION_MAPPINGS: &[(InputActions, u8)] = &[
     (Knob(Knob2Pressed), 36),
     (Knob(Knob3Pressed), 37),
     (Knob(Knob4Pressed), 38),
+    // Buttons only found on the 15 key models
+    (Button(Button11Pressed), 39),
+    (Button(Button12Pressed), 40),
+    (Button(Button13Pressed), 41),
+    (Button(Button14Pressed), 42),
+    (Button(Button15Pressed), 43),
+    (Button(Button11Released), 44),
+    (Button(Button12Released), 45),
+    (Button(Button13Released), 46),
+    (Button(Button14Released), 47),
+    (Button(Button15Released), 48),
 ];
 impl From<u8> for InputActions {
     fn from(value: u8) -> Self {
@@ -76,11 +138,16 @@ impl From<u8> for InputActions {
             .iter()
             .find(|(_, code)| *code == value)
             .map(|(action, _)| action.clone())
+            .or_else(|| Gesture::from_code(value).map(InputActions::Gesture))
             .unwrap_or(InputActions::Unknown)
     }
 }
 impl From<InputActions> for u8 {
     fn from(value: InputActions) -> Self {
+        if let InputActions::Gesture(gesture) = value {
+            return gesture.code();
+        }
+
         INPUT_ACTION_MAPPINGS
             .iter()
             .find(|(action, _)| *action == value)
@@ -89,66 +156,210 @@ impl From<InputActions> for u8 {
     }
 }
 
+/// Every report the AKP05 is known to send, only the code and state bytes are compared
+pub(crate) const KNOWN_REPORTS: &[(ByteArray<BUFFER_SIZE_13>, InputActions)] = &[
+    // Buttons Pressed
+    (
+        buttons::BUTTON_1_PRESSED,
+        Button(ButtonActions::Button1Pressed),
+    ),
+    (
+        buttons::BUTTON_2_PRESSED,
+        Button(ButtonActions::Button2Pressed),
+    ),
+    (
+        buttons::BUTTON_3_PRESSED,
+        Button(ButtonActions::Button3Pressed),
+    ),
+    (
+        buttons::BUTTON_4_PRESSED,
+        Button(ButtonActions::Button4Pressed),
+    ),
+    (
+        buttons::BUTTON_5_PRESSED,
+        Button(ButtonActions::Button5Pressed),
+    ),
+    (
+        buttons::BUTTON_6_PRESSED,
+        Button(ButtonActions::Button6Pressed),
+    ),
+    (
+        buttons::BUTTON_7_PRESSED,
+        Button(ButtonActions::Button7Pressed),
+    ),
+    (
+        buttons::BUTTON_8_PRESSED,
+        Button(ButtonActions::Button8Pressed),
+    ),
+    (
+        buttons::BUTTON_9_PRESSED,
+        Button(ButtonActions::Button9Pressed),
+    ),
+    (
+        buttons::BUTTON_10_PRESSED,
+        Button(ButtonActions::Button10Pressed),
+    ),
+    // Buttons Released
+    (
+        buttons::BUTTON_1_RELEASED,
+        Button(ButtonActions::Button1Released),
+    ),
+    (
+        buttons::BUTTON_2_RELEASED,
+        Button(ButtonActions::Button2Released),
+    ),
+    (
+        buttons::BUTTON_3_RELEASED,
+        Button(ButtonActions::Button3Released),
+    ),
+    (
+        buttons::BUTTON_4_RELEASED,
+        Button(ButtonActions::Button4Released),
+    ),
+    (
+        buttons::BUTTON_5_RELEASED,
+        Button(ButtonActions::Button5Released),
+    ),
+    (
+        buttons::BUTTON_6_RELEASED,
+        Button(ButtonActions::Button6Released),
+    ),
+    (
+        buttons::BUTTON_7_RELEASED,
+        Button(ButtonActions::Button7Released),
+    ),
+    (
+        buttons::BUTTON_8_RELEASED,
+        Button(ButtonActions::Button8Released),
+    ),
+    (
+        buttons::BUTTON_9_RELEASED,
+        Button(ButtonActions::Button9Released),
+    ),
+    (
+        buttons::BUTTON_10_RELEASED,
+        Button(ButtonActions::Button10Released),
+    ),
+    // Touchscreen pressed
+    (
+        touchscreen::ZONE_1_PRESSED,
+        Touchscreen(TouchscreenAction::Zone1Pressed),
+    ),
+    (
+        touchscreen::ZONE_2_PRESSED,
+        Touchscreen(TouchscreenAction::Zone2Pressed),
+    ),
+    (
+        touchscreen::ZONE_3_PRESSED,
+        Touchscreen(TouchscreenAction::Zone3Pressed),
+    ),
+    (
+        touchscreen::ZONE_4_PRESSED,
+        Touchscreen(TouchscreenAction::Zone4Pressed),
+    ),
+    // Touchscreen swiped
+    (
+        touchscreen::SWIPED_LEFT,
+        Touchscreen(TouchscreenAction::SwipedLeft),
+    ),
+    (
+        touchscreen::SWIPED_RIGHT,
+        Touchscreen(TouchscreenAction::SwipedRight),
+    ),
+    // Knob clockwise
+    (knobs::KNOB_1_CLOCKWISE, Knob(KnobActions::Knob1Clockwise)),
+    (knobs::KNOB_2_CLOCKWISE, Knob(KnobActions::Knob2Clockwise)),
+    (knobs::KNOB_3_CLOCKWISE, Knob(KnobActions::Knob3Clockwise)),
+    (knobs::KNOB_4_CLOCKWISE, Knob(KnobActions::Knob4Clockwise)),
+    // Knob counter-clockwise
+    (
+        knobs::KNOB_1_COUNTER_CLOCKWISE,
+        Knob(KnobActions::Knob1CounterClockwise),
+    ),
+    (
+        knobs::KNOB_2_COUNTER_CLOCKWISE,
+        Knob(KnobActions::Knob2CounterClockwise),
+    ),
+    (
+        knobs::KNOB_3_COUNTER_CLOCKWISE,
+        Knob(KnobActions::Knob3CounterClockwise),
+    ),
+    (
+        knobs::KNOB_4_COUNTER_CLOCKWISE,
+        Knob(KnobActions::Knob4CounterClockwise),
+    ),
+    // Knob pressed
+    (knobs::KNOB_1_PRESSED, Knob(KnobActions::Knob1Pressed)),
+    (knobs::KNOB_2_PRESSED, Knob(KnobActions::Knob2Pressed)),
+    (knobs::KNOB_3_PRESSED, Knob(KnobActions::Knob3Pressed)),
+    (knobs::KNOB_4_PRESSED, Knob(KnobActions::Knob4Pressed)),
+];
+
+/// The header the launchpad puts in front of every input report
+pub const REPORT_HEADER: &[u8] = b"ACK\0\0OK\0\0";
+const CODE_INDEX: usize = REPORT_HEADER.len();
+const STATE_INDEX: usize = CODE_INDEX + 1;
+
+/// The fields of an input report: `ACK\0\0OK\0\0` followed by a code identifying the
+/// input and a state (e.g. 1 for pressed, 0 for released)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InputReport {
+    pub code: u8,
+    pub state: u8,
+}
+
+impl InputReport {
+    /// `None` if `report` does not start with the `ACK`/`OK` header
+    pub fn parse(report: &[u8]) -> Option<Self> {
+        if !report.starts_with(REPORT_HEADER) {
+            return None;
+        }
+
+        Some(Self {
+            code: *report.get(CODE_INDEX)?,
+            state: *report.get(STATE_INDEX)?,
+        })
+    }
+}
+
+/// A well formed report that does not match any known input, e.g. from a newer
+/// firmware revision or an event that has not been mapped yet (like knob releases)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawInput {
+    pub code: u8,
+    pub state: u8,
+    /// The whole report as it was read
+    pub bytes: ByteArray<BUFFER_SIZE_13>,
+}
+
+/// Reports are decoded as sent by the AKP05, use `DeviceProfile::decode_input` for other models
 impl From<ByteArray<BUFFER_SIZE_13>> for InputActions {
     fn from(value: ByteArray<BUFFER_SIZE_13>) -> Self {
-        match value {
-            // Buttons Pressed
-            buttons::BUTTON_1_PRESSED => Button(ButtonActions::Button1Pressed),
-            buttons::BUTTON_2_PRESSED => Button(ButtonActions::Button2Pressed),
-            buttons::BUTTON_3_PRESSED => Button(ButtonActions::Button3Pressed),
-            buttons::BUTTON_4_PRESSED => Button(ButtonActions::Button4Pressed),
-            buttons::BUTTON_5_PRESSED => Button(ButtonActions::Button5Pressed),
-            buttons::BUTTON_6_PRESSED => Button(ButtonActions::Button6Pressed),
-            buttons::BUTTON_7_PRESSED => Button(ButtonActions::Button7Pressed),
-            buttons::BUTTON_8_PRESSED => Button(ButtonActions::Button8Pressed),
-            buttons::BUTTON_9_PRESSED => Button(ButtonActions::Button9Pressed),
-            buttons::BUTTON_10_PRESSED => Button(ButtonActions::Button10Pressed),
-
-            // Buttons Pressed
-            buttons::BUTTON_1_RELEASED => Button(ButtonActions::Button1Released),
-            buttons::BUTTON_2_RELEASED => Button(ButtonActions::Button2Released),
-            buttons::BUTTON_3_RELEASED => Button(ButtonActions::Button3Released),
-            buttons::BUTTON_4_RELEASED => Button(ButtonActions::Button4Released),
-            buttons::BUTTON_5_RELEASED => Button(ButtonActions::Button5Released),
-            buttons::BUTTON_6_RELEASED => Button(ButtonActions::Button6Released),
-            buttons::BUTTON_7_RELEASED => Button(ButtonActions::Button7Released),
-            buttons::BUTTON_8_RELEASED => Button(ButtonActions::Button8Released),
-            buttons::BUTTON_9_RELEASED => Button(ButtonActions::Button9Released),
-            buttons::BUTTON_10_RELEASED => Button(ButtonActions::Button10Released),
-
-            // Touchscreen pressed
-            touchscreen::ZONE_1_PRESSED => Touchscreen(TouchscreenAction::Zone1Pressed),
-            touchscreen::ZONE_2_PRESSED => Touchscreen(TouchscreenAction::Zone2Pressed),
-            touchscreen::ZONE_3_PRESSED => Touchscreen(TouchscreenAction::Zone3Pressed),
-            touchscreen::ZONE_4_PRESSED => Touchscreen(TouchscreenAction::Zone4Pressed),
-
-            // Touchscreen swiped
-            touchscreen::SWIPED_LEFT => Touchscreen(TouchscreenAction::SwipedLeft),
-            touchscreen::SWIPED_RIGHT => Touchscreen(TouchscreenAction::SwipedRight),
-
-            // Knob clockwise
-            knobs::KNOB_1_CLOCKWISE => Knob(KnobActions::Knob1Clockwise),
-            knobs::KNOB_2_CLOCKWISE => Knob(KnobActions::Knob2Clockwise),
-            knobs::KNOB_3_CLOCKWISE => Knob(KnobActions::Knob3Clockwise),
-            knobs::KNOB_4_CLOCKWISE => Knob(KnobActions::Knob4Clockwise),
-
-            // Knob counter-clockwise
-            knobs::KNOB_1_COUNTER_CLOCKWISE => Knob(KnobActions::Knob1CounterClockwise),
-            knobs::KNOB_2_COUNTER_CLOCKWISE => Knob(KnobActions::Knob2CounterClockwise),
-            knobs::KNOB_3_COUNTER_CLOCKWISE => Knob(KnobActions::Knob3CounterClockwise),
-            knobs::KNOB_4_COUNTER_CLOCKWISE => Knob(KnobActions::Knob4CounterClockwise),
-
-            // Knob pressed
-            knobs::KNOB_1_PRESSED => Knob(KnobActions::Knob1Pressed),
-            knobs::KNOB_2_PRESSED => Knob(KnobActions::Knob2Pressed),
-            knobs::KNOB_3_PRESSED => Knob(KnobActions::Knob3Pressed),
-            knobs::KNOB_4_PRESSED => Knob(KnobActions::Knob4Pressed),
-
-            _ => InputActions::Unknown,
-        }
+        decode_report(KNOWN_REPORTS, value)
     }
 }
 
+/// Looks `value` up in a table of known reports, anything with the right header that is
+/// not in the table is kept as a `RawInput`
+pub(crate) fn decode_report(
+    known_reports: &[(ByteArray<BUFFER_SIZE_13>, InputActions)],
+    value: ByteArray<BUFFER_SIZE_13>,
+) -> InputActions {
+    let Some(report) = InputReport::parse(&value) else {
+        return InputActions::Unknown;
+    };
+
+    known_reports
+        .iter()
+        .find(|(known, _)| InputReport::parse(known) == Some(report))
+        .map(|(_, action)| action.clone())
+        .unwrap_or(InputActions::Raw(RawInput {
+            code: report.code,
+            state: report.state,
+            bytes: value,
+        }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -355,4 +566,54 @@ mod tests {
             InputActions::Unknown
         ));
     }
+
+    #[test]
+    fn trailing_bytes_are_ignored() {
+        let mut report = buttons::BUTTON_7_PRESSED;
+        report[11] = 0x42;
+        report[12] = 0x13;
+
+        assert_eq!(
+            InputActions::from(report),
+            Button(ButtonActions::Button7Pressed)
+        );
+    }
+
+    #[test]
+    fn unrecognised_reports_keep_their_fields() {
+        // Knob 1 released, which has no action of its own
+        let report = *b"ACK\0\0OK\0\0\x37\x00\0\x09";
+
+        assert_eq!(
+            InputActions::from(report),
+            InputActions::Raw(RawInput {
+                code: 0x37,
+                state: 0x00,
+                bytes: report
+            })
+        );
+    }
+
+    #[test]
+    fn parses_report_fields() {
+        assert_eq!(
+            InputReport::parse(&knobs::KNOB_2_PRESSED),
+            Some(InputReport {
+                code: 0x35,
+                state: 0x01
+            })
+        );
+        assert_eq!(InputReport::parse(b"NAK\0\0OK\0\0\x01\x01"), None);
+        assert_eq!(InputReport::parse(b"ACK\0\0OK\0\0"), None);
+    }
+
+    #[test]
+    fn gestures_are_stored_after_the_other_inputs() {
+        let long_press = InputActions::Gesture(Gesture::LongPress(2));
+        let code = u8::from(long_press.clone());
+
+        assert!(INPUT_ACTION_MAPPINGS.iter().all(|(_, known)| *known < code));
+        assert_eq!(InputActions::from(code), long_press);
+        assert_eq!(InputActions::from(u8::MAX), InputActions::Unknown);
+    }
 }
@@ -5,6 +5,8 @@ use crate::inputs::input_buffer::BUFFER_SIZE_13;
 ///  | button1 | button2 | button3 | button4 | button5 |
 ///  |---------|---------|---------|---------|---------|
 ///  | button6 | button7 | button8 | button9 | button10|
+///
+/// Buttons 11 to 15 only exist on models with a third row of keys (e.g. the AKP153)
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
 pub enum ButtonActions {
     Button1Pressed,
@@ -17,6 +19,11 @@ pub enum ButtonActions {
     Button8Pressed,
     Button9Pressed,
     Button10Pressed,
+    Button11Pressed,
+    Button12Pressed,
+    Button13Pressed,
+    Button14Pressed,
+    Button15Pressed,
 
     Button1Released,
     Button2Released,
@@ -28,6 +35,87 @@ pub enum ButtonActions {
     Button8Released,
     Button9Released,
     Button10Released,
+    Button11Released,
+    Button12Released,
+    Button13Released,
+    Button14Released,
+    Button15Released,
+}
+
+impl ButtonActions {
+    /// The number of the button (1 - 15, see the layout above) and whether it was pressed
+    /// (`true`) or released
+    pub fn button(&self) -> (u8, bool) {
+        match self {
+            ButtonActions::Button1Pressed => (1, true),
+            ButtonActions::Button2Pressed => (2, true),
+            ButtonActions::Button3Pressed => (3, true),
+            ButtonActions::Button4Pressed => (4, true),
+            ButtonActions::Button5Pressed => (5, true),
+            ButtonActions::Button6Pressed => (6, true),
+            ButtonActions::Button7Pressed => (7, true),
+            ButtonActions::Button8Pressed => (8, true),
+            ButtonActions::Button9Pressed => (9, true),
+            ButtonActions::Button10Pressed => (10, true),
+            ButtonActions::Button11Pressed => (11, true),
+            ButtonActions::Button12Pressed => (12, true),
+            ButtonActions::Button13Pressed => (13, true),
+            ButtonActions::Button14Pressed => (14, true),
+            ButtonActions::Button15Pressed => (15, true),
+            ButtonActions::Button1Released => (1, false),
+            ButtonActions::Button2Released => (2, false),
+            ButtonActions::Button3Released => (3, false),
+            ButtonActions::Button4Released => (4, false),
+            ButtonActions::Button5Released => (5, false),
+            ButtonActions::Button6Released => (6, false),
+            ButtonActions::Button7Released => (7, false),
+            ButtonActions::Button8Released => (8, false),
+            ButtonActions::Button9Released => (9, false),
+            ButtonActions::Button10Released => (10, false),
+            ButtonActions::Button11Released => (11, false),
+            ButtonActions::Button12Released => (12, false),
+            ButtonActions::Button13Released => (13, false),
+            ButtonActions::Button14Released => (14, false),
+            ButtonActions::Button15Released => (15, false),
+        }
+    }
+
+    /// The inverse of `button`, `None` for numbers outside 1 - 15
+    pub const fn from_button(button: u8, pressed: bool) -> Option<Self> {
+        Some(match (button, pressed) {
+            (1, true) => ButtonActions::Button1Pressed,
+            (2, true) => ButtonActions::Button2Pressed,
+            (3, true) => ButtonActions::Button3Pressed,
+            (4, true) => ButtonActions::Button4Pressed,
+            (5, true) => ButtonActions::Button5Pressed,
+            (6, true) => ButtonActions::Button6Pressed,
+            (7, true) => ButtonActions::Button7Pressed,
+            (8, true) => ButtonActions::Button8Pressed,
+            (9, true) => ButtonActions::Button9Pressed,
+            (10, true) => ButtonActions::Button10Pressed,
+            (11, true) => ButtonActions::Button11Pressed,
+            (12, true) => ButtonActions::Button12Pressed,
+            (13, true) => ButtonActions::Button13Pressed,
+            (14, true) => ButtonActions::Button14Pressed,
+            (15, true) => ButtonActions::Button15Pressed,
+            (1, false) => ButtonActions::Button1Released,
+            (2, false) => ButtonActions::Button2Released,
+            (3, false) => ButtonActions::Button3Released,
+            (4, false) => ButtonActions::Button4Released,
+            (5, false) => ButtonActions::Button5Released,
+            (6, false) => ButtonActions::Button6Released,
+            (7, false) => ButtonActions::Button7Released,
+            (8, false) => ButtonActions::Button8Released,
+            (9, false) => ButtonActions::Button9Released,
+            (10, false) => ButtonActions::Button10Released,
+            (11, false) => ButtonActions::Button11Released,
+            (12, false) => ButtonActions::Button12Released,
+            (13, false) => ButtonActions::Button13Released,
+            (14, false) => ButtonActions::Button14Released,
+            (15, false) => ButtonActions::Button15Released,
+            _ => return None,
+        })
+    }
 }
 
 pub const BUTTON_1_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x01\x01\0\0";
@@ -41,6 +129,11 @@ pub const BUTTON_8_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x08\x01
 
 pub const BUTTON_9_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x09\x01\0\0";
 pub const BUTTON_10_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x0A\x01\0\0";
+pub const BUTTON_11_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x0B\x01\0\0";
+pub const BUTTON_12_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x0C\x01\0\0";
+pub const BUTTON_13_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x0D\x01\0\0";
+pub const BUTTON_14_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x0E\x01\0\0";
+pub const BUTTON_15_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x0F\x01\0\0";
 
 pub const BUTTON_1_RELEASED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x01\x00\0\0";
 pub const BUTTON_2_RELEASED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x02\x00\0\0";
@@ -52,3 +145,8 @@ pub const BUTTON_7_RELEASED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x07\x0
 pub const BUTTON_8_RELEASED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x08\x00\0\0";
 pub const BUTTON_9_RELEASED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x09\x00\0\0";
 pub const BUTTON_10_RELEASED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x0A\x00\0\0";
+pub const BUTTON_11_RELEASED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x0B\x00\0\0";
+pub const BUTTON_12_RELEASED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x0C\x00\0\0";
+pub const BUTTON_13_RELEASED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x0D\x00\0\0";
+pub const BUTTON_14_RELEASED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x0E\x00\0\0";
+pub const BUTTON_15_RELEASED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x0F\x00\0\0";
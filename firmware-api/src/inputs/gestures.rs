@@ -0,0 +1,414 @@
+//! Turns the presses and releases of the buttons into taps, long presses, double taps,
+//! repeats while held and two button chords.
+//!
+//! A `GestureRecognizer` is `push`ed the button actions as they are read. Some gestures
+//! can only be told apart after a while (a tap is only a tap once no second tap followed),
+//! so it is [tick-driven](crate#tick-driven-helpers): `GestureRecognizer::tick` hands out
+//! every gesture recognised by then.
+//!
+//! The presses and releases are still reported as before, gestures come on top of them:
+//! * `Tap` - pressed and released again before `long_press`, with no second tap within
+//!   `double_tap` of the release
+//! * `DoubleTap` - pressed again within `double_tap` of a tap, sent on the second press
+//! * `LongPress` - held for `long_press`, sent once
+//! * `HoldRepeat` - held for `long_press`, sent then and every `repeat_interval` after
+//!   until the button is released
+//! * `Chord` - a second button pressed within `chord` of the first one, neither of them
+//!   makes any other gesture until it is released. Only buttons 1 to 10 make chords.
+use crate::inputs::buttons::ButtonActions;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// How long a button has to be held for a long press
+pub const DEFAULT_LONG_PRESS: Duration = Duration::from_millis(500);
+
+/// How soon after a tap the second one has to follow for a double tap
+pub const DEFAULT_DOUBLE_TAP: Duration = Duration::from_millis(250);
+
+/// How often a held button repeats
+pub const DEFAULT_REPEAT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How close together two buttons have to be pressed for a chord
+pub const DEFAULT_CHORD: Duration = Duration::from_millis(50);
+
+/// Buttons above this number do not make chords, which keeps their number manageable
+pub const CHORD_BUTTONS: u8 = 10;
+
+const BUTTONS: u8 = 15;
+
+/// The code of `Tap(1)` when stored, see `Gesture::code`. Follows the codes of the
+/// other `InputActions`.
+const FIRST_GESTURE_CODE: u8 = 49;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gesture {
+    /// A short press of a button, numbered like `ButtonActions::button`
+    Tap(u8),
+    LongPress(u8),
+    DoubleTap(u8),
+    HoldRepeat(u8),
+    /// Two buttons pressed together, the lower number first
+    Chord(u8, u8),
+}
+
+impl Gesture {
+    /// Identifies the gesture when stored, like `u8::from(InputActions)` does for the other
+    /// inputs. 0 for buttons that do not exist (or do not make chords).
+    pub fn code(&self) -> u8 {
+        let single = |kind: u8, button: u8| match button {
+            1..=BUTTONS => FIRST_GESTURE_CODE + kind * BUTTONS + button - 1,
+            _ => 0,
+        };
+
+        match *self {
+            Gesture::Tap(button) => single(0, button),
+            Gesture::LongPress(button) => single(1, button),
+            Gesture::DoubleTap(button) => single(2, button),
+            Gesture::HoldRepeat(button) => single(3, button),
+            Gesture::Chord(first, second) => chords()
+                .position(|chord| chord == (first, second))
+                .map_or(0, |index| FIRST_GESTURE_CODE + 4 * BUTTONS + index as u8),
+        }
+    }
+
+    /// The gesture stored as `code`, `None` if it is not a gesture
+    pub fn from_code(code: u8) -> Option<Self> {
+        let index = code.checked_sub(FIRST_GESTURE_CODE)?;
+        let button = index % BUTTONS + 1;
+
+        match index / BUTTONS {
+            0 => Some(Gesture::Tap(button)),
+            1 => Some(Gesture::LongPress(button)),
+            2 => Some(Gesture::DoubleTap(button)),
+            3 => Some(Gesture::HoldRepeat(button)),
+            _ => chords()
+                .nth(usize::from(index - 4 * BUTTONS))
+                .map(|(first, second)| Gesture::Chord(first, second)),
+        }
+    }
+}
+
+/// Every pair of buttons that makes a chord, in the order of their codes
+fn chords() -> impl Iterator<Item = (u8, u8)> {
+    (1..=CHORD_BUTTONS)
+        .flat_map(|first| (first + 1..=CHORD_BUTTONS).map(move |second| (first, second)))
+}
+
+/// When a gesture is recognised, see the module documentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GestureTimings {
+    pub long_press: Duration,
+    pub double_tap: Duration,
+    pub repeat_interval: Duration,
+    pub chord: Duration,
+}
+
+impl Default for GestureTimings {
+    fn default() -> Self {
+        Self {
+            long_press: DEFAULT_LONG_PRESS,
+            double_tap: DEFAULT_DOUBLE_TAP,
+            repeat_interval: DEFAULT_REPEAT_INTERVAL,
+            chord: DEFAULT_CHORD,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ButtonState {
+    /// Held down, `next_repeat` is set once it became a long press
+    Down {
+        pressed: Instant,
+        next_repeat: Option<Instant>,
+    },
+    /// Released after a short press, waiting for a second tap
+    Tapped { released: Instant },
+    /// Already part of a chord or double tap, nothing else happens until it is released
+    Used,
+}
+
+/// Recognises gestures, see the module documentation
+#[derive(Debug, Clone, Default)]
+pub struct GestureRecognizer {
+    timings: GestureTimings,
+    buttons: BTreeMap<u8, ButtonState>,
+    ready: Vec<Gesture>,
+}
+
+impl GestureRecognizer {
+    pub fn new(timings: GestureTimings) -> Self {
+        Self {
+            timings,
+            buttons: BTreeMap::new(),
+            ready: Vec::new(),
+        }
+    }
+
+    pub fn timings(&self) -> &GestureTimings {
+        &self.timings
+    }
+
+    /// Changes when gestures are recognised, buttons that are already held are timed by the
+    /// new timings from when they were pressed
+    pub fn set_timings(&mut self, timings: GestureTimings) {
+        self.timings = timings;
+    }
+
+    /// Records a press or release read at `now`
+    pub fn push(&mut self, action: &ButtonActions, now: Instant) {
+        let (button, pressed) = action.button();
+        match pressed {
+            true => self.press(button, now),
+            false => self.release(button, now),
+        }
+    }
+
+    fn press(&mut self, button: u8, now: Instant) {
+        // A tap that was not handed out in time is still a tap
+        if let Some(ButtonState::Tapped { released }) = self.buttons.get(&button)
+            && now < *released + self.timings.double_tap
+        {
+            self.buttons.insert(button, ButtonState::Used);
+            self.ready.push(Gesture::DoubleTap(button));
+            return;
+        }
+        self.expire_tap(button);
+
+        let chord = self.buttons.iter().find_map(|(other, state)| match state {
+            ButtonState::Down {
+                pressed,
+                next_repeat: None,
+            } if button <= CHORD_BUTTONS
+                && *other <= CHORD_BUTTONS
+                && now < *pressed + self.timings.chord =>
+            {
+                Some(*other)
+            }
+            _ => None,
+        });
+        if let Some(other) = chord {
+            self.buttons.insert(other, ButtonState::Used);
+            self.buttons.insert(button, ButtonState::Used);
+            self.ready
+                .push(Gesture::Chord(other.min(button), other.max(button)));
+            return;
+        }
+
+        self.buttons.insert(
+            button,
+            ButtonState::Down {
+                pressed: now,
+                next_repeat: None,
+            },
+        );
+    }
+
+    fn release(&mut self, button: u8, now: Instant) {
+        match self.buttons.remove(&button) {
+            Some(ButtonState::Down {
+                next_repeat: None, ..
+            }) => {
+                self.buttons
+                    .insert(button, ButtonState::Tapped { released: now });
+            }
+            // A release without a press, e.g. the button was held while connecting
+            Some(tapped @ ButtonState::Tapped { .. }) => {
+                self.buttons.insert(button, tapped);
+            }
+            _ => {}
+        }
+    }
+
+    /// Hands out a pending tap of `button`, since it can no longer become a double tap
+    fn expire_tap(&mut self, button: u8) {
+        if let Some(ButtonState::Tapped { .. }) = self.buttons.get(&button) {
+            self.buttons.remove(&button);
+            self.ready.push(Gesture::Tap(button));
+        }
+    }
+
+    /// When `tick` next has a gesture to hand out (or `None` while no button is pressed).
+    /// Chords and double taps are recognised on a press, so they are ready straight away.
+    pub fn next_deadline(&self, now: Instant) -> Option<Instant> {
+        if !self.ready.is_empty() {
+            return Some(now);
+        }
+
+        self.buttons
+            .values()
+            .filter_map(|state| match state {
+                ButtonState::Down {
+                    pressed,
+                    next_repeat,
+                } => Some(next_repeat.unwrap_or(*pressed + self.timings.long_press)),
+                ButtonState::Tapped { released } => Some(*released + self.timings.double_tap),
+                ButtonState::Used => None,
+            })
+            .min()
+    }
+
+    /// Every gesture recognised by `now`, in the order they happened (buttons that are due
+    /// at the same tick are in the order of their numbers)
+    pub fn tick(&mut self, now: Instant) -> Vec<Gesture> {
+        let timings = self.timings;
+        let mut expired = Vec::new();
+
+        for (button, state) in self.buttons.iter_mut() {
+            match state {
+                ButtonState::Down {
+                    pressed,
+                    next_repeat: next_repeat @ None,
+                } if now >= *pressed + timings.long_press => {
+                    self.ready.push(Gesture::LongPress(*button));
+                    self.ready.push(Gesture::HoldRepeat(*button));
+                    *next_repeat = Some(*pressed + timings.long_press + timings.repeat_interval);
+                }
+                ButtonState::Down {
+                    next_repeat: Some(next_repeat),
+                    ..
+                } if now >= *next_repeat => {
+                    // A late tick sends a single repeat rather than catching up
+                    self.ready.push(Gesture::HoldRepeat(*button));
+                    while *next_repeat <= now {
+                        *next_repeat += timings.repeat_interval.max(Duration::from_millis(1));
+                    }
+                }
+                ButtonState::Tapped { released } if now >= *released + timings.double_tap => {
+                    expired.push(*button);
+                }
+                _ => {}
+            }
+        }
+
+        for button in expired {
+            self.expire_tap(button);
+        }
+
+        std::mem::take(&mut self.ready)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::buttons::ButtonActions::*;
+
+    fn millis(start: Instant, millis: u64) -> Instant {
+        start + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn every_gesture_has_its_own_code() {
+        let gestures: Vec<Gesture> = (0..=u8::MAX).filter_map(Gesture::from_code).collect();
+
+        assert_eq!(gestures.len(), 4 * 15 + 45);
+        for gesture in gestures {
+            assert_eq!(Gesture::from_code(gesture.code()), Some(gesture));
+        }
+        assert_eq!(Gesture::Tap(1).code(), 49);
+        assert_eq!(Gesture::Chord(9, 10).code(), 153);
+        assert_eq!(Gesture::Chord(10, 11).code(), 0);
+        assert_eq!(Gesture::from_code(48), None);
+    }
+
+    #[test]
+    fn taps_wait_for_a_second_tap() {
+        let start = Instant::now();
+        let mut recognizer = GestureRecognizer::default();
+
+        recognizer.push(&Button3Pressed, start);
+        recognizer.push(&Button3Released, millis(start, 80));
+        assert!(recognizer.tick(millis(start, 200)).is_empty());
+        assert_eq!(recognizer.next_deadline(start), Some(millis(start, 330)));
+        assert_eq!(recognizer.tick(millis(start, 330)), [Gesture::Tap(3)]);
+
+        recognizer.push(&Button3Pressed, millis(start, 1000));
+        recognizer.push(&Button3Released, millis(start, 1050));
+        recognizer.push(&Button3Pressed, millis(start, 1150));
+        recognizer.push(&Button3Released, millis(start, 1200));
+        assert_eq!(
+            recognizer.tick(millis(start, 1200)),
+            [Gesture::DoubleTap(3)]
+        );
+        assert!(recognizer.tick(millis(start, 2000)).is_empty());
+    }
+
+    #[test]
+    fn held_buttons_long_press_and_repeat() {
+        let start = Instant::now();
+        let mut recognizer = GestureRecognizer::new(GestureTimings {
+            long_press: Duration::from_millis(300),
+            ..GestureTimings::default()
+        });
+
+        recognizer.push(&Button1Pressed, start);
+        assert!(recognizer.tick(millis(start, 299)).is_empty());
+        assert_eq!(
+            recognizer.tick(millis(start, 300)),
+            [Gesture::LongPress(1), Gesture::HoldRepeat(1)]
+        );
+        assert!(recognizer.tick(millis(start, 350)).is_empty());
+        assert_eq!(
+            recognizer.tick(millis(start, 400)),
+            [Gesture::HoldRepeat(1)]
+        );
+        // Late ticks do not catch up
+        assert_eq!(
+            recognizer.tick(millis(start, 750)),
+            [Gesture::HoldRepeat(1)]
+        );
+        assert!(recognizer.tick(millis(start, 780)).is_empty());
+
+        recognizer.push(&Button1Released, millis(start, 790));
+        assert!(recognizer.tick(millis(start, 5000)).is_empty());
+        assert_eq!(recognizer.next_deadline(start), None);
+    }
+
+    #[test]
+    fn buttons_pressed_together_are_a_chord() {
+        let start = Instant::now();
+        let mut recognizer = GestureRecognizer::default();
+
+        recognizer.push(&Button7Pressed, start);
+        recognizer.push(&Button2Pressed, millis(start, 20));
+        assert_eq!(recognizer.tick(millis(start, 20)), [Gesture::Chord(2, 7)]);
+
+        // Neither of them taps or long presses
+        recognizer.push(&Button2Released, millis(start, 100));
+        assert!(recognizer.tick(millis(start, 1000)).is_empty());
+        recognizer.push(&Button7Released, millis(start, 1100));
+        assert!(recognizer.tick(millis(start, 2000)).is_empty());
+
+        // Too far apart
+        recognizer.push(&Button4Pressed, millis(start, 3000));
+        recognizer.push(&Button5Pressed, millis(start, 3100));
+        recognizer.push(&Button4Released, millis(start, 3150));
+        recognizer.push(&Button5Released, millis(start, 3160));
+        assert_eq!(
+            recognizer.tick(millis(start, 4000)),
+            [Gesture::Tap(4), Gesture::Tap(5)]
+        );
+    }
+
+    #[test]
+    fn buttons_above_chord_buttons_do_not_make_chords() {
+        let start = Instant::now();
+        let mut recognizer = GestureRecognizer::default();
+
+        recognizer.push(&Button11Pressed, start);
+        recognizer.push(&Button3Pressed, millis(start, 10));
+        recognizer.push(&Button12Pressed, millis(start, 15));
+        assert!(recognizer.tick(millis(start, 15)).is_empty());
+
+        // Button 11 does not stop button 3 from making a chord with another one
+        recognizer.push(&Button6Pressed, millis(start, 20));
+        assert_eq!(recognizer.tick(millis(start, 20)), [Gesture::Chord(3, 6)]);
+
+        recognizer.push(&Button11Released, millis(start, 100));
+        recognizer.push(&Button12Released, millis(start, 100));
+        assert_eq!(
+            recognizer.tick(millis(start, 1000)),
+            [Gesture::Tap(11), Gesture::Tap(12)]
+        );
+    }
+}
@@ -33,3 +33,49 @@ pub const KNOB_1_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x37\x01\0
 pub const KNOB_2_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x35\x01\0\0";
 pub const KNOB_3_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x33\x01\0\0";
 pub const KNOB_4_PRESSED: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\x36\x01\0\0";
+
+/// One of the knobs, regardless of what it was doing
+#[derive(Debug, Clone, Copy, PartialEq, Hash, Eq)]
+pub enum Knob {
+    Knob1,
+    Knob2,
+    Knob3,
+    Knob4,
+}
+
+impl Knob {
+    /// The action a single detent of this knob is reported as
+    pub fn detent(&self, clockwise: bool) -> KnobActions {
+        match (self, clockwise) {
+            (Knob::Knob1, true) => KnobActions::Knob1Clockwise,
+            (Knob::Knob2, true) => KnobActions::Knob2Clockwise,
+            (Knob::Knob3, true) => KnobActions::Knob3Clockwise,
+            (Knob::Knob4, true) => KnobActions::Knob4Clockwise,
+            (Knob::Knob1, false) => KnobActions::Knob1CounterClockwise,
+            (Knob::Knob2, false) => KnobActions::Knob2CounterClockwise,
+            (Knob::Knob3, false) => KnobActions::Knob3CounterClockwise,
+            (Knob::Knob4, false) => KnobActions::Knob4CounterClockwise,
+        }
+    }
+}
+
+impl KnobActions {
+    /// The knob a detent was turned on along with its direction, 1 for clockwise and -1 for
+    /// counter-clockwise. Presses are not rotations.
+    pub fn rotation(&self) -> Option<(Knob, i32)> {
+        match self {
+            KnobActions::Knob1Clockwise => Some((Knob::Knob1, 1)),
+            KnobActions::Knob2Clockwise => Some((Knob::Knob2, 1)),
+            KnobActions::Knob3Clockwise => Some((Knob::Knob3, 1)),
+            KnobActions::Knob4Clockwise => Some((Knob::Knob4, 1)),
+            KnobActions::Knob1CounterClockwise => Some((Knob::Knob1, -1)),
+            KnobActions::Knob2CounterClockwise => Some((Knob::Knob2, -1)),
+            KnobActions::Knob3CounterClockwise => Some((Knob::Knob3, -1)),
+            KnobActions::Knob4CounterClockwise => Some((Knob::Knob4, -1)),
+            KnobActions::Knob1Pressed
+            | KnobActions::Knob2Pressed
+            | KnobActions::Knob3Pressed
+            | KnobActions::Knob4Pressed => None,
+        }
+    }
+}
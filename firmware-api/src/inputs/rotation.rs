@@ -0,0 +1,260 @@
+//! Turns the single detents of the knobs into rotations with a delta and a velocity.
+//!
+//! The device reports every detent on its own, so spinning a knob quickly looks just like
+//! turning it slowly, only with more reports. A `RotationAggregator` is `push`ed the
+//! detents as they are read and hands out one `KnobRotation` per knob once its `window`
+//! has passed (or straight away when the knob changes direction), with the number of
+//! detents and how fast they came in. It is [tick-driven](crate#tick-driven-helpers).
+//!
+//! ```no_run
+//! # use firmware_api::device::{Device, HidDeviceWrapper, InputHandler};
+//! # use firmware_api::inputs::{InputActions, InputEvent};
+//! # use firmware_api::inputs::rotation::RotationAggregator;
+//! # use std::sync::Mutex;
+//! # use std::time::Instant;
+//! struct Knobs(Mutex<RotationAggregator>);
+//!
+//! impl InputHandler for Knobs {
+//!     fn handle(&self, _action: InputActions) {}
+//!
+//!     fn handle_event(&self, event: InputEvent) {
+//!         if let InputActions::Knob(knob_action) = event.action {
+//!             self.0.lock().unwrap().push(&knob_action, event.read_at);
+//!         }
+//!     }
+//! }
+//!
+//! # fn run(device: Device<HidDeviceWrapper, Knobs>) {
+//! loop {
+//!     device.read_input().ok();
+//!     for rotation in device.handler().0.lock().unwrap().tick(Instant::now()) {
+//!         println!("{:?} turned {} at {:.0}/s", rotation.knob, rotation.delta, rotation.velocity);
+//!     }
+//! }
+//! # }
+//! ```
+use crate::inputs::knobs::{Knob, KnobActions};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long detents are collected before they are handed out, short enough not to be noticed
+pub const DEFAULT_ROTATION_WINDOW: Duration = Duration::from_millis(50);
+
+/// A detent this long after the previous one (or the first detent) counts as turning slowly
+pub const IDLE_DETENT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Detents of a knob in the same direction, read within one window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KnobRotation {
+    pub knob: Knob,
+    /// Detents turned, positive is clockwise
+    pub delta: i32,
+    /// Detents per second, positive is clockwise. Measured from the detent before the
+    /// first one, anything slower than `IDLE_DETENT_INTERVAL` counts as that.
+    pub velocity: f64,
+    /// When the last detent was read
+    pub at: Instant,
+}
+
+impl KnobRotation {
+    /// The action a single detent in this direction is reported as, e.g. to look up its mapping
+    pub fn detent(&self) -> KnobActions {
+        self.knob.detent(self.delta > 0)
+    }
+}
+
+/// A rotation that is still collecting detents
+#[derive(Debug, Clone)]
+struct PendingRotation {
+    delta: i32,
+    /// Sum of the intervals between the detents
+    elapsed: Duration,
+    started: Instant,
+    last: Instant,
+}
+
+impl PendingRotation {
+    fn finish(self, knob: Knob) -> KnobRotation {
+        let elapsed = self.elapsed.max(Duration::from_millis(1));
+
+        KnobRotation {
+            knob,
+            delta: self.delta,
+            velocity: f64::from(self.delta) / elapsed.as_secs_f64(),
+            at: self.last,
+        }
+    }
+}
+
+/// Collects detents into rotations, see the module documentation
+#[derive(Debug, Clone)]
+pub struct RotationAggregator {
+    window: Duration,
+    pending: HashMap<Knob, PendingRotation>,
+    /// When each knob last turned and in which direction
+    last_detents: HashMap<Knob, (Instant, i32)>,
+    ready: Vec<KnobRotation>,
+}
+
+impl Default for RotationAggregator {
+    fn default() -> Self {
+        Self::new(DEFAULT_ROTATION_WINDOW)
+    }
+}
+
+impl RotationAggregator {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: HashMap::new(),
+            last_detents: HashMap::new(),
+            ready: Vec::new(),
+        }
+    }
+
+    /// Records a detent read at `now`, returns whether `action` was one (presses are not)
+    pub fn push(&mut self, action: &KnobActions, now: Instant) -> bool {
+        let Some((knob, direction)) = action.rotation() else {
+            return false;
+        };
+
+        let interval = match self.last_detents.insert(knob, (now, direction)) {
+            Some((last, last_direction)) if last_direction == direction => now
+                .saturating_duration_since(last)
+                .min(IDLE_DETENT_INTERVAL),
+            _ => IDLE_DETENT_INTERVAL,
+        };
+
+        // Turning back hands out what was turned so far, a rotation only goes one way
+        if let Some(pending) = self.pending.get(&knob)
+            && pending.delta.signum() != direction
+            && let Some(pending) = self.pending.remove(&knob)
+        {
+            self.ready.push(pending.finish(knob));
+        }
+
+        let pending = self.pending.entry(knob).or_insert(PendingRotation {
+            delta: 0,
+            elapsed: Duration::ZERO,
+            started: now,
+            last: now,
+        });
+        pending.delta += direction;
+        pending.elapsed += interval;
+        pending.last = now;
+        true
+    }
+
+    /// When `tick` next has a rotation to hand out, `None` while no knob is turning
+    pub fn next_deadline(&self, now: Instant) -> Option<Instant> {
+        if !self.ready.is_empty() {
+            return Some(now);
+        }
+
+        self.pending
+            .values()
+            .map(|pending| pending.started + self.window)
+            .min()
+    }
+
+    /// Every rotation that finished by `now`, in the order their last detent was read
+    pub fn tick(&mut self, now: Instant) -> Vec<KnobRotation> {
+        let window = self.window;
+        let due: Vec<Knob> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| pending.started + window <= now)
+            .map(|(knob, _)| *knob)
+            .collect();
+
+        for knob in due {
+            if let Some(pending) = self.pending.remove(&knob) {
+                self.ready.push(pending.finish(knob));
+            }
+        }
+
+        let mut ready = std::mem::take(&mut self.ready);
+        ready.sort_by_key(|rotation| rotation.at);
+        ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_millis(50);
+
+    fn millis(start: Instant, millis: u64) -> Instant {
+        start + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn detents_within_the_window_are_one_rotation() {
+        let start = Instant::now();
+        let mut aggregator = RotationAggregator::new(WINDOW);
+
+        assert!(aggregator.push(&KnobActions::Knob2Clockwise, start));
+        aggregator.push(&KnobActions::Knob2Clockwise, millis(start, 10));
+        aggregator.push(&KnobActions::Knob2Clockwise, millis(start, 20));
+        assert!(!aggregator.push(&KnobActions::Knob2Pressed, millis(start, 25)));
+
+        assert!(aggregator.tick(millis(start, 40)).is_empty());
+        assert_eq!(aggregator.next_deadline(start), Some(millis(start, 50)));
+
+        let rotations = aggregator.tick(millis(start, 50));
+        assert_eq!(rotations.len(), 1);
+        assert_eq!(rotations[0].knob, Knob::Knob2);
+        assert_eq!(rotations[0].delta, 3);
+        assert_eq!(rotations[0].detent(), KnobActions::Knob2Clockwise);
+        // The first detent counts as a slow one: 3 detents in 0.52s
+        assert!((rotations[0].velocity - 3.0 / 0.52).abs() < 0.001);
+        assert_eq!(aggregator.next_deadline(start), None);
+    }
+
+    #[test]
+    fn spinning_quickly_is_faster_than_turning_slowly() {
+        let start = Instant::now();
+        let mut aggregator = RotationAggregator::new(WINDOW);
+
+        aggregator.push(&KnobActions::Knob1CounterClockwise, start);
+        let slow = aggregator.tick(millis(start, 100));
+        for detent in 0..5 {
+            aggregator.push(
+                &KnobActions::Knob1CounterClockwise,
+                millis(start, 300 + detent * 8),
+            );
+        }
+        let fast = aggregator.tick(millis(start, 400));
+
+        assert_eq!(slow[0].delta, -1);
+        assert_eq!(fast[0].delta, -5);
+        assert!(fast[0].velocity.abs() > 5.0 * slow[0].velocity.abs());
+        assert!(fast[0].velocity < 0.0 && slow[0].velocity < 0.0);
+    }
+
+    #[test]
+    fn turning_back_starts_a_new_rotation() {
+        let start = Instant::now();
+        let mut aggregator = RotationAggregator::new(WINDOW);
+
+        aggregator.push(&KnobActions::Knob3Clockwise, start);
+        aggregator.push(&KnobActions::Knob3Clockwise, millis(start, 5));
+        aggregator.push(&KnobActions::Knob4Clockwise, millis(start, 8));
+        aggregator.push(&KnobActions::Knob3CounterClockwise, millis(start, 10));
+
+        assert_eq!(
+            aggregator.next_deadline(millis(start, 10)),
+            Some(millis(start, 10))
+        );
+        let rotations: Vec<(Knob, i32)> = aggregator
+            .tick(millis(start, 60))
+            .iter()
+            .map(|rotation| (rotation.knob, rotation.delta))
+            .collect();
+        assert_eq!(
+            rotations,
+            [(Knob::Knob3, 2), (Knob::Knob4, 1), (Knob::Knob3, -1)]
+        );
+    }
+}
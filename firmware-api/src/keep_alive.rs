@@ -0,0 +1,135 @@
+//! Sends the heartbeat the device expects from a connected host.
+//!
+//! Without it the launchpad eventually assumes the host has gone away and falls back to
+//! its boot logo. A `KeepAlive` is [tick-driven](crate#tick-driven-helpers), the
+//! `AsyncDevice` (behind the `async` feature) can send heartbeats from its own thread instead.
+//!
+//! ```no_run
+//! # use firmware_api::device::{Device, FunctionHandler, HidDeviceWrapper};
+//! # use firmware_api::keep_alive::KeepAlive;
+//! # use std::time::Instant;
+//! # fn run(device: Device<HidDeviceWrapper, FunctionHandler>) {
+//! let mut keep_alive = KeepAlive::default();
+//!
+//! loop {
+//!     device.read_input().ok();
+//!     keep_alive.tick(&device, Instant::now()).ok();
+//! }
+//! # }
+//! ```
+use crate::device::{Device, HidDeviceOperations, InputHandler};
+use crate::error::Result;
+use std::time::{Duration, Instant};
+
+/// How often the StreamDock software sends a heartbeat
+pub const DEFAULT_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Keeps track of when the next heartbeat is due
+#[derive(Debug, Clone)]
+pub struct KeepAlive {
+    interval: Duration,
+    /// `None` until the first heartbeat is sent, which is due straight away
+    last_sent: Option<Instant>,
+}
+
+impl Default for KeepAlive {
+    fn default() -> Self {
+        Self::new(DEFAULT_KEEP_ALIVE_INTERVAL)
+    }
+}
+
+impl KeepAlive {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_sent: None,
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// When the next heartbeat needs to be sent, useful to know how long the caller can sleep
+    pub fn next_deadline(&self, now: Instant) -> Instant {
+        self.last_sent
+            .map_or(now, |last_sent| last_sent + self.interval)
+    }
+
+    /// Whether a heartbeat is due at `now`, if so it is counted as sent.
+    ///
+    /// Use this when the heartbeat is sent some other way, e.g. to several devices at once.
+    pub fn due(&mut self, now: Instant) -> bool {
+        if now < self.next_deadline(now) {
+            return false;
+        }
+
+        self.last_sent = Some(now);
+        true
+    }
+
+    /// Sends a heartbeat to `device` if one is due at `now`, returns whether one was sent
+    pub fn tick<H: HidDeviceOperations, I: InputHandler>(
+        &mut self,
+        device: &Device<H, I>,
+        now: Instant,
+    ) -> Result<bool> {
+        if !self.due(now) {
+            return Ok(false);
+        }
+
+        device.send_heartbeat()?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::DecodedCommand;
+    use crate::device::FunctionHandler;
+    use crate::mock::MockHidDevice;
+
+    fn heartbeats_sent(mock: &MockHidDevice) -> usize {
+        mock.written_frames()
+            .iter()
+            .filter(|frame| crate::decoder::decode(frame) == DecodedCommand::Heartbeat)
+            .count()
+    }
+
+    #[test]
+    fn first_heartbeat_is_sent_straight_away() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        let mut keep_alive = KeepAlive::default();
+
+        assert!(keep_alive.tick(&device, Instant::now()).unwrap());
+        mock.assert_heartbeat_sent();
+    }
+
+    #[test]
+    fn heartbeats_are_sent_once_per_interval() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        let mut keep_alive = KeepAlive::new(Duration::from_secs(5));
+        let start = Instant::now();
+
+        keep_alive.tick(&device, start).unwrap();
+        assert!(
+            !keep_alive
+                .tick(&device, start + Duration::from_secs(4))
+                .unwrap()
+        );
+        assert!(
+            keep_alive
+                .tick(&device, start + Duration::from_secs(5))
+                .unwrap()
+        );
+
+        assert_eq!(heartbeats_sent(&mock), 2);
+        assert_eq!(
+            keep_alive.next_deadline(start),
+            start + Duration::from_secs(10)
+        );
+    }
+}
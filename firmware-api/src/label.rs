@@ -0,0 +1,337 @@
+//! Renders text labels (optionally with an icon) into images for the display zones.
+//!
+//! Labels are drawn at the native resolution of the zone, so the output of
+//! `Label::render_jpeg` can be uploaded as-is. The simplest way to show a label is
+//! through `Device::set_display_zone_label`.
+use crate::image_processing::{Resolution, encode_jpeg};
+use ab_glyph::{Font, PxScale, ScaleFont, point};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageResult, Rgba, RgbaImage, imageops};
+use std::path::Path;
+
+pub use ab_glyph::FontArc;
+
+/// Space (in pixels) kept clear around the edges of the zone and between the icon and text
+const PADDING: u32 = 6;
+
+const DEFAULT_FONT_SIZE: f32 = 20.0;
+const DEFAULT_FOREGROUND: [u8; 3] = [255, 255, 255];
+const DEFAULT_BACKGROUND: [u8; 3] = [0, 0, 0];
+
+/// Loads a TrueType or OpenType font from disk so it can be used for labels
+pub fn load_font(path: impl AsRef<Path>) -> std::io::Result<FontArc> {
+    let data = std::fs::read(path)?;
+    FontArc::try_from_vec(data).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// A piece of text to be shown on a display zone.
+///
+/// Defaults to white text (20px) on a black background without an icon.
+/// Text that does not fit on one line is wrapped on word boundaries,
+/// explicit line breaks (`\n`) are also respected.
+#[derive(Clone)]
+pub struct Label {
+    text: String,
+    font: FontArc,
+    font_size: f32,
+    foreground: [u8; 3],
+    background: [u8; 3],
+    icon: Option<Vec<u8>>,
+}
+
+impl Label {
+    pub fn new(text: impl Into<String>, font: FontArc) -> Self {
+        Self {
+            text: text.into(),
+            font,
+            font_size: DEFAULT_FONT_SIZE,
+            foreground: DEFAULT_FOREGROUND,
+            background: DEFAULT_BACKGROUND,
+            icon: None,
+        }
+    }
+
+    /// * `font_size` - height of the text in pixels
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// * `foreground` - RGB colour of the text
+    /// * `background` - RGB colour the rest of the zone is filled with
+    pub fn with_colours(mut self, foreground: [u8; 3], background: [u8; 3]) -> Self {
+        self.foreground = foreground;
+        self.background = background;
+        self
+    }
+
+    /// Shows an icon above the text, scaled down to fit the space the text does not use
+    ///
+    /// * `icon` - the encoded image (any format supported by `image_processing`)
+    pub fn with_icon(mut self, icon: impl Into<Vec<u8>>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Draws the label onto an image of exactly `resolution`
+    ///
+    /// Only fails if the icon could not be decoded.
+    pub fn render(&self, resolution: Resolution) -> ImageResult<DynamicImage> {
+        let Resolution { width, height } = resolution;
+        let [red, green, blue] = self.background;
+        let mut canvas = RgbaImage::from_pixel(width, height, Rgba([red, green, blue, 255]));
+
+        let font = self.font.as_scaled(PxScale::from(self.font_size));
+        let max_line_width = width.saturating_sub(2 * PADDING) as f32;
+        let lines = wrap_text(&self.text, max_line_width, |line| measure_line(&font, line));
+
+        let line_height = font.height() + font.line_gap();
+        let text_height = match lines.len() {
+            0 => 0.0,
+            count => count as f32 * line_height - font.line_gap(),
+        };
+
+        let text_top = match &self.icon {
+            Some(icon) => {
+                let icon = image::load_from_memory(icon)?;
+                let icon_space = height as f32 - text_height - (3 * PADDING) as f32;
+                if icon_space >= 1.0 {
+                    let icon = icon.resize(
+                        width.saturating_sub(2 * PADDING),
+                        icon_space as u32,
+                        FilterType::CatmullRom,
+                    );
+                    let x = (width - icon.width()) / 2;
+                    imageops::overlay(&mut canvas, &icon.to_rgba8(), x.into(), PADDING.into());
+                }
+                height as f32 - PADDING as f32 - text_height
+            }
+            None => (height as f32 - text_height) / 2.0,
+        };
+
+        for (index, line) in lines.iter().enumerate() {
+            let x = (width as f32 - measure_line(&font, line)) / 2.0;
+            let baseline = text_top + index as f32 * line_height + font.ascent();
+            self.draw_line(&mut canvas, &font, line, x, baseline);
+        }
+
+        Ok(DynamicImage::ImageRgba8(canvas))
+    }
+
+    /// Same as `render`, but encoded as a JPEG ready to be sent to the device
+    pub fn render_jpeg(&self, resolution: Resolution) -> ImageResult<Vec<u8>> {
+        encode_jpeg(&self.render(resolution)?)
+    }
+
+    fn draw_line<F: Font, SF: ScaleFont<F>>(
+        &self,
+        canvas: &mut RgbaImage,
+        font: &SF,
+        line: &str,
+        x: f32,
+        baseline: f32,
+    ) {
+        let mut caret = x;
+        let mut previous = None;
+
+        for character in line.chars() {
+            let glyph_id = font.glyph_id(character);
+            if let Some(previous) = previous {
+                caret += font.kern(previous, glyph_id);
+            }
+            previous = Some(glyph_id);
+
+            let glyph = glyph_id.with_scale_and_position(font.scale(), point(caret, baseline));
+            caret += font.h_advance(glyph_id);
+
+            let Some(outline) = font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outline.px_bounds();
+
+            outline.draw(|glyph_x, glyph_y, coverage| {
+                let x = bounds.min.x as i64 + glyph_x as i64;
+                let y = bounds.min.y as i64 + glyph_y as i64;
+                if x < 0 || y < 0 || x >= canvas.width() as i64 || y >= canvas.height() as i64 {
+                    return;
+                }
+
+                let pixel = canvas.get_pixel_mut(x as u32, y as u32);
+                for channel in 0..3 {
+                    let blended = self.foreground[channel] as f32 * coverage
+                        + pixel[channel] as f32 * (1.0 - coverage);
+                    pixel[channel] = blended.round() as u8;
+                }
+            });
+        }
+    }
+}
+
+fn measure_line<F: Font, SF: ScaleFont<F>>(font: &SF, line: &str) -> f32 {
+    let mut width = 0.0;
+    let mut previous = None;
+
+    for character in line.chars() {
+        let glyph_id = font.glyph_id(character);
+        if let Some(previous) = previous {
+            width += font.kern(previous, glyph_id);
+        }
+        width += font.h_advance(glyph_id);
+        previous = Some(glyph_id);
+    }
+
+    width
+}
+
+/// Greedily splits `text` into lines no wider than `max_width`.
+///
+/// A single word that is wider than `max_width` is kept on its own line
+/// rather than being broken up.
+fn wrap_text(text: &str, max_width: f32, measure: impl Fn(&str) -> f32) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut current = String::new();
+
+        for word in paragraph.split_whitespace() {
+            let candidate = match current.is_empty() {
+                true => word.to_string(),
+                false => format!("{} {}", current, word),
+            };
+
+            if current.is_empty() || measure(&candidate) <= max_width {
+                current = candidate;
+            } else {
+                lines.push(std::mem::replace(&mut current, word.to_string()));
+            }
+        }
+
+        lines.push(current);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A TrueType font with a single glyph, a filled square, that every character falls back to
+    fn square_font() -> FontArc {
+        let be16 =
+            |values: &[i16]| -> Vec<u8> { values.iter().flat_map(|v| v.to_be_bytes()).collect() };
+
+        let mut head = be16(&[1, 0, 1, 0, 0, 0, 0x5f0f, 0x3cf5u16 as i16, 0, 1000]);
+        head.extend([0; 16]); // created and modified
+        head.extend(be16(&[100, 0, 600, 700, 0, 8, 2, 0, 0]));
+        let hhea = be16(&[
+            1, 0, 800, -200, 0, 700, 100, 100, 600, 1, 0, 0, 0, 0, 0, 0, 0, 1,
+        ]);
+        let maxp = be16(&[0, 0x5000, 1]);
+        let hmtx = be16(&[700, 100]);
+        // One contour through (100, 0), (100, 700), (600, 700) and (600, 0), as deltas
+        let mut glyf = be16(&[1, 100, 0, 600, 700, 3, 0]);
+        glyf.extend([1; 4]);
+        glyf.extend(be16(&[100, 0, 500, 0, 0, 700, 0, -700]));
+        glyf.resize(36, 0);
+        let loca = be16(&[0, 18]);
+
+        let tables = [
+            (b"glyf", glyf),
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"hmtx", hmtx),
+            (b"loca", loca),
+            (b"maxp", maxp),
+        ];
+        let mut font = be16(&[1, 0, tables.len() as i16, 0, 0, 0]);
+        let mut data = Vec::new();
+        let data_start = font.len() + 16 * tables.len();
+        for (tag, table) in tables {
+            font.extend(tag);
+            font.extend([0; 4]); // checksum
+            font.extend(((data_start + data.len()) as u32).to_be_bytes());
+            font.extend((table.len() as u32).to_be_bytes());
+            data.extend(&table);
+            data.resize(data.len().next_multiple_of(4), 0);
+        }
+        font.extend(data);
+
+        FontArc::try_from_vec(font).unwrap()
+    }
+
+    #[test]
+    fn labels_are_drawn_at_the_zone_resolution() {
+        let resolution = Resolution {
+            width: 112,
+            height: 112,
+        };
+        let label = Label::new("Mic Off", square_font()).with_colours([255, 0, 0], [0, 0, 255]);
+
+        let image = label.render(resolution).unwrap().to_rgb8();
+
+        assert_eq!(image.dimensions(), (112, 112));
+        assert_eq!(image.get_pixel(0, 0).0, [0, 0, 255]);
+        let text_pixels = image
+            .pixels()
+            .filter(|pixel| pixel.0 == [255, 0, 0])
+            .count();
+        assert!(text_pixels > 100, "only {} pixels of text", text_pixels);
+    }
+
+    #[test]
+    fn empty_labels_are_only_background() {
+        let resolution = Resolution {
+            width: 176,
+            height: 112,
+        };
+
+        let image = Label::new("", square_font())
+            .render(resolution)
+            .unwrap()
+            .to_rgb8();
+
+        assert_eq!(image.dimensions(), (176, 112));
+        assert!(image.pixels().all(|pixel| pixel.0 == DEFAULT_BACKGROUND));
+    }
+
+    /// Every character is 10 pixels wide
+    fn monospace(line: &str) -> f32 {
+        line.chars().count() as f32 * 10.0
+    }
+
+    #[test]
+    fn short_text_stays_on_one_line() {
+        assert_eq!(wrap_text("Mute", 100.0, monospace), vec!["Mute"]);
+    }
+
+    #[test]
+    fn long_text_wraps_on_words() {
+        assert_eq!(
+            wrap_text("Deploy to production", 100.0, monospace),
+            vec!["Deploy to", "production"]
+        );
+    }
+
+    #[test]
+    fn oversized_word_is_kept_whole() {
+        assert_eq!(
+            wrap_text("Supercalifragilistic on", 100.0, monospace),
+            vec!["Supercalifragilistic", "on"]
+        );
+    }
+
+    #[test]
+    fn explicit_line_breaks_are_respected() {
+        assert_eq!(wrap_text("Mic\nOff", 100.0, monospace), vec!["Mic", "Off"]);
+    }
+
+    #[test]
+    fn empty_text_has_no_lines() {
+        assert!(wrap_text("", 100.0, monospace).is_empty());
+    }
+}
@@ -4,9 +4,17 @@
 //!
 //! Most of the APIs needed are from the Device crate.
 //!
-//! Note for images: when passing the image `File` as an argument, it MUST
-//! be a JPG or JPEG format, or it will not be handled properly by the device
-//! (an exception for background image which somehow works with PNG, but JPG still preferred)
+//! Note for images: the device itself only handles JPG or JPEG images that exactly match
+//! the resolution of the zone they are written to. Images passed to the `Device` are
+//! converted automatically (PNG, WebP, BMP, GIF and differently sized JPEGs are supported),
+//! see the `image_processing` module for details.
+//!
+//! ## Tick-driven helpers
+//!
+//! The `Animator`, `BrightnessFade`, `KeepAlive` and `WriteScheduler` (and on the input
+//! side the `RotationAggregator` and `GestureRecognizer`) do not spawn any threads. Each has
+//! a `tick` method taking the current `Instant`, to be called regularly from the loop that
+//! owns the `Device` (or reads its input), which does whatever is due by then.
 //!
 //! ## Display zone Layout
 //!
@@ -31,5 +39,32 @@ pub mod inputs;
 mod common;
 
 pub mod display_zones;
+pub mod profiles;
+
+pub mod image_processing;
+
+pub mod animation;
+pub mod fade;
+
+pub mod keep_alive;
+pub mod scheduler;
+pub mod transfer;
+
+pub mod label;
+pub mod span;
 
+pub mod error;
+
+pub use error::Error;
+
+#[cfg(feature = "async")]
+pub mod async_device;
 pub mod device;
+
+pub mod decoder;
+
+pub mod capture;
+#[cfg(feature = "session")]
+pub mod session;
+
+pub mod mock;
@@ -0,0 +1,344 @@
+//! An in-memory stand-in for the launchpad so `Device` can be exercised without hardware.
+//!
+//! `MockHidDevice` records every frame written to it, hands out scripted input
+//! reports when read from, and can be told to fail the next read or write.
+//! The `assert_*` helpers search the recorded transcript for a given command and
+//! panic with the whole transcript if it cannot be found. Zones are looked up with the
+//! AKP05 ids unless the mock is made `with_profile` for another model.
+//!
+//! ```
+//! use firmware_api::device::{Device, FunctionHandler};
+//! use firmware_api::mock::MockHidDevice;
+//!
+//! let mock = MockHidDevice::new();
+//! let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+//!
+//! device.set_brightness(40).unwrap();
+//!
+//! mock.assert_brightness_set(40);
+//! ```
+use crate::commands::messages;
+use crate::common::{ByteArray, IMAGE_DATA_PACKET_LENGTH, IMAGE_SIZE_LENGTH_IN_BYTES};
+use crate::decoder::{Decoder, summarise};
+use crate::device::HidDeviceOperations;
+use crate::display_zones::DisplayZones;
+use crate::inputs::input_buffer::BUFFER_SIZE_13;
+use crate::profiles::{Akp05, DeviceProfile};
+#[cfg(feature = "acknowledged-transfer")]
+use crate::transfer::ACKNOWLEDGEMENT;
+use hidapi::{HidError, HidResult};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Default)]
+struct MockState {
+    written_frames: Vec<Vec<u8>>,
+    input_reports: VecDeque<ByteArray<BUFFER_SIZE_13>>,
+    read_errors: VecDeque<HidError>,
+    write_errors: VecDeque<HidError>,
+}
+
+/// Behaves like a launchpad opened in non-blocking mode: reading when no input
+/// report is queued succeeds without filling the buffer
+pub struct MockHidDevice {
+    state: Mutex<MockState>,
+    profile: &'static dyn DeviceProfile,
+}
+
+impl Default for MockHidDevice {
+    fn default() -> Self {
+        Self::with_profile(&Akp05)
+    }
+}
+
+impl MockHidDevice {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A mock whose assertions address zones like `profile` does, for a `Device` of that model
+    pub fn with_profile(profile: &'static dyn DeviceProfile) -> Self {
+        Self {
+            state: Mutex::default(),
+            profile,
+        }
+    }
+
+    /// Adds a report to be returned by a future read, reports are returned in the order queued
+    pub fn queue_input(&self, report: ByteArray<BUFFER_SIZE_13>) {
+        self.state().input_reports.push_back(report);
+    }
+
+    /// Acknowledges an image upload, for a `Device` using `TransferMode::Acknowledged`
+    #[cfg(feature = "acknowledged-transfer")]
+    pub fn queue_acknowledgement(&self) {
+        self.queue_input(ACKNOWLEDGEMENT);
+    }
+
+    /// Makes the next read fail with `error` instead of returning a report
+    pub fn fail_next_read(&self, error: HidError) {
+        self.state().read_errors.push_back(error);
+    }
+
+    /// Makes the next write fail with `error`, the frame will not be recorded
+    pub fn fail_next_write(&self, error: HidError) {
+        self.state().write_errors.push_back(error);
+    }
+
+    /// Every frame written so far (including the leading report id byte)
+    pub fn written_frames(&self) -> Vec<Vec<u8>> {
+        self.state().written_frames.clone()
+    }
+
+    pub fn clear_written_frames(&self) {
+        self.state().written_frames.clear();
+    }
+
+    /// Every written frame decoded into a command, used in assertion failure messages
+    pub fn transcript(&self) -> String {
+        let mut decoder = Decoder::new();
+
+        self.state()
+            .written_frames
+            .iter()
+            .enumerate()
+            .map(|(index, frame)| {
+                format!(
+                    "#{} ({} bytes): {}",
+                    index,
+                    frame.len(),
+                    decoder.decode(frame)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Panics unless a frame with exactly `expected` at its start was written
+    pub fn assert_frame_written(&self, expected: &[u8]) {
+        let found = self
+            .state()
+            .written_frames
+            .iter()
+            .any(|frame| frame.starts_with(expected));
+
+        assert!(
+            found,
+            "expected a frame starting with {}, transcript:\n{}",
+            summarise(expected),
+            self.transcript()
+        );
+    }
+
+    /// Panics unless a `CRT LIG` frame with the given brightness was written
+    pub fn assert_brightness_set(&self, brightness: u8) {
+        self.assert_frame_written(&[messages::SET_BRIGHTNESS.as_slice(), &[brightness]].concat());
+    }
+
+    /// Panics unless a `CRT STP` frame was written
+    pub fn assert_refreshed(&self) {
+        self.assert_frame_written(&messages::REFRESH);
+    }
+
+    /// Panics unless a `CRT DIS` frame was written
+    pub fn assert_screen_woken(&self) {
+        self.assert_frame_written(&messages::WAKE_SCREEN);
+    }
+
+    /// Panics unless a `CRT CONNECT` frame was written
+    pub fn assert_heartbeat_sent(&self) {
+        self.assert_frame_written(&messages::HEARTBEAT);
+    }
+
+    /// Panics unless a `CRT HAN` frame was written
+    pub fn assert_screen_slept(&self) {
+        self.assert_frame_written(&messages::SLEEP_SCREEN);
+    }
+
+    /// Panics unless a `CRT CLE DC` frame was written
+    pub fn assert_disconnect_sent(&self) {
+        self.assert_frame_written(&messages::DISCONNECT);
+    }
+
+    /// Panics unless a `CRT CLE` frame for every zone was written
+    pub fn assert_all_images_cleared(&self) {
+        self.assert_frame_written(&messages::CLEAR_ALL_IMAGES);
+    }
+
+    /// Panics unless a `CRT CLE` frame for `display_zone` was written
+    pub fn assert_display_zone_cleared(&self, display_zone: DisplayZones) {
+        self.assert_frame_written(
+            &[
+                messages::CLEAR_DISPLAY_ZONE_IMAGE.as_slice(),
+                &[self.wire_id(display_zone)],
+            ]
+            .concat(),
+        );
+    }
+
+    /// Panics unless an image was uploaded to `display_zone` (a `CRT BAT` frame followed
+    /// by its data packets), returns the most recent image sent to that zone
+    pub fn assert_display_zone_image_uploaded(&self, display_zone: DisplayZones) -> Vec<u8> {
+        let wire_id = self.wire_id(display_zone);
+        self.uploaded_image(&messages::INITIATE_SET_DISPLAY_ZONE_IMAGE, |frame| {
+            frame.get(messages::INITIATE_SET_DISPLAY_ZONE_IMAGE.len() + IMAGE_SIZE_LENGTH_IN_BYTES)
+                == Some(&wire_id)
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "expected an image upload to {:?}, transcript:\n{}",
+                display_zone,
+                self.transcript()
+            )
+        })
+    }
+
+    /// Panics unless a background image was uploaded (a `CRT LOG` frame followed
+    /// by its data packets), returns the most recent background image
+    pub fn assert_background_image_uploaded(&self) -> Vec<u8> {
+        self.uploaded_image(&messages::INITIATE_SET_BACKGROUND_IMAGE, |_| true)
+            .unwrap_or_else(|| {
+                panic!(
+                    "expected a background image upload, transcript:\n{}",
+                    self.transcript()
+                )
+            })
+    }
+
+    /// The id the profile of the mock uses for `display_zone`
+    fn wire_id(&self, display_zone: DisplayZones) -> u8 {
+        self.profile
+            .zone_layout(display_zone)
+            .unwrap_or_else(|| panic!("an {} has no {:?}", self.profile.name(), display_zone))
+            .wire_id
+    }
+
+    /// Reassembles the last image whose initiating frame starts with `prefix` and
+    /// matches `filter`, `None` if there is none or not all of its data was sent
+    fn uploaded_image(&self, prefix: &[u8], filter: impl Fn(&[u8]) -> bool) -> Option<Vec<u8>> {
+        let state = self.state();
+        let frames = &state.written_frames;
+
+        let start = frames
+            .iter()
+            .rposition(|frame| frame.starts_with(prefix) && filter(frame))?;
+
+        let size_bytes =
+            frames[start].get(prefix.len()..prefix.len() + IMAGE_SIZE_LENGTH_IN_BYTES)?;
+        let size = u32::from_be_bytes(size_bytes.try_into().ok()?) as usize;
+
+        let mut image = Vec::with_capacity(size);
+        for frame in frames[start + 1..]
+            .iter()
+            .take(size.div_ceil(IMAGE_DATA_PACKET_LENGTH))
+        {
+            // Data packets only skip the report id
+            image.extend_from_slice(frame.get(1..)?);
+        }
+
+        if image.len() < size {
+            return None;
+        }
+
+        image.truncate(size);
+        Some(image)
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        // A panicking assertion must not hide the transcript from later checks
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl HidDeviceOperations for MockHidDevice {
+    fn read(&self, buffer: &mut [u8]) -> HidResult<usize> {
+        let mut state = self.state();
+
+        if let Some(error) = state.read_errors.pop_front() {
+            return Err(error);
+        }
+
+        match state.input_reports.pop_front() {
+            Some(report) => {
+                let length = report.len().min(buffer.len());
+                buffer[..length].copy_from_slice(&report[..length]);
+                Ok(length)
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn write(&self, data: &[u8]) -> HidResult<usize> {
+        let mut state = self.state();
+
+        if let Some(error) = state.write_errors.pop_front() {
+            return Err(error);
+        }
+
+        state.written_frames.push(data.to_vec());
+        Ok(data.len())
+    }
+
+    /// Waits out the whole timeout when no report is queued, like a real device would
+    fn read_timeout(&self, buffer: &mut [u8], timeout_ms: i32) -> HidResult<usize> {
+        let read = self.read(buffer)?;
+        if read == 0 && timeout_ms > 0 {
+            thread::sleep(Duration::from_millis(timeout_ms as u64));
+        }
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queued_reports_are_read_in_order() {
+        let mock = MockHidDevice::new();
+        mock.queue_input([1; BUFFER_SIZE_13]);
+        mock.queue_input([2; BUFFER_SIZE_13]);
+
+        let mut buffer = [0; BUFFER_SIZE_13];
+        mock.read(&mut buffer).unwrap();
+        assert_eq!(buffer, [1; BUFFER_SIZE_13]);
+        mock.read(&mut buffer).unwrap();
+        assert_eq!(buffer, [2; BUFFER_SIZE_13]);
+
+        // Nothing left to read behaves like a non-blocking read
+        let mut buffer = [0; BUFFER_SIZE_13];
+        assert_eq!(mock.read(&mut buffer).unwrap(), 0);
+        assert_eq!(buffer, [0; BUFFER_SIZE_13]);
+    }
+
+    #[test]
+    fn injected_errors_are_returned_once() {
+        let mock = MockHidDevice::new();
+        mock.fail_next_write(HidError::HidApiErrorEmpty);
+
+        assert!(mock.write(&[0, 1]).is_err());
+        assert!(mock.write(&[0, 2]).is_ok());
+        assert_eq!(mock.written_frames(), vec![vec![0, 2]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "transcript")]
+    fn failed_assertion_panics_with_transcript() {
+        let mock = MockHidDevice::new();
+        mock.write(&messages::REFRESH).unwrap();
+
+        mock.assert_brightness_set(40);
+    }
+
+    #[test]
+    fn summarises_printable_prefix() {
+        let mut frame = [0u8; 513];
+        frame[..9].copy_from_slice(&messages::REFRESH);
+
+        assert_eq!(summarise(&frame), "\\x00CRT\\x00\\x00STP");
+    }
+}
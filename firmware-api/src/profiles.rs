@@ -0,0 +1,391 @@
+//! Describes the models of the StreamDock family this crate can drive.
+//!
+//! The models share the same protocol, what differs between them is how many display
+//! zones they have, how big those are, the ids the device uses for them and which input
+//! reports they send. A `Device` uses the AKP05 profile unless told otherwise.
+//!
+//! ```no_run
+//! # use firmware_api::device::{Device, FunctionHandler, HidDeviceWrapper};
+//! # use firmware_api::profiles;
+//! let hid_api = hidapi::HidApi::new().unwrap();
+//! let info = hid_api
+//!     .device_list()
+//!     .find(|info| profiles::find_profile(info.vendor_id(), info.product_id()).is_some())
+//!     .unwrap();
+//! let profile = profiles::find_profile(info.vendor_id(), info.product_id()).unwrap();
+//!
+//! let mut device = Device::new(
+//!     HidDeviceWrapper::new(info.open_device(&hid_api).unwrap(), false),
+//!     FunctionHandler::new(|action| println!("{:?}", action)),
+//! );
+//! device.set_profile(profile);
+//! ```
+use crate::common::ByteArray;
+use crate::display_zones::{
+    BACKGROUND_RESOLUTION, BUTTON_RESOLUTION, DisplayZones, TOUCHSCREEN_RESOLUTION,
+};
+use crate::image_processing::Resolution;
+use crate::inputs::InputActions::Button;
+use crate::inputs::buttons::{self, ButtonActions};
+use crate::inputs::input_buffer::BUFFER_SIZE_13;
+use crate::inputs::{InputActions, KNOWN_REPORTS, decode_report};
+
+/// The vendor and product id a model enumerates with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UsbIdentifier {
+    pub vendor_id: u16,
+    pub product_id: u16,
+}
+
+/// The physical panel a display zone is part of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Panel {
+    Buttons,
+    Touchscreen,
+}
+
+/// Where a display zone is and what the device expects to be sent for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ZoneLayout {
+    pub display_zone: DisplayZones,
+    /// The id the device uses for the zone in its commands
+    pub wire_id: u8,
+    pub panel: Panel,
+    /// Position within the panel, counted from the top left
+    pub row: u8,
+    pub column: u8,
+    pub resolution: Resolution,
+}
+
+pub trait DeviceProfile: Send + Sync {
+    /// The model name, e.g. `AKP05`
+    fn name(&self) -> &'static str;
+
+    /// Every vendor/product id pair the model is sold with
+    fn usb_identifiers(&self) -> &'static [UsbIdentifier];
+
+    /// Every display zone the model has, the background is not one of them
+    fn display_zones(&self) -> &'static [ZoneLayout];
+
+    /// Resolution of the background image, which spans the whole display
+    fn background_resolution(&self) -> Resolution;
+
+    /// Every input report the model is known to send, only the code and state bytes are compared
+    fn known_reports(&self) -> &'static [(ByteArray<BUFFER_SIZE_13>, InputActions)];
+
+    fn matches(&self, vendor_id: u16, product_id: u16) -> bool {
+        self.usb_identifiers()
+            .iter()
+            .any(|id| id.vendor_id == vendor_id && id.product_id == product_id)
+    }
+
+    /// `None` if the model does not have `display_zone`
+    fn zone_layout(&self, display_zone: DisplayZones) -> Option<&'static ZoneLayout> {
+        self.display_zones()
+            .iter()
+            .find(|layout| layout.display_zone == display_zone)
+    }
+
+    /// Same as `InputActions::from`, but using the reports of this model
+    fn decode_input(&self, report: ByteArray<BUFFER_SIZE_13>) -> InputActions {
+        decode_report(self.known_reports(), report)
+    }
+}
+
+/// Every model this crate knows about, the experimental `Akp153` is left out until it is
+/// confirmed on a physical unit
+pub const PROFILES: &[&dyn DeviceProfile] = &[&Akp05];
+
+/// The profile of the model that enumerates with the given ids, if it is a known one
+pub fn find_profile(vendor_id: u16, product_id: u16) -> Option<&'static dyn DeviceProfile> {
+    PROFILES
+        .iter()
+        .copied()
+        .find(|profile| profile.matches(vendor_id, product_id))
+}
+
+/// The profile of the model with the given `DeviceProfile::name`, if it is a known one
+pub fn find_profile_by_name(name: &str) -> Option<&'static dyn DeviceProfile> {
+    PROFILES
+        .iter()
+        .copied()
+        .find(|profile| profile.name() == name)
+}
+
+const fn zone(
+    display_zone: DisplayZones,
+    wire_id: u8,
+    panel: Panel,
+    (row, column): (u8, u8),
+    resolution: Resolution,
+) -> ZoneLayout {
+    ZoneLayout {
+        display_zone,
+        wire_id,
+        panel,
+        row,
+        column,
+        resolution,
+    }
+}
+
+/// The AKP05 (Stream Dock N4): 10 buttons over a touchscreen strip with 4 zones and 4 knobs
+pub struct Akp05;
+
+const fn akp05_button(display_zone: DisplayZones, wire_id: u8, row: u8, column: u8) -> ZoneLayout {
+    zone(
+        display_zone,
+        wire_id,
+        Panel::Buttons,
+        (row, column),
+        BUTTON_RESOLUTION,
+    )
+}
+
+const fn akp05_touchscreen(display_zone: DisplayZones, wire_id: u8, column: u8) -> ZoneLayout {
+    zone(
+        display_zone,
+        wire_id,
+        Panel::Touchscreen,
+        (0, column),
+        TOUCHSCREEN_RESOLUTION,
+    )
+}
+
+const AKP05_ZONES: &[ZoneLayout] = &[
+    akp05_button(DisplayZones::Button1, 11, 0, 0),
+    akp05_button(DisplayZones::Button2, 12, 0, 1),
+    akp05_button(DisplayZones::Button3, 13, 0, 2),
+    akp05_button(DisplayZones::Button4, 14, 0, 3),
+    akp05_button(DisplayZones::Button5, 15, 0, 4),
+    akp05_button(DisplayZones::Button6, 6, 1, 0),
+    akp05_button(DisplayZones::Button7, 7, 1, 1),
+    akp05_button(DisplayZones::Button8, 8, 1, 2),
+    akp05_button(DisplayZones::Button9, 9, 1, 3),
+    akp05_button(DisplayZones::Button10, 10, 1, 4),
+    akp05_touchscreen(DisplayZones::Touchscreen1, 1, 0),
+    akp05_touchscreen(DisplayZones::Touchscreen2, 2, 1),
+    akp05_touchscreen(DisplayZones::Touchscreen3, 3, 2),
+    akp05_touchscreen(DisplayZones::Touchscreen4, 4, 3),
+];
+
+impl DeviceProfile for Akp05 {
+    fn name(&self) -> &'static str {
+        "AKP05"
+    }
+
+    fn usb_identifiers(&self) -> &'static [UsbIdentifier] {
+        &[UsbIdentifier {
+            vendor_id: 0x0300,
+            product_id: 0x3004,
+        }]
+    }
+
+    fn display_zones(&self) -> &'static [ZoneLayout] {
+        AKP05_ZONES
+    }
+
+    fn background_resolution(&self) -> Resolution {
+        BACKGROUND_RESOLUTION
+    }
+
+    fn known_reports(&self) -> &'static [(ByteArray<BUFFER_SIZE_13>, InputActions)] {
+        KNOWN_REPORTS
+    }
+}
+
+/// Resolution of each key of the AKP153, assumed
+pub const AKP153_BUTTON_RESOLUTION: Resolution = Resolution::new(85, 85);
+
+/// The AKP153 (Stream Dock 293): 15 keys in a 5×3 grid, without knobs or touchscreen.
+///
+/// Experimental and not part of `PROFILES`, so `find_profile` never picks it: the layout is
+/// assumed and not taken from the StreamDock SDK. The keys are numbered 1 to 15 row by row for
+/// images and input reports, while the SDK maps the keys of the AKP153 rotated. `0300:1010` is
+/// the id of the AKP153E variant, and the `Device` still sends it the 1025 byte frames of the
+/// AKP05. Only use it through `Device::set_profile` to experiment with a unit.
+pub struct Akp153;
+
+const fn akp153_button(display_zone: DisplayZones, wire_id: u8, row: u8, column: u8) -> ZoneLayout {
+    zone(
+        display_zone,
+        wire_id,
+        Panel::Buttons,
+        (row, column),
+        AKP153_BUTTON_RESOLUTION,
+    )
+}
+
+const AKP153_ZONES: &[ZoneLayout] = &[
+    akp153_button(DisplayZones::Button1, 1, 0, 0),
+    akp153_button(DisplayZones::Button2, 2, 0, 1),
+    akp153_button(DisplayZones::Button3, 3, 0, 2),
+    akp153_button(DisplayZones::Button4, 4, 0, 3),
+    akp153_button(DisplayZones::Button5, 5, 0, 4),
+    akp153_button(DisplayZones::Button6, 6, 1, 0),
+    akp153_button(DisplayZones::Button7, 7, 1, 1),
+    akp153_button(DisplayZones::Button8, 8, 1, 2),
+    akp153_button(DisplayZones::Button9, 9, 1, 3),
+    akp153_button(DisplayZones::Button10, 10, 1, 4),
+    akp153_button(DisplayZones::Button11, 11, 2, 0),
+    akp153_button(DisplayZones::Button12, 12, 2, 1),
+    akp153_button(DisplayZones::Button13, 13, 2, 2),
+    akp153_button(DisplayZones::Button14, 14, 2, 3),
+    akp153_button(DisplayZones::Button15, 15, 2, 4),
+];
+
+const AKP153_BUTTONS: usize = 15;
+
+const AKP153_REPORTS: &[(ByteArray<BUFFER_SIZE_13>, InputActions)] = &akp153_reports();
+
+/// Every key pressed, then every key released. The reports follow the AKP05 ones, with the
+/// key number as the code and 1 for pressed as the state.
+const fn akp153_reports() -> [(ByteArray<BUFFER_SIZE_13>, InputActions); 2 * AKP153_BUTTONS] {
+    let mut reports =
+        [const { (buttons::BUTTON_1_PRESSED, InputActions::Unknown) }; 2 * AKP153_BUTTONS];
+
+    let mut i = 0;
+    while i < reports.len() {
+        let button = (i % AKP153_BUTTONS) as u8 + 1;
+        let pressed = i < AKP153_BUTTONS;
+
+        let mut report = buttons::BUTTON_1_PRESSED;
+        report[9] = button;
+        report[10] = pressed as u8;
+        reports[i] = (
+            report,
+            Button(ButtonActions::from_button(button, pressed).unwrap()),
+        );
+        i += 1;
+    }
+
+    reports
+}
+
+impl DeviceProfile for Akp153 {
+    fn name(&self) -> &'static str {
+        "AKP153"
+    }
+
+    fn usb_identifiers(&self) -> &'static [UsbIdentifier] {
+        &[UsbIdentifier {
+            vendor_id: 0x0300,
+            product_id: 0x1010,
+        }]
+    }
+
+    fn display_zones(&self) -> &'static [ZoneLayout] {
+        AKP153_ZONES
+    }
+
+    fn background_resolution(&self) -> Resolution {
+        BACKGROUND_RESOLUTION
+    }
+
+    fn known_reports(&self) -> &'static [(ByteArray<BUFFER_SIZE_13>, InputActions)] {
+        AKP153_REPORTS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::knobs::KNOB_1_CLOCKWISE;
+
+    #[test]
+    fn profiles_are_found_by_usb_id() {
+        assert_eq!(find_profile(0x0300, 0x3004).unwrap().name(), "AKP05");
+        assert!(find_profile(0x0300, 0x1010).is_none());
+        assert!(find_profile(0x0300, 0xffff).is_none());
+    }
+
+    #[test]
+    fn profiles_are_found_by_name() {
+        assert_eq!(find_profile_by_name("AKP05").unwrap().name(), "AKP05");
+        assert!(find_profile_by_name("AKP153").is_none());
+        assert!(find_profile_by_name("AKP03").is_none());
+    }
+
+    #[test]
+    fn akp05_matches_the_display_zone_ids() {
+        for layout in Akp05.display_zones() {
+            assert_eq!(layout.wire_id, u8::from(layout.display_zone));
+            assert_eq!(layout.resolution, layout.display_zone.resolution());
+        }
+        assert!(Akp05.zone_layout(DisplayZones::Button11).is_none());
+    }
+
+    #[test]
+    fn zone_ids_and_positions_are_unique() {
+        for profile in PROFILES
+            .iter()
+            .copied()
+            .chain([&Akp153 as &dyn DeviceProfile])
+        {
+            let zones = profile.display_zones();
+            for (i, a) in zones.iter().enumerate() {
+                for b in &zones[i + 1..] {
+                    assert_ne!(a.wire_id, b.wire_id, "{}", profile.name());
+                    assert_ne!(a.display_zone, b.display_zone, "{}", profile.name());
+                    assert!(
+                        (a.panel, a.row, a.column) != (b.panel, b.row, b.column),
+                        "{}",
+                        profile.name()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn akp153_has_a_third_row_of_keys() {
+        let layout = Akp153.zone_layout(DisplayZones::Button13).unwrap();
+
+        assert_eq!((layout.row, layout.column, layout.wire_id), (2, 2, 13));
+        assert_eq!(layout.resolution, AKP153_BUTTON_RESOLUTION);
+        assert!(Akp153.zone_layout(DisplayZones::Touchscreen1).is_none());
+    }
+
+    #[test]
+    fn akp153_reports_match_the_button_reports() {
+        assert_eq!(AKP153_REPORTS.len(), 30);
+        assert_eq!(
+            AKP153_REPORTS[0],
+            (
+                buttons::BUTTON_1_PRESSED,
+                Button(ButtonActions::Button1Pressed)
+            )
+        );
+        assert_eq!(
+            AKP153_REPORTS[29],
+            (
+                buttons::BUTTON_15_RELEASED,
+                Button(ButtonActions::Button15Released)
+            )
+        );
+        for (report, action) in AKP153_REPORTS {
+            let Button(action) = action else {
+                panic!("{:?} is not a button", action);
+            };
+            let (button, pressed) = action.button();
+            assert_eq!((report[9], report[10] == 1), (button, pressed));
+        }
+    }
+
+    #[test]
+    fn inputs_are_decoded_with_the_model_reports() {
+        assert_eq!(
+            Akp153.decode_input(buttons::BUTTON_12_RELEASED),
+            Button(ButtonActions::Button12Released)
+        );
+        // The AKP05 has no 12th button and the AKP153 has no knobs
+        assert!(matches!(
+            Akp05.decode_input(buttons::BUTTON_12_RELEASED),
+            InputActions::Raw(_)
+        ));
+        assert!(matches!(
+            Akp153.decode_input(KNOB_1_CLOCKWISE),
+            InputActions::Raw(_)
+        ));
+    }
+}
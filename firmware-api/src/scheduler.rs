@@ -0,0 +1,403 @@
+//! Queues display writes by priority and paces them to what the device tolerates.
+//!
+//! Writing to the `Device` directly sends everything straight away, in whatever order the
+//! calls happen to be made. Widgets that redraw every second can then hold up the feedback
+//! to a key press, and long bursts of images can wedge the device. A `WriteScheduler`
+//! queues the writes instead:
+//!
+//! - the write with the highest `Priority` goes first, writes of the same priority go in
+//!   the order they were queued
+//! - a newer write to a display zone replaces the one still queued for it (and keeps its
+//!   place in the queue), so only the newest image of a zone is ever sent
+//! - writes are spaced out so that no more than `max_bytes_per_second` are sent
+//!
+//! The writes are sent from `WriteScheduler::tick`, see the
+//! [tick-driven helpers](crate#tick-driven-helpers).
+//!
+//! ```no_run
+//! # use firmware_api::device::{Device, FunctionHandler, HidDeviceWrapper};
+//! # use firmware_api::display_zones::DisplayZones;
+//! # use firmware_api::scheduler::{Priority, WriteScheduler};
+//! # use std::time::Instant;
+//! # fn run(device: Device<HidDeviceWrapper, FunctionHandler>, clock: Vec<u8>) {
+//! let mut scheduler = WriteScheduler::default();
+//! scheduler.queue_display_zone_image(DisplayZones::Button5, clock, Priority::Image);
+//! scheduler.queue_brightness(70);
+//!
+//! loop {
+//!     device.read_input().ok();
+//!     scheduler.tick(&device, Instant::now()).ok();
+//! }
+//! # }
+//! ```
+use crate::commands::output_buffer::{BUFFER_SIZE_513, BUFFER_SIZE_1025};
+use crate::common::IMAGE_DATA_PACKET_LENGTH;
+use crate::device::{Device, HidDeviceOperations, InputHandler};
+use crate::display_zones::DisplayZones;
+use crate::error::Result;
+use crate::transfer::CancellationToken;
+use std::time::{Duration, Instant};
+
+/// An estimate of what the AKP05 keeps up with while other devices share the USB bus, it
+/// has not been measured against the limit of the device
+pub const DEFAULT_MAX_BYTES_PER_SECOND: usize = 256 * 1024;
+
+/// How urgently a write has to reach the device, from most to least urgent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Feedback to an input, e.g. the image of a key changing while it is pressed
+    Input,
+    Brightness,
+    /// Anything else shown on the display zones, e.g. widgets that redraw periodically
+    Image,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Write {
+    DisplayZoneImage {
+        display_zone: DisplayZones,
+        image: Vec<u8>,
+    },
+    ClearDisplayZoneImage(DisplayZones),
+    Brightness(u8),
+}
+
+impl Write {
+    /// The zone the write is for, `None` for writes not tied to a zone
+    fn display_zone(&self) -> Option<DisplayZones> {
+        match self {
+            Write::DisplayZoneImage { display_zone, .. } => Some(*display_zone),
+            Write::ClearDisplayZoneImage(display_zone) => Some(*display_zone),
+            Write::Brightness(_) => None,
+        }
+    }
+
+    fn replaces(&self, queued: &Write) -> bool {
+        match (self, queued) {
+            (Write::Brightness(_), Write::Brightness(_)) => true,
+            _ => self.display_zone().is_some() && self.display_zone() == queued.display_zone(),
+        }
+    }
+}
+
+struct QueuedWrite {
+    priority: Priority,
+    write: Write,
+}
+
+/// Queued display writes, see the module documentation
+pub struct WriteScheduler {
+    max_bytes_per_second: usize,
+    /// In the order queued
+    queue: Vec<QueuedWrite>,
+    /// When the device is ready for the next write, `None` until something was written
+    next_write: Option<Instant>,
+}
+
+impl Default for WriteScheduler {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BYTES_PER_SECOND)
+    }
+}
+
+impl WriteScheduler {
+    /// * `max_bytes_per_second` - the most that is written to the device per second
+    pub fn new(max_bytes_per_second: usize) -> Self {
+        Self {
+            max_bytes_per_second: max_bytes_per_second.max(1),
+            queue: Vec::new(),
+            next_write: None,
+        }
+    }
+
+    /// Queues an image for a display zone (any supported format and size, it is converted
+    /// when sent), replacing whatever is still queued for the zone
+    pub fn queue_display_zone_image(
+        &mut self,
+        display_zone: DisplayZones,
+        image: impl Into<Vec<u8>>,
+        priority: Priority,
+    ) {
+        self.queue(
+            Write::DisplayZoneImage {
+                display_zone,
+                image: image.into(),
+            },
+            priority,
+        );
+    }
+
+    /// Queues clearing a display zone, replacing whatever is still queued for the zone
+    pub fn queue_clear_display_zone_image(
+        &mut self,
+        display_zone: DisplayZones,
+        priority: Priority,
+    ) {
+        self.queue(Write::ClearDisplayZoneImage(display_zone), priority);
+    }
+
+    /// Queues a brightness change, replacing one that is still queued
+    pub fn queue_brightness(&mut self, brightness: u8) {
+        self.queue(Write::Brightness(brightness), Priority::Brightness);
+    }
+
+    /// How many writes are waiting to be sent
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// When the next write can be sent, `None` when nothing is queued.
+    /// Useful to know how long the caller can sleep.
+    pub fn next_deadline(&self, now: Instant) -> Option<Instant> {
+        if self.queue.is_empty() {
+            return None;
+        }
+        Some(
+            self.next_write
+                .map_or(now, |next_write| next_write.max(now)),
+        )
+    }
+
+    /// Sends the most urgent write if the rate limit allows it at `now`, returns whether
+    /// one was sent. A write that fails is dropped.
+    pub fn tick<H: HidDeviceOperations, I: InputHandler>(
+        &mut self,
+        device: &Device<H, I>,
+        now: Instant,
+    ) -> Result<bool> {
+        if self.next_write.is_some_and(|next_write| now < next_write) {
+            return Ok(false);
+        }
+
+        // Earliest queued write among the most urgent ones
+        let Some(index) = self
+            .queue
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, queued)| queued.priority)
+            .map(|(index, _)| index)
+        else {
+            return Ok(false);
+        };
+        let queued = self.queue.remove(index);
+
+        let bytes_written = self.send(device, queued.write)?;
+        self.next_write = Some(now + self.time_to_write(bytes_written));
+        Ok(true)
+    }
+
+    fn queue(&mut self, write: Write, priority: Priority) {
+        match self
+            .queue
+            .iter_mut()
+            .find(|queued| write.replaces(&queued.write))
+        {
+            // Keeps its place so frequent updates cannot push it back indefinitely
+            Some(queued) => {
+                queued.write = write;
+                queued.priority = queued.priority.min(priority);
+            }
+            None => self.queue.push(QueuedWrite { priority, write }),
+        }
+    }
+
+    /// Returns the number of bytes the write took, frames included
+    fn send<H: HidDeviceOperations, I: InputHandler>(
+        &self,
+        device: &Device<H, I>,
+        write: Write,
+    ) -> Result<usize> {
+        match write {
+            Write::DisplayZoneImage {
+                display_zone,
+                image,
+            } => {
+                // The converted image is only known to the upload, which reports its size
+                let mut image_len = 0;
+                device.set_display_zone_image_with_progress(
+                    display_zone,
+                    image,
+                    |_, total| image_len = total,
+                    &CancellationToken::default(),
+                )?;
+
+                let packets = image_len.div_ceil(IMAGE_DATA_PACKET_LENGTH);
+                Ok((packets + 1) * BUFFER_SIZE_1025)
+            }
+            Write::ClearDisplayZoneImage(display_zone) => {
+                device.clear_display_zone_image(display_zone)?;
+                Ok(BUFFER_SIZE_513)
+            }
+            Write::Brightness(brightness) => {
+                device.set_brightness(brightness)?;
+                Ok(BUFFER_SIZE_513)
+            }
+        }
+    }
+
+    fn time_to_write(&self, bytes: usize) -> Duration {
+        Duration::from_secs_f64(bytes as f64 / self.max_bytes_per_second as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::{self, DecodedCommand};
+    use crate::device::FunctionHandler;
+    use crate::error::Error;
+    use crate::mock::MockHidDevice;
+    use image::{DynamicImage, ImageFormat, Rgb, RgbImage};
+    use std::io::Cursor;
+
+    fn encoded_image(colour: u8) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::from_pixel(112, 112, Rgb([colour, 0, 0])))
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    fn commands_sent(mock: &MockHidDevice) -> Vec<DecodedCommand> {
+        mock.written_frames()
+            .iter()
+            .map(|frame| decoder::decode(frame))
+            .filter(|command| !matches!(command, DecodedCommand::ImageDataPacket(_)))
+            .collect()
+    }
+
+    /// Ticks far enough apart for the rate limit not to matter
+    fn drain(scheduler: &mut WriteScheduler, device: &Device<&MockHidDevice, FunctionHandler>) {
+        let mut now = Instant::now();
+        while !scheduler.is_empty() {
+            scheduler.tick(device, now).unwrap();
+            now += Duration::from_secs(1);
+        }
+    }
+
+    #[test]
+    fn most_urgent_writes_go_first() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        let mut scheduler = WriteScheduler::default();
+
+        scheduler.queue_display_zone_image(
+            DisplayZones::Button1,
+            encoded_image(0),
+            Priority::Image,
+        );
+        scheduler.queue_brightness(50);
+        scheduler.queue_clear_display_zone_image(DisplayZones::Button2, Priority::Input);
+        drain(&mut scheduler, &device);
+
+        let commands = commands_sent(&mock);
+        assert_eq!(
+            commands[..2],
+            [
+                DecodedCommand::ClearDisplayZoneImage(DisplayZones::Button2),
+                DecodedCommand::SetBrightness(50),
+            ]
+        );
+        assert!(matches!(
+            commands[2],
+            DecodedCommand::InitiateSetDisplayZoneImage {
+                display_zone: DisplayZones::Button1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn only_the_newest_write_to_a_zone_is_sent() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        let mut scheduler = WriteScheduler::default();
+
+        scheduler.queue_display_zone_image(
+            DisplayZones::Button1,
+            encoded_image(0),
+            Priority::Image,
+        );
+        scheduler.queue_display_zone_image(
+            DisplayZones::Button2,
+            encoded_image(0),
+            Priority::Image,
+        );
+        scheduler.queue_display_zone_image(
+            DisplayZones::Button1,
+            encoded_image(255),
+            Priority::Image,
+        );
+        scheduler.queue_brightness(20);
+        scheduler.queue_brightness(80);
+        assert_eq!(scheduler.len(), 3);
+        drain(&mut scheduler, &device);
+
+        let commands = commands_sent(&mock);
+        assert_eq!(commands.len(), 3);
+        assert_eq!(commands[0], DecodedCommand::SetBrightness(80));
+        // Button 1 kept its place ahead of button 2
+        assert!(matches!(
+            commands[1],
+            DecodedCommand::InitiateSetDisplayZoneImage {
+                display_zone: DisplayZones::Button1,
+                ..
+            }
+        ));
+
+        let uploaded = image::load_from_memory(
+            &mock.assert_display_zone_image_uploaded(DisplayZones::Button1),
+        )
+        .unwrap()
+        .to_rgb8();
+        assert!(uploaded.get_pixel(56, 56)[0] > 200);
+    }
+
+    #[test]
+    fn writes_are_paced_by_the_rate_limit() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        // One simple command per second
+        let mut scheduler = WriteScheduler::new(BUFFER_SIZE_513);
+        let start = Instant::now();
+
+        scheduler.queue_clear_display_zone_image(DisplayZones::Button1, Priority::Image);
+        scheduler.queue_clear_display_zone_image(DisplayZones::Button2, Priority::Image);
+
+        assert!(scheduler.tick(&device, start).unwrap());
+        assert!(
+            !scheduler
+                .tick(&device, start + Duration::from_millis(500))
+                .unwrap()
+        );
+        assert_eq!(
+            scheduler.next_deadline(start),
+            Some(start + Duration::from_secs(1))
+        );
+        assert!(
+            scheduler
+                .tick(&device, start + Duration::from_secs(1))
+                .unwrap()
+        );
+        assert_eq!(scheduler.next_deadline(start), None);
+    }
+
+    #[test]
+    fn failed_writes_are_dropped() {
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, FunctionHandler::new(|_| {}));
+        let mut scheduler = WriteScheduler::default();
+
+        scheduler.queue_display_zone_image(DisplayZones::Button1, [1, 2, 3], Priority::Input);
+
+        assert!(matches!(
+            scheduler.tick(&device, Instant::now()),
+            Err(Error::InvalidImage(_))
+        ));
+        assert!(scheduler.is_empty());
+    }
+}
@@ -0,0 +1,366 @@
+//! Records the inputs of a device to a JSON-lines file and plays them back later, so a session
+//! from a real launchpad can be reproduced without one (e.g. in a regression test).
+//!
+//! Every line of the file is a `SessionEntry`: when the input was read relative to the first
+//! one, which device it came from and the report the device sent, along with the action it was
+//! decoded as for people reading the file.
+//!
+//! ```text
+//! {"elapsed_us":0,"sequence":0,"serial_number":"AKP05-LEFT","model":"AKP05","report":[65,67,75,0,0,79,75,0,0,145,0,0,0],"action":"Knob(Knob3Clockwise)"}
+//! {"elapsed_us":8120,"sequence":1,"serial_number":"AKP05-LEFT","model":"AKP05","report":[65,67,75,0,0,79,75,0,0,145,0,0,0],"action":"Knob(Knob3Clockwise)"}
+//! ```
+//!
+//! A `SessionRecorder` is an `InputHandler` that records every event before passing it on. A
+//! `Session` read back from the file can be queued on a `MockHidDevice`, for a `Device` to read
+//! the reports like it would from the launchpad, or handed straight to an `InputHandler`.
+//! Neither waits between the inputs. Only `Session::replay` (and `Session::events`) keep the
+//! recorded spacing in `read_at`, a `Device` times the queued reports when it reads them.
+//!
+//! ```no_run
+//! # use firmware_api::device::{Device, FunctionHandler};
+//! # use firmware_api::mock::MockHidDevice;
+//! # use firmware_api::session::Session;
+//! let session = Session::open("knob-3-skips.jsonl").unwrap();
+//!
+//! let mock = MockHidDevice::new();
+//! let device = Device::new(&mock, FunctionHandler::new(|action| println!("{:?}", action)));
+//! session.queue_on(&mock);
+//! for _ in session.entries() {
+//!     device.read_input().unwrap();
+//! }
+//! ```
+use crate::common::ByteArray;
+use crate::device::InputHandler;
+use crate::inputs::input_buffer::BUFFER_SIZE_13;
+use crate::inputs::{DeviceIdentity, InputActions, InputEvent};
+use crate::mock::MockHidDevice;
+use crate::profiles::find_profile_by_name;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A line of a session file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionEntry {
+    /// Microseconds since the first input of the session was read
+    pub elapsed_us: u64,
+    /// See `InputEvent::sequence`
+    pub sequence: u64,
+    pub serial_number: String,
+    pub model: String,
+    pub report: ByteArray<BUFFER_SIZE_13>,
+    /// What `report` was decoded as while recording, playing back decodes it again
+    #[serde(default)]
+    pub action: String,
+}
+
+impl SessionEntry {
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_micros(self.elapsed_us)
+    }
+
+    /// Decodes the report the way the model it was recorded from does (unknown models as
+    /// an AKP05)
+    pub fn action(&self) -> InputActions {
+        match find_profile_by_name(&self.model) {
+            Some(profile) => profile.decode_input(self.report),
+            None => InputActions::from(self.report),
+        }
+    }
+}
+
+struct RecorderState<W: Write> {
+    writer: W,
+    started: Option<Instant>,
+    /// The first write that failed, see `SessionRecorder::flush`
+    error: Option<Error>,
+}
+
+/// Writes every event to a session file before passing it on to the handler it wraps
+pub struct SessionRecorder<W: Write, I: InputHandler> {
+    state: Mutex<RecorderState<W>>,
+    handler: I,
+}
+
+impl<I: InputHandler> SessionRecorder<File, I> {
+    /// Records to a new file at `path`, replacing any file that is already there
+    pub fn create(path: impl AsRef<Path>, handler: I) -> std::io::Result<Self> {
+        Ok(Self::new(File::create(path)?, handler))
+    }
+}
+
+impl<W: Write, I: InputHandler> SessionRecorder<W, I> {
+    pub fn new(writer: W, handler: I) -> Self {
+        Self {
+            state: Mutex::new(RecorderState {
+                writer,
+                started: None,
+                error: None,
+            }),
+            handler,
+        }
+    }
+
+    pub fn handler(&self) -> &I {
+        &self.handler
+    }
+
+    /// Writes `event` as a line of the session, timed relative to the first event recorded
+    pub fn record(&self, event: &InputEvent) -> std::io::Result<()> {
+        let mut state = self.state.lock().map_err(|_| poisoned())?;
+        let started = *state.started.get_or_insert(event.read_at);
+
+        let entry = SessionEntry {
+            elapsed_us: event.read_at.saturating_duration_since(started).as_micros() as u64,
+            sequence: event.sequence,
+            serial_number: event.device.serial_number.to_string(),
+            model: event.device.model.to_string(),
+            report: event.report,
+            action: format!("{:?}", event.action),
+        };
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+
+        // Flushed straight away, the session is usually wanted because something went wrong
+        state.writer.write_all(&line)?;
+        state.writer.flush()
+    }
+
+    /// Flushes the file, failing with the first error any of the events ran into while being
+    /// recorded (`handle_event` has no way to report them)
+    pub fn flush(&self) -> std::io::Result<()> {
+        let mut state = self.state.lock().map_err(|_| poisoned())?;
+        if let Some(error) = state.error.take() {
+            return Err(error);
+        }
+
+        state.writer.flush()
+    }
+}
+
+impl<W: Write, I: InputHandler> InputHandler for SessionRecorder<W, I> {
+    /// Actions without an event cannot be recorded, they are only passed on
+    fn handle(&self, action: InputActions) {
+        self.handler.handle(action);
+    }
+
+    fn handle_event(&self, event: InputEvent) {
+        if let Err(error) = self.record(&event)
+            && let Ok(mut state) = self.state.lock()
+        {
+            state.error.get_or_insert(error);
+        }
+
+        self.handler.handle_event(event);
+    }
+}
+
+fn poisoned() -> Error {
+    Error::other("a recording panicked")
+}
+
+/// A recorded session, see the module documentation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Session {
+    entries: Vec<SessionEntry>,
+}
+
+impl Session {
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a session file, blank lines are skipped
+    pub fn read(reader: impl BufRead) -> std::io::Result<Self> {
+        let mut entries = Vec::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let entry = serde_json::from_str(&line).map_err(|e| {
+                Error::new(ErrorKind::InvalidData, format!("line {}: {}", index + 1, e))
+            })?;
+            entries.push(entry);
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn entries(&self) -> &[SessionEntry] {
+        &self.entries
+    }
+
+    /// Queues every report on `mock`, a `Device` built on it reads one per `read_input`.
+    /// The events are timed by the `Device`, use `replay` to keep the recorded timing.
+    pub fn queue_on(&self, mock: &MockHidDevice) {
+        for entry in &self.entries {
+            mock.queue_input(entry.report);
+        }
+    }
+
+    /// The recorded inputs as events, read `start` plus however long after the first input
+    /// they were recorded
+    pub fn events(&self, start: Instant) -> impl Iterator<Item = InputEvent> + '_ {
+        self.entries.iter().map(move |entry| InputEvent {
+            action: entry.action(),
+            read_at: start + entry.elapsed(),
+            sequence: entry.sequence,
+            device: DeviceIdentity {
+                serial_number: Arc::from(entry.serial_number.as_str()),
+                model: find_profile_by_name(&entry.model).map_or("", |profile| profile.name()),
+            },
+            report: entry.report,
+        })
+    }
+
+    /// Hands every input to `handler` straight away, see `events`
+    pub fn replay(&self, handler: &impl InputHandler, start: Instant) {
+        for event in self.events(start) {
+            handler.handle_event(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::{ChannelHandler, Device};
+    use crate::inputs::buttons::{BUTTON_3_PRESSED, BUTTON_3_RELEASED, ButtonActions};
+    use crate::inputs::knobs::{KNOB_3_CLOCKWISE, KnobActions};
+    use std::io::Cursor;
+
+    /// A writer the test can still read after handing it to the recorder
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn recorded_session() -> Session {
+        let buffer = SharedBuffer::default();
+        let (handler, _actions) = ChannelHandler::channel();
+        let mock = MockHidDevice::new();
+        let mut device = Device::new(&mock, SessionRecorder::new(buffer.clone(), handler));
+        device.set_serial_number("AKP05-LEFT");
+
+        for report in [KNOB_3_CLOCKWISE, BUTTON_3_PRESSED, BUTTON_3_RELEASED] {
+            mock.queue_input(report);
+            device.read_input().unwrap();
+        }
+        device.handler().flush().unwrap();
+
+        let recorded = buffer.0.lock().unwrap().clone();
+        Session::read(Cursor::new(recorded)).unwrap()
+    }
+
+    #[test]
+    fn every_input_is_recorded_on_its_own_line() {
+        let session = recorded_session();
+        let entries = session.entries();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].elapsed_us, 0);
+        assert!(entries[1].elapsed() <= entries[2].elapsed());
+        assert_eq!(entries[2].sequence, 2);
+        assert_eq!(entries[0].serial_number, "AKP05-LEFT");
+        assert_eq!(entries[0].report, KNOB_3_CLOCKWISE);
+        assert_eq!(entries[0].action, "Knob(Knob3Clockwise)");
+    }
+
+    #[test]
+    fn sessions_play_back_through_a_mock_device() {
+        let session = recorded_session();
+        let (handler, actions) = ChannelHandler::channel();
+        let mock = MockHidDevice::new();
+        let device = Device::new(&mock, handler);
+
+        session.queue_on(&mock);
+        for _ in session.entries() {
+            device.read_input().unwrap();
+        }
+
+        assert_eq!(
+            actions
+                .try_iter()
+                .map(|event| event.action)
+                .collect::<Vec<_>>(),
+            [
+                InputActions::Knob(KnobActions::Knob3Clockwise),
+                InputActions::Button(ButtonActions::Button3Pressed),
+                InputActions::Button(ButtonActions::Button3Released),
+            ]
+        );
+    }
+
+    #[test]
+    fn sessions_play_back_with_their_timing() {
+        let session = Session::read(Cursor::new(
+            "{\"elapsed_us\":0,\"sequence\":7,\"serial_number\":\"\",\"model\":\"AKP05\",\"report\":[65,67,75,0,0,79,75,0,0,3,1,0,0]}\n\
+             \n\
+             {\"elapsed_us\":1500,\"sequence\":8,\"serial_number\":\"\",\"model\":\"AKP05\",\"report\":[65,67,75,0,0,79,75,0,0,3,0,0,0]}\n",
+        ))
+        .unwrap();
+        let start = Instant::now();
+
+        let events: Vec<InputEvent> = session.events(start).collect();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[1].read_at - events[0].read_at,
+            Duration::from_micros(1500)
+        );
+        assert_eq!(events[0].device.model, "AKP05");
+        assert_eq!(
+            events[1].action,
+            InputActions::Button(ButtonActions::Button3Released)
+        );
+    }
+
+    #[test]
+    fn replayed_events_keep_their_spacing() {
+        let session = Session::read(Cursor::new(
+            "{\"elapsed_us\":0,\"sequence\":0,\"serial_number\":\"AKP05-LEFT\",\"model\":\"AKP05\",\"report\":[65,67,75,0,0,79,75,0,0,145,0,0,0]}\n\
+             {\"elapsed_us\":8120,\"sequence\":1,\"serial_number\":\"AKP05-LEFT\",\"model\":\"AKP05\",\"report\":[65,67,75,0,0,79,75,0,0,145,0,0,0]}\n\
+             {\"elapsed_us\":250000,\"sequence\":2,\"serial_number\":\"AKP05-LEFT\",\"model\":\"AKP05\",\"report\":[65,67,75,0,0,79,75,0,0,3,1,0,0]}\n",
+        ))
+        .unwrap();
+        let (handler, events) = ChannelHandler::channel();
+        let start = Instant::now();
+
+        session.replay(&handler, start);
+
+        let read_at: Vec<Duration> = events
+            .try_iter()
+            .map(|event| event.read_at - start)
+            .collect();
+        assert_eq!(
+            read_at,
+            [
+                Duration::ZERO,
+                Duration::from_micros(8120),
+                Duration::from_micros(250_000)
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_lines_are_reported() {
+        let error = Session::read(Cursor::new("\n{\"elapsed_us\":\"soon\"}\n")).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("line 2:"), "{}", error);
+    }
+}
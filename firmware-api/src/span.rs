@@ -0,0 +1,237 @@
+//! Spreads one image across several display zones, as if they were a single screen.
+//!
+//! The zones of a panel are laid out on a virtual canvas following their row and column
+//! in the `DeviceProfile`, with some space between neighbouring zones for the physical
+//! gaps between the keys. The image is fitted to the whole canvas and each zone gets the
+//! part of it that lies underneath, what falls in the gaps is not shown anywhere (just
+//! like the frame between the keys hides it).
+//!
+//! When several panels are spanned they are stacked top to bottom in the order given,
+//! centred horizontally. On the AKP05 that is the button grid above the touchscreen strip.
+//!
+//! ```no_run
+//! # use firmware_api::device::{Device, FunctionHandler, HidDeviceWrapper};
+//! # use firmware_api::profiles::Panel;
+//! # use firmware_api::span::SpanLayout;
+//! # fn run(device: Device<HidDeviceWrapper, FunctionHandler>) {
+//! let wallpaper = std::fs::read("wallpaper.png").unwrap();
+//! device
+//!     .set_spanned_image(
+//!         &[Panel::Buttons, Panel::Touchscreen],
+//!         wallpaper,
+//!         &SpanLayout::default(),
+//!     )
+//!     .unwrap();
+//! # }
+//! ```
+use crate::display_zones::DisplayZones;
+use crate::image_processing::{Resolution, ScalingMode, encode_jpeg, fit_image};
+use crate::profiles::{Panel, ZoneLayout};
+use image::{DynamicImage, ImageResult};
+
+/// Space left between neighbouring zones, in pixels of the zones they separate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpanLayout {
+    /// Between two keys (or touchscreen zones) of the same panel
+    pub zone_gap: u32,
+    /// Between two panels, e.g. the bottom row of keys and the touchscreen strip
+    pub panel_gap: u32,
+}
+
+/// An estimate of the frame between the keys of an AKP05 relative to the key size, adjust to taste
+impl Default for SpanLayout {
+    fn default() -> Self {
+        Self {
+            zone_gap: 24,
+            panel_gap: 48,
+        }
+    }
+}
+
+/// Where a panel ends up on the canvas
+struct PlacedPanel<'a> {
+    zones: Vec<&'a ZoneLayout>,
+    /// Every zone of a panel has the same resolution
+    cell: Resolution,
+    x: u32,
+    y: u32,
+}
+
+/// Slices `image` across the zones of `panels`, returning a JPEG for each zone
+///
+/// * `zones` - every zone of the model, see `DeviceProfile::display_zones`
+/// * `panels` - the panels to span, from top to bottom. Panels without zones are skipped.
+/// * `scaling_mode` - how `image` is fitted to the canvas the zones make up
+pub fn span_image(
+    image: &DynamicImage,
+    zones: &[ZoneLayout],
+    panels: &[Panel],
+    layout: &SpanLayout,
+    scaling_mode: ScalingMode,
+) -> ImageResult<Vec<(DisplayZones, Vec<u8>)>> {
+    let (placed, canvas) = place_panels(zones, panels, layout);
+    if placed.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let canvas = fit_image(image, canvas, scaling_mode);
+
+    let mut slices = Vec::new();
+    for panel in &placed {
+        for zone in &panel.zones {
+            let x = panel.x + u32::from(zone.column) * (panel.cell.width + layout.zone_gap);
+            let y = panel.y + u32::from(zone.row) * (panel.cell.height + layout.zone_gap);
+            let slice = canvas.crop_imm(x, y, panel.cell.width, panel.cell.height);
+
+            slices.push((zone.display_zone, encode_jpeg(&slice)?));
+        }
+    }
+
+    Ok(slices)
+}
+
+/// Lays the panels out on the canvas, returning them along with the size of the canvas
+fn place_panels<'a>(
+    zones: &'a [ZoneLayout],
+    panels: &[Panel],
+    layout: &SpanLayout,
+) -> (Vec<PlacedPanel<'a>>, Resolution) {
+    let mut placed = Vec::new();
+    let mut sizes = Vec::new();
+
+    for panel in panels {
+        let panel_zones: Vec<&ZoneLayout> =
+            zones.iter().filter(|zone| zone.panel == *panel).collect();
+        let Some(first) = panel_zones.first() else {
+            continue;
+        };
+
+        let columns = panel_zones
+            .iter()
+            .map(|zone| u32::from(zone.column))
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let rows = panel_zones
+            .iter()
+            .map(|zone| u32::from(zone.row))
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let cell = first.resolution;
+
+        sizes.push(Resolution::new(
+            columns * cell.width + (columns - 1) * layout.zone_gap,
+            rows * cell.height + (rows - 1) * layout.zone_gap,
+        ));
+        placed.push(PlacedPanel {
+            zones: panel_zones,
+            cell,
+            x: 0,
+            y: 0,
+        });
+    }
+
+    let width = sizes.iter().map(|size| size.width).max().unwrap_or(0);
+    let mut y = 0;
+    for (panel, size) in placed.iter_mut().zip(&sizes) {
+        panel.x = (width - size.width) / 2;
+        panel.y = y;
+        y += size.height + layout.panel_gap;
+    }
+    let height = y.saturating_sub(layout.panel_gap);
+
+    (placed, Resolution::new(width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiles::{Akp05, DeviceProfile};
+    use image::{GenericImageView, Rgb, RgbImage};
+
+    const LAYOUT: SpanLayout = SpanLayout {
+        zone_gap: 10,
+        panel_gap: 20,
+    };
+
+    fn brightest_channel(jpeg: &[u8]) -> u8 {
+        let image = image::load_from_memory(jpeg).unwrap().to_rgb8();
+        image.pixels().flat_map(|pixel| pixel.0).max().unwrap()
+    }
+
+    #[test]
+    fn canvas_includes_the_gaps_between_keys() {
+        let (_, canvas) = place_panels(Akp05.display_zones(), &[Panel::Buttons], &LAYOUT);
+        assert_eq!(canvas, Resolution::new(5 * 112 + 4 * 10, 2 * 112 + 10));
+
+        let (placed, canvas) = place_panels(
+            Akp05.display_zones(),
+            &[Panel::Buttons, Panel::Touchscreen],
+            &LAYOUT,
+        );
+        assert_eq!(canvas, Resolution::new(4 * 176 + 3 * 10, 234 + 20 + 112));
+        // The narrower button grid is centred above the touchscreen strip
+        assert_eq!((placed[0].x, placed[1].y), ((734 - 600) / 2, 254));
+    }
+
+    #[test]
+    fn what_falls_in_the_gaps_is_not_shown() {
+        // White only where the gap between the first two columns of keys is
+        let mut image = RgbImage::new(600, 234);
+        for (x, _, pixel) in image.enumerate_pixels_mut() {
+            if (112..122).contains(&x) {
+                *pixel = Rgb([255, 255, 255]);
+            }
+        }
+
+        let slices = span_image(
+            &DynamicImage::ImageRgb8(image),
+            Akp05.display_zones(),
+            &[Panel::Buttons],
+            &LAYOUT,
+            ScalingMode::Stretch,
+        )
+        .unwrap();
+
+        assert_eq!(slices.len(), 10);
+        for (display_zone, jpeg) in &slices {
+            assert!(
+                brightest_channel(jpeg) < 64,
+                "{:?} shows the gap",
+                display_zone
+            );
+        }
+    }
+
+    #[test]
+    fn every_zone_gets_its_own_part_of_the_image() {
+        // Left half red, right half blue
+        let image = RgbImage::from_fn(734, 366, |x, _| match x < 367 {
+            true => Rgb([255, 0, 0]),
+            false => Rgb([0, 0, 255]),
+        });
+
+        let slices = span_image(
+            &DynamicImage::ImageRgb8(image),
+            Akp05.display_zones(),
+            &[Panel::Buttons, Panel::Touchscreen],
+            &LAYOUT,
+            ScalingMode::Stretch,
+        )
+        .unwrap();
+        let slice = |display_zone| {
+            let (_, jpeg) = slices
+                .iter()
+                .find(|(zone, _)| *zone == display_zone)
+                .unwrap();
+            image::load_from_memory(jpeg).unwrap()
+        };
+
+        assert_eq!(slices.len(), 14);
+        assert!(slice(DisplayZones::Button6).get_pixel(56, 56)[0] > 200);
+        assert!(slice(DisplayZones::Button5).get_pixel(56, 56)[2] > 200);
+        assert_eq!(slice(DisplayZones::Touchscreen4).dimensions(), (176, 112));
+        assert!(slice(DisplayZones::Touchscreen4).get_pixel(88, 56)[2] > 200);
+    }
+}
@@ -0,0 +1,171 @@
+//! How image uploads are confirmed with the device, and how callers can follow (or abort) them.
+//!
+//! The frames of an image are written as fast as the device accepts them. A frame that
+//! could not be written (or only in part) would leave a half drawn zone behind, so the whole
+//! image is sent again instead, up to `DEFAULT_TRANSFER_RETRIES` times.
+//!
+//! Nothing confirms the device received frames that were written though. The experimental
+//! `acknowledged-transfer` feature adds `TransferMode::Acknowledged`, where the device has to
+//! acknowledge every image. Its `ACKNOWLEDGEMENT` report is assumed from the input report
+//! header and has not been confirmed against a USB capture of a launchpad, a device that
+//! never sends it fails every upload once the retries are used up.
+//!
+//! Large images (like a background) take a while to send. The `*_with_progress` upload
+//! methods of the `Device` report every packet to a `TransferObserver` and stop between
+//! packets once their `CancellationToken` is cancelled.
+//!
+//! ```no_run
+//! # use firmware_api::device::{Device, FunctionHandler, HidDeviceWrapper};
+//! # use firmware_api::transfer::TransferMode;
+//! # fn run(mut device: Device<HidDeviceWrapper, FunctionHandler>) {
+//! device.set_transfer_mode(TransferMode::Unacknowledged { retries: 0 });
+//! # }
+//! ```
+#[cfg(feature = "acknowledged-transfer")]
+use crate::common::ByteArray;
+#[cfg(feature = "acknowledged-transfer")]
+use crate::inputs::InputReport;
+#[cfg(feature = "acknowledged-transfer")]
+use crate::inputs::input_buffer::BUFFER_SIZE_13;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "acknowledged-transfer")]
+use std::time::Duration;
+
+/// What the device is assumed to answer once it received a whole image: the input report
+/// header without an input code. Not confirmed by a capture yet, see the module documentation.
+#[cfg(feature = "acknowledged-transfer")]
+pub const ACKNOWLEDGEMENT: ByteArray<BUFFER_SIZE_13> = *b"ACK\0\0OK\0\0\0\0\0\0";
+
+/// How many times an image is sent again before giving up
+pub const DEFAULT_TRANSFER_RETRIES: u32 = 2;
+
+/// How long `TransferMode::acknowledged` waits for the device to acknowledge an image
+#[cfg(feature = "acknowledged-transfer")]
+pub const DEFAULT_ACKNOWLEDGEMENT_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferMode {
+    /// Images are written without waiting for the device, an image whose frames could not
+    /// all be written is sent again up to `retries` times
+    Unacknowledged { retries: u32 },
+    /// Every image has to be acknowledged within `timeout`, failed images are sent
+    /// again up to `retries` times.
+    ///
+    /// Experimental until a capture confirms the `ACKNOWLEDGEMENT` report.
+    #[cfg(feature = "acknowledged-transfer")]
+    Acknowledged { retries: u32, timeout: Duration },
+}
+
+impl Default for TransferMode {
+    fn default() -> Self {
+        Self::Unacknowledged {
+            retries: DEFAULT_TRANSFER_RETRIES,
+        }
+    }
+}
+
+impl TransferMode {
+    /// Acknowledged transfers with the default retries and timeout, experimental like
+    /// `TransferMode::Acknowledged`
+    #[cfg(feature = "acknowledged-transfer")]
+    pub fn acknowledged() -> Self {
+        Self::Acknowledged {
+            retries: DEFAULT_TRANSFER_RETRIES,
+            timeout: DEFAULT_ACKNOWLEDGEMENT_TIMEOUT,
+        }
+    }
+}
+
+/// The part of an image transfer that failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferStage {
+    /// The frame announcing the image and its size
+    Initiate,
+    /// A data packet, counted from 0
+    Chunk(usize),
+    /// The device did not acknowledge the image
+    #[cfg(feature = "acknowledged-transfer")]
+    Acknowledgement,
+}
+
+impl fmt::Display for TransferStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferStage::Initiate => write!(f, "the initiating frame"),
+            TransferStage::Chunk(index) => write!(f, "chunk {}", index),
+            #[cfg(feature = "acknowledged-transfer")]
+            TransferStage::Acknowledgement => write!(f, "the acknowledgement"),
+        }
+    }
+}
+
+/// Follows the progress of an image upload
+pub trait TransferObserver {
+    /// Called after every data packet with the number of image bytes sent so far out of
+    /// `total`. An image that is sent again starts over from 0.
+    fn on_progress(&mut self, sent: usize, total: usize);
+}
+
+impl<F: FnMut(usize, usize)> TransferObserver for F {
+    fn on_progress(&mut self, sent: usize, total: usize) {
+        self(sent, total);
+    }
+}
+
+/// Aborts an upload from another thread (or from a `TransferObserver`), clones share the
+/// same state.
+///
+/// Whatever the device already received stays on the display, clear the zone if a partial
+/// image is not acceptable.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes every upload using this token stop before its next packet
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Input reports share the header, but always carry a code
+#[cfg(feature = "acknowledged-transfer")]
+pub(crate) fn is_acknowledgement(report: &[u8]) -> bool {
+    InputReport::parse(report).is_some_and(|report| report.code == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "acknowledged-transfer")]
+    #[test]
+    fn only_reports_without_a_code_are_acknowledgements() {
+        assert!(is_acknowledgement(&ACKNOWLEDGEMENT));
+        assert!(!is_acknowledgement(
+            &crate::inputs::buttons::BUTTON_3_PRESSED
+        ));
+        assert!(!is_acknowledgement(&[0; BUFFER_SIZE_13]));
+    }
+
+    #[test]
+    fn cancelling_is_shared_between_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+
+        clone.cancel();
+
+        assert!(token.is_cancelled());
+    }
+}
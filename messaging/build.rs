@@ -11,6 +11,7 @@ fn main() {
         .includes(["protobufs"])
         // Inputs must reside in some of include paths.
         .input("protobufs/top_level.proto")
+        .input("protobufs/server_message.proto")
         .input("protobufs/commands/key_config.proto")
         .input("protobufs/commands/boot_logo.proto")
         .input("protobufs/commands/brightness.proto")
@@ -18,6 +19,7 @@ fn main() {
         .input("protobufs/commands/common/keys.proto")
         .input("protobufs/commands/common/inputs.proto")
         .input("protobufs/commands/common/display_zones.proto")
+        .input("protobufs/events/upload_progress.proto")
         // Specify output directory relative to Cargo output directory.
         .out_dir("./src/protos")
         .customize(Customize::tokio_bytes(Customize::default(), true))
@@ -1,20 +1,23 @@
-use crate::protos::boot_logo::SetBootLogo;
-use crate::protos::brightness::SetBrightness;
+use crate::protos::boot_logo::{CancelUpload, SetBootLogo};
+use crate::protos::brightness::{Easing, FadeBrightness, SetBrightness};
 use crate::protos::display_zone_image::{
     ClearAllDisplayZoneImages, ClearDisplayZoneImage, SetDisplayZoneImage,
 };
 use crate::protos::display_zones::DisplayZone;
 use crate::protos::inputs::InputId;
-use crate::protos::key_config::{Action, KeyConfig};
+use crate::protos::key_config::{Action, KeyConfig, SetLongPress};
+use crate::protos::server_message::ServerMessage;
 use crate::protos::top_level::TopLevel;
 use crate::protos::top_level::top_level::Command;
 use crate::socket;
-use crate::socket::MessageSender;
+use crate::socket::{MessageReceiver, MessageSender};
 use protobuf::{EnumOrUnknown, Message};
 use std::io::Error;
+use std::time::Duration;
 
 pub struct ClientWrapper {
     client: socket::Client,
+    device_serial: String,
 }
 
 /// This trait defines all known operations.
@@ -25,16 +28,37 @@ pub trait ClientCommands {
     /// * `actions` - the sequence of actions that should occur when input happens
     fn send_key_config(&mut self, input_id: InputId, actions: Vec<Action>) -> Result<(), Error>;
 
+    /// Sets how long a button has to be held for its long press (and hold repeat) gestures
+    ///
+    /// * `duration` - how long the button is held, more than 0
+    fn set_long_press(&mut self, duration: Duration) -> Result<(), Error>;
+
     /// Sets the boot logo image that displays when the device starts up
     ///
     /// * `image_path` - path to the image file to use as the boot logo, **it should be an absolute path**
     fn set_boot_logo(&mut self, image_path: String) -> Result<(), Error>;
 
+    /// Stops the boot logo upload that is going on, if there is one. Part of the image may have
+    /// been sent already.
+    fn cancel_upload(&mut self) -> Result<(), Error>;
+
     /// Adjusts the overall brightness of the device display
     ///
     /// * `brightness_percentage` - brightness level as a percentage (0-100)
     fn set_brightness(&mut self, brightness_percentage: u8) -> Result<(), Error>;
 
+    /// Moves the brightness of the device display gradually to a new level
+    ///
+    /// * `brightness_percentage` - brightness level to end at as a percentage (0-100)
+    /// * `duration` - how long it takes to get there
+    /// * `easing` - how the brightness moves along the way
+    fn fade_brightness(
+        &mut self,
+        brightness_percentage: u8,
+        duration: Duration,
+        easing: Easing,
+    ) -> Result<(), Error>;
+
     /// Sets an image for a specific display zone on the device
     ///
     /// * `display_zone` - the specific area/zone of the display to update
@@ -59,7 +83,30 @@ pub trait ClientCommands {
 /// To be used by any client that wants to communicate with the server
 impl ClientWrapper {
     pub fn new(client: socket::Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            device_serial: String::new(),
+        }
+    }
+
+    /// Sends every following command to the launchpad with the given serial number only,
+    /// `None` (the default) targets every launchpad
+    pub fn select_device(&mut self, device_serial: Option<String>) {
+        self.device_serial = device_serial.unwrap_or_default();
+    }
+
+    /// Blocks until the server sends a message, e.g. the progress of an upload this client asked for
+    pub fn read_server_message(&mut self) -> Result<ServerMessage, Error> {
+        let message = self.client.read_message()?;
+        Ok(ServerMessage::parse_from_bytes(message.as_slice())?)
+    }
+
+    fn create_command(&self, command: Command) -> TopLevel {
+        TopLevel {
+            command: Some(command),
+            device_serial: self.device_serial.clone(),
+            ..TopLevel::default()
+        }
     }
 }
 
@@ -71,15 +118,26 @@ impl ClientCommands for ClientWrapper {
             ..KeyConfig::default()
         };
         self.client.send_message(
-            create_command(Command::KeyConfigCommand(protobuf))
+            self.create_command(Command::KeyConfigCommand(protobuf))
                 .write_to_bytes()?
                 .as_slice(),
         )
     }
 
+    fn set_long_press(&mut self, duration: Duration) -> Result<(), Error> {
+        self.client.send_message(
+            self.create_command(Command::SetLongPressCommand(SetLongPress {
+                duration_ms: duration.as_millis().try_into().unwrap_or(u32::MAX),
+                ..SetLongPress::default()
+            }))
+            .write_to_bytes()?
+            .as_slice(),
+        )
+    }
+
     fn set_boot_logo(&mut self, image_path: String) -> Result<(), Error> {
         self.client.send_message(
-            create_command(Command::SetBootLogoCommand(SetBootLogo {
+            self.create_command(Command::SetBootLogoCommand(SetBootLogo {
                 image_path,
                 ..SetBootLogo::default()
             }))
@@ -88,9 +146,17 @@ impl ClientCommands for ClientWrapper {
         )
     }
 
+    fn cancel_upload(&mut self) -> Result<(), Error> {
+        self.client.send_message(
+            self.create_command(Command::CancelUploadCommand(CancelUpload::default()))
+                .write_to_bytes()?
+                .as_slice(),
+        )
+    }
+
     fn set_brightness(&mut self, brightness_percentage: u8) -> Result<(), Error> {
         self.client.send_message(
-            create_command(Command::SetBrightnessCommand(SetBrightness {
+            self.create_command(Command::SetBrightnessCommand(SetBrightness {
                 brightness_value: brightness_percentage.into(),
                 ..SetBrightness::default()
             }))
@@ -99,13 +165,31 @@ impl ClientCommands for ClientWrapper {
         )
     }
 
+    fn fade_brightness(
+        &mut self,
+        brightness_percentage: u8,
+        duration: Duration,
+        easing: Easing,
+    ) -> Result<(), Error> {
+        self.client.send_message(
+            self.create_command(Command::FadeBrightnessCommand(FadeBrightness {
+                brightness_value: brightness_percentage.into(),
+                duration_ms: duration.as_millis().try_into().unwrap_or(u32::MAX),
+                easing: EnumOrUnknown::new(easing),
+                ..FadeBrightness::default()
+            }))
+            .write_to_bytes()?
+            .as_slice(),
+        )
+    }
+
     fn set_display_zone_image(
         &mut self,
         display_zone: DisplayZone,
         image_path: String,
     ) -> Result<(), Error> {
         self.client.send_message(
-            create_command(Command::SetDisplayZoneImageCommand(SetDisplayZoneImage {
+            self.create_command(Command::SetDisplayZoneImageCommand(SetDisplayZoneImage {
                 display_zone: EnumOrUnknown::from(display_zone),
                 image_path,
                 ..SetDisplayZoneImage::default()
@@ -117,7 +201,7 @@ impl ClientCommands for ClientWrapper {
 
     fn clear_all_images(&mut self, unpersist_images: bool) -> Result<(), Error> {
         self.client.send_message(
-            create_command(Command::ClearAllDisplayZoneImagesCommand(
+            self.create_command(Command::ClearAllDisplayZoneImagesCommand(
                 ClearAllDisplayZoneImages {
                     unpersist_images,
                     ..ClearAllDisplayZoneImages::default()
@@ -130,7 +214,7 @@ impl ClientCommands for ClientWrapper {
 
     fn clear_display_zone_image(&mut self, display_zone: DisplayZone) -> Result<(), Error> {
         self.client.send_message(
-            create_command(Command::ClearDisplayZoneImageCommand(
+            self.create_command(Command::ClearDisplayZoneImageCommand(
                 ClearDisplayZoneImage {
                     display_zone: EnumOrUnknown::from(display_zone),
                     ..ClearDisplayZoneImage::default()
@@ -141,10 +225,3 @@ impl ClientCommands for ClientWrapper {
         )
     }
 }
-
-fn create_command(command: Command) -> TopLevel {
-    TopLevel {
-        command: Some(command),
-        ..TopLevel::default()
-    }
-}
@@ -146,9 +146,244 @@ impl ::protobuf::reflect::ProtobufValue for SetBrightness {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
+// @@protoc_insertion_point(message:brightness.FadeBrightness)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct FadeBrightness {
+    // message fields
+    // @@protoc_insertion_point(field:brightness.FadeBrightness.brightness_value)
+    pub brightness_value: u32,
+    // @@protoc_insertion_point(field:brightness.FadeBrightness.duration_ms)
+    pub duration_ms: u32,
+    // @@protoc_insertion_point(field:brightness.FadeBrightness.easing)
+    pub easing: ::protobuf::EnumOrUnknown<Easing>,
+    // special fields
+    // @@protoc_insertion_point(special_field:brightness.FadeBrightness.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a FadeBrightness {
+    fn default() -> &'a FadeBrightness {
+        <FadeBrightness as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl FadeBrightness {
+    pub fn new() -> FadeBrightness {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "brightness_value",
+            |m: &FadeBrightness| { &m.brightness_value },
+            |m: &mut FadeBrightness| { &mut m.brightness_value },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "duration_ms",
+            |m: &FadeBrightness| { &m.duration_ms },
+            |m: &mut FadeBrightness| { &mut m.duration_ms },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "easing",
+            |m: &FadeBrightness| { &m.easing },
+            |m: &mut FadeBrightness| { &mut m.easing },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<FadeBrightness>(
+            "FadeBrightness",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for FadeBrightness {
+    const NAME: &'static str = "FadeBrightness";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.brightness_value = is.read_uint32()?;
+                },
+                16 => {
+                    self.duration_ms = is.read_uint32()?;
+                },
+                24 => {
+                    self.easing = is.read_enum_or_unknown()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.brightness_value != 0 {
+            my_size += ::protobuf::rt::uint32_size(1, self.brightness_value);
+        }
+        if self.duration_ms != 0 {
+            my_size += ::protobuf::rt::uint32_size(2, self.duration_ms);
+        }
+        if self.easing != ::protobuf::EnumOrUnknown::new(Easing::LINEAR) {
+            my_size += ::protobuf::rt::int32_size(3, self.easing.value());
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.brightness_value != 0 {
+            os.write_uint32(1, self.brightness_value)?;
+        }
+        if self.duration_ms != 0 {
+            os.write_uint32(2, self.duration_ms)?;
+        }
+        if self.easing != ::protobuf::EnumOrUnknown::new(Easing::LINEAR) {
+            os.write_enum(3, ::protobuf::EnumOrUnknown::value(&self.easing))?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> FadeBrightness {
+        FadeBrightness::new()
+    }
+
+    fn clear(&mut self) {
+        self.brightness_value = 0;
+        self.duration_ms = 0;
+        self.easing = ::protobuf::EnumOrUnknown::new(Easing::LINEAR);
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static FadeBrightness {
+        static instance: FadeBrightness = FadeBrightness {
+            brightness_value: 0,
+            duration_ms: 0,
+            easing: ::protobuf::EnumOrUnknown::from_i32(0),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for FadeBrightness {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("FadeBrightness").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for FadeBrightness {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for FadeBrightness {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+// @@protoc_insertion_point(enum:brightness.Easing)
+pub enum Easing {
+    // @@protoc_insertion_point(enum_value:brightness.Easing.LINEAR)
+    LINEAR = 0,
+    // @@protoc_insertion_point(enum_value:brightness.Easing.EASE_IN)
+    EASE_IN = 1,
+    // @@protoc_insertion_point(enum_value:brightness.Easing.EASE_OUT)
+    EASE_OUT = 2,
+    // @@protoc_insertion_point(enum_value:brightness.Easing.EASE_IN_OUT)
+    EASE_IN_OUT = 3,
+}
+
+impl ::protobuf::Enum for Easing {
+    const NAME: &'static str = "Easing";
+
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<Easing> {
+        match value {
+            0 => ::std::option::Option::Some(Easing::LINEAR),
+            1 => ::std::option::Option::Some(Easing::EASE_IN),
+            2 => ::std::option::Option::Some(Easing::EASE_OUT),
+            3 => ::std::option::Option::Some(Easing::EASE_IN_OUT),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn from_str(str: &str) -> ::std::option::Option<Easing> {
+        match str {
+            "LINEAR" => ::std::option::Option::Some(Easing::LINEAR),
+            "EASE_IN" => ::std::option::Option::Some(Easing::EASE_IN),
+            "EASE_OUT" => ::std::option::Option::Some(Easing::EASE_OUT),
+            "EASE_IN_OUT" => ::std::option::Option::Some(Easing::EASE_IN_OUT),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    const VALUES: &'static [Easing] = &[
+        Easing::LINEAR,
+        Easing::EASE_IN,
+        Easing::EASE_OUT,
+        Easing::EASE_IN_OUT,
+    ];
+}
+
+impl ::protobuf::EnumFull for Easing {
+    fn enum_descriptor() -> ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().enum_by_package_relative_name("Easing").unwrap()).clone()
+    }
+
+    fn descriptor(&self) -> ::protobuf::reflect::EnumValueDescriptor {
+        let index = *self as usize;
+        Self::enum_descriptor().value_by_index(index)
+    }
+}
+
+impl ::std::default::Default for Easing {
+    fn default() -> Self {
+        Easing::LINEAR
+    }
+}
+
+impl Easing {
+    fn generated_enum_descriptor_data() -> ::protobuf::reflect::GeneratedEnumDescriptorData {
+        ::protobuf::reflect::GeneratedEnumDescriptorData::new::<Easing>("Easing")
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x19commands/brightness.proto\x12\nbrightness\":\n\rSetBrightness\x12)\
-    \n\x10brightness_value\x18\x01\x20\x01(\rR\x0fbrightnessValueb\x06proto3\
+    \n\x10brightness_value\x18\x01\x20\x01(\rR\x0fbrightnessValue\"\x88\x01\
+    \n\x0eFadeBrightness\x12)\n\x10brightness_value\x18\x01\x20\x01(\rR\x0fb\
+    rightnessValue\x12\x1f\n\x0bduration_ms\x18\x02\x20\x01(\rR\ndurationMs\
+    \x12*\n\x06easing\x18\x03\x20\x01(\x0e2\x12.brightness.EasingR\x06easing\
+    *@\n\x06Easing\x12\n\n\x06LINEAR\x10\0\x12\x0b\n\x07EASE_IN\x10\x01\x12\
+    \x0c\n\x08EASE_OUT\x10\x02\x12\x0f\n\x0bEASE_IN_OUT\x10\x03b\x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
@@ -166,9 +401,11 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
     file_descriptor.get(|| {
         let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
             let mut deps = ::std::vec::Vec::with_capacity(0);
-            let mut messages = ::std::vec::Vec::with_capacity(1);
+            let mut messages = ::std::vec::Vec::with_capacity(2);
             messages.push(SetBrightness::generated_message_descriptor_data());
-            let mut enums = ::std::vec::Vec::with_capacity(0);
+            messages.push(FadeBrightness::generated_message_descriptor_data());
+            let mut enums = ::std::vec::Vec::with_capacity(1);
+            enums.push(Easing::generated_enum_descriptor_data());
             ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
                 file_descriptor_proto(),
                 deps,
@@ -57,6 +57,16 @@ pub enum DisplayZone {
     TOUCHSCREEN_ZONE_3 = 13,
     // @@protoc_insertion_point(enum_value:DisplayZone.TOUCHSCREEN_ZONE_4)
     TOUCHSCREEN_ZONE_4 = 14,
+    // @@protoc_insertion_point(enum_value:DisplayZone.BUTTON_11)
+    BUTTON_11 = 15,
+    // @@protoc_insertion_point(enum_value:DisplayZone.BUTTON_12)
+    BUTTON_12 = 16,
+    // @@protoc_insertion_point(enum_value:DisplayZone.BUTTON_13)
+    BUTTON_13 = 17,
+    // @@protoc_insertion_point(enum_value:DisplayZone.BUTTON_14)
+    BUTTON_14 = 18,
+    // @@protoc_insertion_point(enum_value:DisplayZone.BUTTON_15)
+    BUTTON_15 = 19,
 }
 
 impl ::protobuf::Enum for DisplayZone {
@@ -83,6 +93,11 @@ impl ::protobuf::Enum for DisplayZone {
             12 => ::std::option::Option::Some(DisplayZone::TOUCHSCREEN_ZONE_2),
             13 => ::std::option::Option::Some(DisplayZone::TOUCHSCREEN_ZONE_3),
             14 => ::std::option::Option::Some(DisplayZone::TOUCHSCREEN_ZONE_4),
+            15 => ::std::option::Option::Some(DisplayZone::BUTTON_11),
+            16 => ::std::option::Option::Some(DisplayZone::BUTTON_12),
+            17 => ::std::option::Option::Some(DisplayZone::BUTTON_13),
+            18 => ::std::option::Option::Some(DisplayZone::BUTTON_14),
+            19 => ::std::option::Option::Some(DisplayZone::BUTTON_15),
             _ => ::std::option::Option::None
         }
     }
@@ -104,6 +119,11 @@ impl ::protobuf::Enum for DisplayZone {
             "TOUCHSCREEN_ZONE_2" => ::std::option::Option::Some(DisplayZone::TOUCHSCREEN_ZONE_2),
             "TOUCHSCREEN_ZONE_3" => ::std::option::Option::Some(DisplayZone::TOUCHSCREEN_ZONE_3),
             "TOUCHSCREEN_ZONE_4" => ::std::option::Option::Some(DisplayZone::TOUCHSCREEN_ZONE_4),
+            "BUTTON_11" => ::std::option::Option::Some(DisplayZone::BUTTON_11),
+            "BUTTON_12" => ::std::option::Option::Some(DisplayZone::BUTTON_12),
+            "BUTTON_13" => ::std::option::Option::Some(DisplayZone::BUTTON_13),
+            "BUTTON_14" => ::std::option::Option::Some(DisplayZone::BUTTON_14),
+            "BUTTON_15" => ::std::option::Option::Some(DisplayZone::BUTTON_15),
             _ => ::std::option::Option::None
         }
     }
@@ -124,6 +144,11 @@ impl ::protobuf::Enum for DisplayZone {
         DisplayZone::TOUCHSCREEN_ZONE_2,
         DisplayZone::TOUCHSCREEN_ZONE_3,
         DisplayZone::TOUCHSCREEN_ZONE_4,
+        DisplayZone::BUTTON_11,
+        DisplayZone::BUTTON_12,
+        DisplayZone::BUTTON_13,
+        DisplayZone::BUTTON_14,
+        DisplayZone::BUTTON_15,
     ];
 }
 
@@ -152,14 +177,16 @@ impl DisplayZone {
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n#commands/common/display_zones.proto*\x98\x02\n\x0bDisplayZone\x12\x1c\
+    \n#commands/common/display_zones.proto*\xe3\x02\n\x0bDisplayZone\x12\x1c\
     \n\x18DISPLAY_ZONE_UNSPECIFIED\x10\0\x12\x0c\n\x08BUTTON_1\x10\x01\x12\
     \x0c\n\x08BUTTON_2\x10\x02\x12\x0c\n\x08BUTTON_3\x10\x03\x12\x0c\n\x08BU\
     TTON_4\x10\x04\x12\x0c\n\x08BUTTON_5\x10\x05\x12\x0c\n\x08BUTTON_6\x10\
     \x06\x12\x0c\n\x08BUTTON_7\x10\x07\x12\x0c\n\x08BUTTON_8\x10\x08\x12\x0c\
     \n\x08BUTTON_9\x10\t\x12\r\n\tBUTTON_10\x10\n\x12\x16\n\x12TOUCHSCREEN_Z\
     ONE_1\x10\x0b\x12\x16\n\x12TOUCHSCREEN_ZONE_2\x10\x0c\x12\x16\n\x12TOUCH\
-    SCREEN_ZONE_3\x10\r\x12\x16\n\x12TOUCHSCREEN_ZONE_4\x10\x0eb\x06proto3\
+    SCREEN_ZONE_3\x10\r\x12\x16\n\x12TOUCHSCREEN_ZONE_4\x10\x0e\x12\r\n\tBUT\
+    TON_11\x10\x0f\x12\r\n\tBUTTON_12\x10\x10\x12\r\n\tBUTTON_13\x10\x11\x12\
+    \r\n\tBUTTON_14\x10\x12\x12\r\n\tBUTTON_15\x10\x13b\x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
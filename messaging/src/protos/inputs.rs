@@ -105,6 +105,236 @@ pub enum InputId {
     TOUCHSCREEN_SWIPED_LEFT = 37,
     // @@protoc_insertion_point(enum_value:InputId.TOUCHSCREEN_SWIPED_RIGHT)
     TOUCHSCREEN_SWIPED_RIGHT = 38,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_11_PRESSED)
+    BUTTON_11_PRESSED = 39,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_12_PRESSED)
+    BUTTON_12_PRESSED = 40,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_13_PRESSED)
+    BUTTON_13_PRESSED = 41,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_14_PRESSED)
+    BUTTON_14_PRESSED = 42,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_15_PRESSED)
+    BUTTON_15_PRESSED = 43,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_11_RELEASED)
+    BUTTON_11_RELEASED = 44,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_12_RELEASED)
+    BUTTON_12_RELEASED = 45,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_13_RELEASED)
+    BUTTON_13_RELEASED = 46,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_14_RELEASED)
+    BUTTON_14_RELEASED = 47,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_15_RELEASED)
+    BUTTON_15_RELEASED = 48,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_1_TAP)
+    BUTTON_1_TAP = 49,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_2_TAP)
+    BUTTON_2_TAP = 50,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_3_TAP)
+    BUTTON_3_TAP = 51,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_4_TAP)
+    BUTTON_4_TAP = 52,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_5_TAP)
+    BUTTON_5_TAP = 53,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_6_TAP)
+    BUTTON_6_TAP = 54,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_7_TAP)
+    BUTTON_7_TAP = 55,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_8_TAP)
+    BUTTON_8_TAP = 56,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_9_TAP)
+    BUTTON_9_TAP = 57,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_10_TAP)
+    BUTTON_10_TAP = 58,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_11_TAP)
+    BUTTON_11_TAP = 59,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_12_TAP)
+    BUTTON_12_TAP = 60,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_13_TAP)
+    BUTTON_13_TAP = 61,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_14_TAP)
+    BUTTON_14_TAP = 62,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_15_TAP)
+    BUTTON_15_TAP = 63,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_1_LONG_PRESS)
+    BUTTON_1_LONG_PRESS = 64,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_2_LONG_PRESS)
+    BUTTON_2_LONG_PRESS = 65,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_3_LONG_PRESS)
+    BUTTON_3_LONG_PRESS = 66,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_4_LONG_PRESS)
+    BUTTON_4_LONG_PRESS = 67,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_5_LONG_PRESS)
+    BUTTON_5_LONG_PRESS = 68,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_6_LONG_PRESS)
+    BUTTON_6_LONG_PRESS = 69,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_7_LONG_PRESS)
+    BUTTON_7_LONG_PRESS = 70,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_8_LONG_PRESS)
+    BUTTON_8_LONG_PRESS = 71,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_9_LONG_PRESS)
+    BUTTON_9_LONG_PRESS = 72,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_10_LONG_PRESS)
+    BUTTON_10_LONG_PRESS = 73,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_11_LONG_PRESS)
+    BUTTON_11_LONG_PRESS = 74,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_12_LONG_PRESS)
+    BUTTON_12_LONG_PRESS = 75,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_13_LONG_PRESS)
+    BUTTON_13_LONG_PRESS = 76,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_14_LONG_PRESS)
+    BUTTON_14_LONG_PRESS = 77,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_15_LONG_PRESS)
+    BUTTON_15_LONG_PRESS = 78,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_1_DOUBLE_TAP)
+    BUTTON_1_DOUBLE_TAP = 79,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_2_DOUBLE_TAP)
+    BUTTON_2_DOUBLE_TAP = 80,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_3_DOUBLE_TAP)
+    BUTTON_3_DOUBLE_TAP = 81,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_4_DOUBLE_TAP)
+    BUTTON_4_DOUBLE_TAP = 82,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_5_DOUBLE_TAP)
+    BUTTON_5_DOUBLE_TAP = 83,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_6_DOUBLE_TAP)
+    BUTTON_6_DOUBLE_TAP = 84,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_7_DOUBLE_TAP)
+    BUTTON_7_DOUBLE_TAP = 85,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_8_DOUBLE_TAP)
+    BUTTON_8_DOUBLE_TAP = 86,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_9_DOUBLE_TAP)
+    BUTTON_9_DOUBLE_TAP = 87,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_10_DOUBLE_TAP)
+    BUTTON_10_DOUBLE_TAP = 88,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_11_DOUBLE_TAP)
+    BUTTON_11_DOUBLE_TAP = 89,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_12_DOUBLE_TAP)
+    BUTTON_12_DOUBLE_TAP = 90,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_13_DOUBLE_TAP)
+    BUTTON_13_DOUBLE_TAP = 91,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_14_DOUBLE_TAP)
+    BUTTON_14_DOUBLE_TAP = 92,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_15_DOUBLE_TAP)
+    BUTTON_15_DOUBLE_TAP = 93,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_1_HOLD_REPEAT)
+    BUTTON_1_HOLD_REPEAT = 94,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_2_HOLD_REPEAT)
+    BUTTON_2_HOLD_REPEAT = 95,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_3_HOLD_REPEAT)
+    BUTTON_3_HOLD_REPEAT = 96,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_4_HOLD_REPEAT)
+    BUTTON_4_HOLD_REPEAT = 97,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_5_HOLD_REPEAT)
+    BUTTON_5_HOLD_REPEAT = 98,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_6_HOLD_REPEAT)
+    BUTTON_6_HOLD_REPEAT = 99,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_7_HOLD_REPEAT)
+    BUTTON_7_HOLD_REPEAT = 100,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_8_HOLD_REPEAT)
+    BUTTON_8_HOLD_REPEAT = 101,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_9_HOLD_REPEAT)
+    BUTTON_9_HOLD_REPEAT = 102,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_10_HOLD_REPEAT)
+    BUTTON_10_HOLD_REPEAT = 103,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_11_HOLD_REPEAT)
+    BUTTON_11_HOLD_REPEAT = 104,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_12_HOLD_REPEAT)
+    BUTTON_12_HOLD_REPEAT = 105,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_13_HOLD_REPEAT)
+    BUTTON_13_HOLD_REPEAT = 106,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_14_HOLD_REPEAT)
+    BUTTON_14_HOLD_REPEAT = 107,
+    // @@protoc_insertion_point(enum_value:InputId.BUTTON_15_HOLD_REPEAT)
+    BUTTON_15_HOLD_REPEAT = 108,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_1_2)
+    CHORD_BUTTONS_1_2 = 109,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_1_3)
+    CHORD_BUTTONS_1_3 = 110,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_1_4)
+    CHORD_BUTTONS_1_4 = 111,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_1_5)
+    CHORD_BUTTONS_1_5 = 112,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_1_6)
+    CHORD_BUTTONS_1_6 = 113,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_1_7)
+    CHORD_BUTTONS_1_7 = 114,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_1_8)
+    CHORD_BUTTONS_1_8 = 115,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_1_9)
+    CHORD_BUTTONS_1_9 = 116,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_1_10)
+    CHORD_BUTTONS_1_10 = 117,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_2_3)
+    CHORD_BUTTONS_2_3 = 118,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_2_4)
+    CHORD_BUTTONS_2_4 = 119,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_2_5)
+    CHORD_BUTTONS_2_5 = 120,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_2_6)
+    CHORD_BUTTONS_2_6 = 121,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_2_7)
+    CHORD_BUTTONS_2_7 = 122,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_2_8)
+    CHORD_BUTTONS_2_8 = 123,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_2_9)
+    CHORD_BUTTONS_2_9 = 124,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_2_10)
+    CHORD_BUTTONS_2_10 = 125,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_3_4)
+    CHORD_BUTTONS_3_4 = 126,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_3_5)
+    CHORD_BUTTONS_3_5 = 127,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_3_6)
+    CHORD_BUTTONS_3_6 = 128,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_3_7)
+    CHORD_BUTTONS_3_7 = 129,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_3_8)
+    CHORD_BUTTONS_3_8 = 130,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_3_9)
+    CHORD_BUTTONS_3_9 = 131,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_3_10)
+    CHORD_BUTTONS_3_10 = 132,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_4_5)
+    CHORD_BUTTONS_4_5 = 133,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_4_6)
+    CHORD_BUTTONS_4_6 = 134,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_4_7)
+    CHORD_BUTTONS_4_7 = 135,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_4_8)
+    CHORD_BUTTONS_4_8 = 136,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_4_9)
+    CHORD_BUTTONS_4_9 = 137,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_4_10)
+    CHORD_BUTTONS_4_10 = 138,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_5_6)
+    CHORD_BUTTONS_5_6 = 139,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_5_7)
+    CHORD_BUTTONS_5_7 = 140,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_5_8)
+    CHORD_BUTTONS_5_8 = 141,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_5_9)
+    CHORD_BUTTONS_5_9 = 142,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_5_10)
+    CHORD_BUTTONS_5_10 = 143,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_6_7)
+    CHORD_BUTTONS_6_7 = 144,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_6_8)
+    CHORD_BUTTONS_6_8 = 145,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_6_9)
+    CHORD_BUTTONS_6_9 = 146,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_6_10)
+    CHORD_BUTTONS_6_10 = 147,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_7_8)
+    CHORD_BUTTONS_7_8 = 148,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_7_9)
+    CHORD_BUTTONS_7_9 = 149,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_7_10)
+    CHORD_BUTTONS_7_10 = 150,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_8_9)
+    CHORD_BUTTONS_8_9 = 151,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_8_10)
+    CHORD_BUTTONS_8_10 = 152,
+    // @@protoc_insertion_point(enum_value:InputId.CHORD_BUTTONS_9_10)
+    CHORD_BUTTONS_9_10 = 153,
 }
 
 impl ::protobuf::Enum for InputId {
@@ -155,6 +385,121 @@ impl ::protobuf::Enum for InputId {
             36 => ::std::option::Option::Some(InputId::TOUCHSCREEN_ZONE_4_PRESSED),
             37 => ::std::option::Option::Some(InputId::TOUCHSCREEN_SWIPED_LEFT),
             38 => ::std::option::Option::Some(InputId::TOUCHSCREEN_SWIPED_RIGHT),
+            39 => ::std::option::Option::Some(InputId::BUTTON_11_PRESSED),
+            40 => ::std::option::Option::Some(InputId::BUTTON_12_PRESSED),
+            41 => ::std::option::Option::Some(InputId::BUTTON_13_PRESSED),
+            42 => ::std::option::Option::Some(InputId::BUTTON_14_PRESSED),
+            43 => ::std::option::Option::Some(InputId::BUTTON_15_PRESSED),
+            44 => ::std::option::Option::Some(InputId::BUTTON_11_RELEASED),
+            45 => ::std::option::Option::Some(InputId::BUTTON_12_RELEASED),
+            46 => ::std::option::Option::Some(InputId::BUTTON_13_RELEASED),
+            47 => ::std::option::Option::Some(InputId::BUTTON_14_RELEASED),
+            48 => ::std::option::Option::Some(InputId::BUTTON_15_RELEASED),
+            49 => ::std::option::Option::Some(InputId::BUTTON_1_TAP),
+            50 => ::std::option::Option::Some(InputId::BUTTON_2_TAP),
+            51 => ::std::option::Option::Some(InputId::BUTTON_3_TAP),
+            52 => ::std::option::Option::Some(InputId::BUTTON_4_TAP),
+            53 => ::std::option::Option::Some(InputId::BUTTON_5_TAP),
+            54 => ::std::option::Option::Some(InputId::BUTTON_6_TAP),
+            55 => ::std::option::Option::Some(InputId::BUTTON_7_TAP),
+            56 => ::std::option::Option::Some(InputId::BUTTON_8_TAP),
+            57 => ::std::option::Option::Some(InputId::BUTTON_9_TAP),
+            58 => ::std::option::Option::Some(InputId::BUTTON_10_TAP),
+            59 => ::std::option::Option::Some(InputId::BUTTON_11_TAP),
+            60 => ::std::option::Option::Some(InputId::BUTTON_12_TAP),
+            61 => ::std::option::Option::Some(InputId::BUTTON_13_TAP),
+            62 => ::std::option::Option::Some(InputId::BUTTON_14_TAP),
+            63 => ::std::option::Option::Some(InputId::BUTTON_15_TAP),
+            64 => ::std::option::Option::Some(InputId::BUTTON_1_LONG_PRESS),
+            65 => ::std::option::Option::Some(InputId::BUTTON_2_LONG_PRESS),
+            66 => ::std::option::Option::Some(InputId::BUTTON_3_LONG_PRESS),
+            67 => ::std::option::Option::Some(InputId::BUTTON_4_LONG_PRESS),
+            68 => ::std::option::Option::Some(InputId::BUTTON_5_LONG_PRESS),
+            69 => ::std::option::Option::Some(InputId::BUTTON_6_LONG_PRESS),
+            70 => ::std::option::Option::Some(InputId::BUTTON_7_LONG_PRESS),
+            71 => ::std::option::Option::Some(InputId::BUTTON_8_LONG_PRESS),
+            72 => ::std::option::Option::Some(InputId::BUTTON_9_LONG_PRESS),
+            73 => ::std::option::Option::Some(InputId::BUTTON_10_LONG_PRESS),
+            74 => ::std::option::Option::Some(InputId::BUTTON_11_LONG_PRESS),
+            75 => ::std::option::Option::Some(InputId::BUTTON_12_LONG_PRESS),
+            76 => ::std::option::Option::Some(InputId::BUTTON_13_LONG_PRESS),
+            77 => ::std::option::Option::Some(InputId::BUTTON_14_LONG_PRESS),
+            78 => ::std::option::Option::Some(InputId::BUTTON_15_LONG_PRESS),
+            79 => ::std::option::Option::Some(InputId::BUTTON_1_DOUBLE_TAP),
+            80 => ::std::option::Option::Some(InputId::BUTTON_2_DOUBLE_TAP),
+            81 => ::std::option::Option::Some(InputId::BUTTON_3_DOUBLE_TAP),
+            82 => ::std::option::Option::Some(InputId::BUTTON_4_DOUBLE_TAP),
+            83 => ::std::option::Option::Some(InputId::BUTTON_5_DOUBLE_TAP),
+            84 => ::std::option::Option::Some(InputId::BUTTON_6_DOUBLE_TAP),
+            85 => ::std::option::Option::Some(InputId::BUTTON_7_DOUBLE_TAP),
+            86 => ::std::option::Option::Some(InputId::BUTTON_8_DOUBLE_TAP),
+            87 => ::std::option::Option::Some(InputId::BUTTON_9_DOUBLE_TAP),
+            88 => ::std::option::Option::Some(InputId::BUTTON_10_DOUBLE_TAP),
+            89 => ::std::option::Option::Some(InputId::BUTTON_11_DOUBLE_TAP),
+            90 => ::std::option::Option::Some(InputId::BUTTON_12_DOUBLE_TAP),
+            91 => ::std::option::Option::Some(InputId::BUTTON_13_DOUBLE_TAP),
+            92 => ::std::option::Option::Some(InputId::BUTTON_14_DOUBLE_TAP),
+            93 => ::std::option::Option::Some(InputId::BUTTON_15_DOUBLE_TAP),
+            94 => ::std::option::Option::Some(InputId::BUTTON_1_HOLD_REPEAT),
+            95 => ::std::option::Option::Some(InputId::BUTTON_2_HOLD_REPEAT),
+            96 => ::std::option::Option::Some(InputId::BUTTON_3_HOLD_REPEAT),
+            97 => ::std::option::Option::Some(InputId::BUTTON_4_HOLD_REPEAT),
+            98 => ::std::option::Option::Some(InputId::BUTTON_5_HOLD_REPEAT),
+            99 => ::std::option::Option::Some(InputId::BUTTON_6_HOLD_REPEAT),
+            100 => ::std::option::Option::Some(InputId::BUTTON_7_HOLD_REPEAT),
+            101 => ::std::option::Option::Some(InputId::BUTTON_8_HOLD_REPEAT),
+            102 => ::std::option::Option::Some(InputId::BUTTON_9_HOLD_REPEAT),
+            103 => ::std::option::Option::Some(InputId::BUTTON_10_HOLD_REPEAT),
+            104 => ::std::option::Option::Some(InputId::BUTTON_11_HOLD_REPEAT),
+            105 => ::std::option::Option::Some(InputId::BUTTON_12_HOLD_REPEAT),
+            106 => ::std::option::Option::Some(InputId::BUTTON_13_HOLD_REPEAT),
+            107 => ::std::option::Option::Some(InputId::BUTTON_14_HOLD_REPEAT),
+            108 => ::std::option::Option::Some(InputId::BUTTON_15_HOLD_REPEAT),
+            109 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_2),
+            110 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_3),
+            111 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_4),
+            112 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_5),
+            113 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_6),
+            114 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_7),
+            115 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_8),
+            116 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_9),
+            117 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_10),
+            118 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_3),
+            119 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_4),
+            120 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_5),
+            121 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_6),
+            122 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_7),
+            123 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_8),
+            124 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_9),
+            125 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_10),
+            126 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_4),
+            127 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_5),
+            128 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_6),
+            129 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_7),
+            130 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_8),
+            131 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_9),
+            132 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_10),
+            133 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_4_5),
+            134 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_4_6),
+            135 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_4_7),
+            136 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_4_8),
+            137 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_4_9),
+            138 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_4_10),
+            139 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_5_6),
+            140 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_5_7),
+            141 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_5_8),
+            142 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_5_9),
+            143 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_5_10),
+            144 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_6_7),
+            145 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_6_8),
+            146 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_6_9),
+            147 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_6_10),
+            148 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_7_8),
+            149 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_7_9),
+            150 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_7_10),
+            151 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_8_9),
+            152 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_8_10),
+            153 => ::std::option::Option::Some(InputId::CHORD_BUTTONS_9_10),
             _ => ::std::option::Option::None
         }
     }
@@ -200,6 +545,121 @@ impl ::protobuf::Enum for InputId {
             "TOUCHSCREEN_ZONE_4_PRESSED" => ::std::option::Option::Some(InputId::TOUCHSCREEN_ZONE_4_PRESSED),
             "TOUCHSCREEN_SWIPED_LEFT" => ::std::option::Option::Some(InputId::TOUCHSCREEN_SWIPED_LEFT),
             "TOUCHSCREEN_SWIPED_RIGHT" => ::std::option::Option::Some(InputId::TOUCHSCREEN_SWIPED_RIGHT),
+            "BUTTON_11_PRESSED" => ::std::option::Option::Some(InputId::BUTTON_11_PRESSED),
+            "BUTTON_12_PRESSED" => ::std::option::Option::Some(InputId::BUTTON_12_PRESSED),
+            "BUTTON_13_PRESSED" => ::std::option::Option::Some(InputId::BUTTON_13_PRESSED),
+            "BUTTON_14_PRESSED" => ::std::option::Option::Some(InputId::BUTTON_14_PRESSED),
+            "BUTTON_15_PRESSED" => ::std::option::Option::Some(InputId::BUTTON_15_PRESSED),
+            "BUTTON_11_RELEASED" => ::std::option::Option::Some(InputId::BUTTON_11_RELEASED),
+            "BUTTON_12_RELEASED" => ::std::option::Option::Some(InputId::BUTTON_12_RELEASED),
+            "BUTTON_13_RELEASED" => ::std::option::Option::Some(InputId::BUTTON_13_RELEASED),
+            "BUTTON_14_RELEASED" => ::std::option::Option::Some(InputId::BUTTON_14_RELEASED),
+            "BUTTON_15_RELEASED" => ::std::option::Option::Some(InputId::BUTTON_15_RELEASED),
+            "BUTTON_1_TAP" => ::std::option::Option::Some(InputId::BUTTON_1_TAP),
+            "BUTTON_2_TAP" => ::std::option::Option::Some(InputId::BUTTON_2_TAP),
+            "BUTTON_3_TAP" => ::std::option::Option::Some(InputId::BUTTON_3_TAP),
+            "BUTTON_4_TAP" => ::std::option::Option::Some(InputId::BUTTON_4_TAP),
+            "BUTTON_5_TAP" => ::std::option::Option::Some(InputId::BUTTON_5_TAP),
+            "BUTTON_6_TAP" => ::std::option::Option::Some(InputId::BUTTON_6_TAP),
+            "BUTTON_7_TAP" => ::std::option::Option::Some(InputId::BUTTON_7_TAP),
+            "BUTTON_8_TAP" => ::std::option::Option::Some(InputId::BUTTON_8_TAP),
+            "BUTTON_9_TAP" => ::std::option::Option::Some(InputId::BUTTON_9_TAP),
+            "BUTTON_10_TAP" => ::std::option::Option::Some(InputId::BUTTON_10_TAP),
+            "BUTTON_11_TAP" => ::std::option::Option::Some(InputId::BUTTON_11_TAP),
+            "BUTTON_12_TAP" => ::std::option::Option::Some(InputId::BUTTON_12_TAP),
+            "BUTTON_13_TAP" => ::std::option::Option::Some(InputId::BUTTON_13_TAP),
+            "BUTTON_14_TAP" => ::std::option::Option::Some(InputId::BUTTON_14_TAP),
+            "BUTTON_15_TAP" => ::std::option::Option::Some(InputId::BUTTON_15_TAP),
+            "BUTTON_1_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_1_LONG_PRESS),
+            "BUTTON_2_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_2_LONG_PRESS),
+            "BUTTON_3_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_3_LONG_PRESS),
+            "BUTTON_4_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_4_LONG_PRESS),
+            "BUTTON_5_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_5_LONG_PRESS),
+            "BUTTON_6_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_6_LONG_PRESS),
+            "BUTTON_7_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_7_LONG_PRESS),
+            "BUTTON_8_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_8_LONG_PRESS),
+            "BUTTON_9_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_9_LONG_PRESS),
+            "BUTTON_10_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_10_LONG_PRESS),
+            "BUTTON_11_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_11_LONG_PRESS),
+            "BUTTON_12_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_12_LONG_PRESS),
+            "BUTTON_13_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_13_LONG_PRESS),
+            "BUTTON_14_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_14_LONG_PRESS),
+            "BUTTON_15_LONG_PRESS" => ::std::option::Option::Some(InputId::BUTTON_15_LONG_PRESS),
+            "BUTTON_1_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_1_DOUBLE_TAP),
+            "BUTTON_2_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_2_DOUBLE_TAP),
+            "BUTTON_3_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_3_DOUBLE_TAP),
+            "BUTTON_4_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_4_DOUBLE_TAP),
+            "BUTTON_5_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_5_DOUBLE_TAP),
+            "BUTTON_6_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_6_DOUBLE_TAP),
+            "BUTTON_7_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_7_DOUBLE_TAP),
+            "BUTTON_8_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_8_DOUBLE_TAP),
+            "BUTTON_9_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_9_DOUBLE_TAP),
+            "BUTTON_10_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_10_DOUBLE_TAP),
+            "BUTTON_11_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_11_DOUBLE_TAP),
+            "BUTTON_12_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_12_DOUBLE_TAP),
+            "BUTTON_13_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_13_DOUBLE_TAP),
+            "BUTTON_14_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_14_DOUBLE_TAP),
+            "BUTTON_15_DOUBLE_TAP" => ::std::option::Option::Some(InputId::BUTTON_15_DOUBLE_TAP),
+            "BUTTON_1_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_1_HOLD_REPEAT),
+            "BUTTON_2_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_2_HOLD_REPEAT),
+            "BUTTON_3_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_3_HOLD_REPEAT),
+            "BUTTON_4_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_4_HOLD_REPEAT),
+            "BUTTON_5_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_5_HOLD_REPEAT),
+            "BUTTON_6_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_6_HOLD_REPEAT),
+            "BUTTON_7_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_7_HOLD_REPEAT),
+            "BUTTON_8_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_8_HOLD_REPEAT),
+            "BUTTON_9_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_9_HOLD_REPEAT),
+            "BUTTON_10_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_10_HOLD_REPEAT),
+            "BUTTON_11_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_11_HOLD_REPEAT),
+            "BUTTON_12_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_12_HOLD_REPEAT),
+            "BUTTON_13_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_13_HOLD_REPEAT),
+            "BUTTON_14_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_14_HOLD_REPEAT),
+            "BUTTON_15_HOLD_REPEAT" => ::std::option::Option::Some(InputId::BUTTON_15_HOLD_REPEAT),
+            "CHORD_BUTTONS_1_2" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_2),
+            "CHORD_BUTTONS_1_3" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_3),
+            "CHORD_BUTTONS_1_4" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_4),
+            "CHORD_BUTTONS_1_5" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_5),
+            "CHORD_BUTTONS_1_6" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_6),
+            "CHORD_BUTTONS_1_7" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_7),
+            "CHORD_BUTTONS_1_8" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_8),
+            "CHORD_BUTTONS_1_9" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_9),
+            "CHORD_BUTTONS_1_10" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_1_10),
+            "CHORD_BUTTONS_2_3" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_3),
+            "CHORD_BUTTONS_2_4" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_4),
+            "CHORD_BUTTONS_2_5" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_5),
+            "CHORD_BUTTONS_2_6" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_6),
+            "CHORD_BUTTONS_2_7" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_7),
+            "CHORD_BUTTONS_2_8" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_8),
+            "CHORD_BUTTONS_2_9" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_9),
+            "CHORD_BUTTONS_2_10" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_2_10),
+            "CHORD_BUTTONS_3_4" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_4),
+            "CHORD_BUTTONS_3_5" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_5),
+            "CHORD_BUTTONS_3_6" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_6),
+            "CHORD_BUTTONS_3_7" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_7),
+            "CHORD_BUTTONS_3_8" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_8),
+            "CHORD_BUTTONS_3_9" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_9),
+            "CHORD_BUTTONS_3_10" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_3_10),
+            "CHORD_BUTTONS_4_5" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_4_5),
+            "CHORD_BUTTONS_4_6" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_4_6),
+            "CHORD_BUTTONS_4_7" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_4_7),
+            "CHORD_BUTTONS_4_8" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_4_8),
+            "CHORD_BUTTONS_4_9" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_4_9),
+            "CHORD_BUTTONS_4_10" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_4_10),
+            "CHORD_BUTTONS_5_6" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_5_6),
+            "CHORD_BUTTONS_5_7" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_5_7),
+            "CHORD_BUTTONS_5_8" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_5_8),
+            "CHORD_BUTTONS_5_9" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_5_9),
+            "CHORD_BUTTONS_5_10" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_5_10),
+            "CHORD_BUTTONS_6_7" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_6_7),
+            "CHORD_BUTTONS_6_8" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_6_8),
+            "CHORD_BUTTONS_6_9" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_6_9),
+            "CHORD_BUTTONS_6_10" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_6_10),
+            "CHORD_BUTTONS_7_8" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_7_8),
+            "CHORD_BUTTONS_7_9" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_7_9),
+            "CHORD_BUTTONS_7_10" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_7_10),
+            "CHORD_BUTTONS_8_9" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_8_9),
+            "CHORD_BUTTONS_8_10" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_8_10),
+            "CHORD_BUTTONS_9_10" => ::std::option::Option::Some(InputId::CHORD_BUTTONS_9_10),
             _ => ::std::option::Option::None
         }
     }
@@ -244,6 +704,121 @@ impl ::protobuf::Enum for InputId {
         InputId::TOUCHSCREEN_ZONE_4_PRESSED,
         InputId::TOUCHSCREEN_SWIPED_LEFT,
         InputId::TOUCHSCREEN_SWIPED_RIGHT,
+        InputId::BUTTON_11_PRESSED,
+        InputId::BUTTON_12_PRESSED,
+        InputId::BUTTON_13_PRESSED,
+        InputId::BUTTON_14_PRESSED,
+        InputId::BUTTON_15_PRESSED,
+        InputId::BUTTON_11_RELEASED,
+        InputId::BUTTON_12_RELEASED,
+        InputId::BUTTON_13_RELEASED,
+        InputId::BUTTON_14_RELEASED,
+        InputId::BUTTON_15_RELEASED,
+        InputId::BUTTON_1_TAP,
+        InputId::BUTTON_2_TAP,
+        InputId::BUTTON_3_TAP,
+        InputId::BUTTON_4_TAP,
+        InputId::BUTTON_5_TAP,
+        InputId::BUTTON_6_TAP,
+        InputId::BUTTON_7_TAP,
+        InputId::BUTTON_8_TAP,
+        InputId::BUTTON_9_TAP,
+        InputId::BUTTON_10_TAP,
+        InputId::BUTTON_11_TAP,
+        InputId::BUTTON_12_TAP,
+        InputId::BUTTON_13_TAP,
+        InputId::BUTTON_14_TAP,
+        InputId::BUTTON_15_TAP,
+        InputId::BUTTON_1_LONG_PRESS,
+        InputId::BUTTON_2_LONG_PRESS,
+        InputId::BUTTON_3_LONG_PRESS,
+        InputId::BUTTON_4_LONG_PRESS,
+        InputId::BUTTON_5_LONG_PRESS,
+        InputId::BUTTON_6_LONG_PRESS,
+        InputId::BUTTON_7_LONG_PRESS,
+        InputId::BUTTON_8_LONG_PRESS,
+        InputId::BUTTON_9_LONG_PRESS,
+        InputId::BUTTON_10_LONG_PRESS,
+        InputId::BUTTON_11_LONG_PRESS,
+        InputId::BUTTON_12_LONG_PRESS,
+        InputId::BUTTON_13_LONG_PRESS,
+        InputId::BUTTON_14_LONG_PRESS,
+        InputId::BUTTON_15_LONG_PRESS,
+        InputId::BUTTON_1_DOUBLE_TAP,
+        InputId::BUTTON_2_DOUBLE_TAP,
+        InputId::BUTTON_3_DOUBLE_TAP,
+        InputId::BUTTON_4_DOUBLE_TAP,
+        InputId::BUTTON_5_DOUBLE_TAP,
+        InputId::BUTTON_6_DOUBLE_TAP,
+        InputId::BUTTON_7_DOUBLE_TAP,
+        InputId::BUTTON_8_DOUBLE_TAP,
+        InputId::BUTTON_9_DOUBLE_TAP,
+        InputId::BUTTON_10_DOUBLE_TAP,
+        InputId::BUTTON_11_DOUBLE_TAP,
+        InputId::BUTTON_12_DOUBLE_TAP,
+        InputId::BUTTON_13_DOUBLE_TAP,
+        InputId::BUTTON_14_DOUBLE_TAP,
+        InputId::BUTTON_15_DOUBLE_TAP,
+        InputId::BUTTON_1_HOLD_REPEAT,
+        InputId::BUTTON_2_HOLD_REPEAT,
+        InputId::BUTTON_3_HOLD_REPEAT,
+        InputId::BUTTON_4_HOLD_REPEAT,
+        InputId::BUTTON_5_HOLD_REPEAT,
+        InputId::BUTTON_6_HOLD_REPEAT,
+        InputId::BUTTON_7_HOLD_REPEAT,
+        InputId::BUTTON_8_HOLD_REPEAT,
+        InputId::BUTTON_9_HOLD_REPEAT,
+        InputId::BUTTON_10_HOLD_REPEAT,
+        InputId::BUTTON_11_HOLD_REPEAT,
+        InputId::BUTTON_12_HOLD_REPEAT,
+        InputId::BUTTON_13_HOLD_REPEAT,
+        InputId::BUTTON_14_HOLD_REPEAT,
+        InputId::BUTTON_15_HOLD_REPEAT,
+        InputId::CHORD_BUTTONS_1_2,
+        InputId::CHORD_BUTTONS_1_3,
+        InputId::CHORD_BUTTONS_1_4,
+        InputId::CHORD_BUTTONS_1_5,
+        InputId::CHORD_BUTTONS_1_6,
+        InputId::CHORD_BUTTONS_1_7,
+        InputId::CHORD_BUTTONS_1_8,
+        InputId::CHORD_BUTTONS_1_9,
+        InputId::CHORD_BUTTONS_1_10,
+        InputId::CHORD_BUTTONS_2_3,
+        InputId::CHORD_BUTTONS_2_4,
+        InputId::CHORD_BUTTONS_2_5,
+        InputId::CHORD_BUTTONS_2_6,
+        InputId::CHORD_BUTTONS_2_7,
+        InputId::CHORD_BUTTONS_2_8,
+        InputId::CHORD_BUTTONS_2_9,
+        InputId::CHORD_BUTTONS_2_10,
+        InputId::CHORD_BUTTONS_3_4,
+        InputId::CHORD_BUTTONS_3_5,
+        InputId::CHORD_BUTTONS_3_6,
+        InputId::CHORD_BUTTONS_3_7,
+        InputId::CHORD_BUTTONS_3_8,
+        InputId::CHORD_BUTTONS_3_9,
+        InputId::CHORD_BUTTONS_3_10,
+        InputId::CHORD_BUTTONS_4_5,
+        InputId::CHORD_BUTTONS_4_6,
+        InputId::CHORD_BUTTONS_4_7,
+        InputId::CHORD_BUTTONS_4_8,
+        InputId::CHORD_BUTTONS_4_9,
+        InputId::CHORD_BUTTONS_4_10,
+        InputId::CHORD_BUTTONS_5_6,
+        InputId::CHORD_BUTTONS_5_7,
+        InputId::CHORD_BUTTONS_5_8,
+        InputId::CHORD_BUTTONS_5_9,
+        InputId::CHORD_BUTTONS_5_10,
+        InputId::CHORD_BUTTONS_6_7,
+        InputId::CHORD_BUTTONS_6_8,
+        InputId::CHORD_BUTTONS_6_9,
+        InputId::CHORD_BUTTONS_6_10,
+        InputId::CHORD_BUTTONS_7_8,
+        InputId::CHORD_BUTTONS_7_9,
+        InputId::CHORD_BUTTONS_7_10,
+        InputId::CHORD_BUTTONS_8_9,
+        InputId::CHORD_BUTTONS_8_10,
+        InputId::CHORD_BUTTONS_9_10,
     ];
 }
 
@@ -272,7 +847,7 @@ impl InputId {
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x1ccommands/common/inputs.proto*\xc6\x07\n\x07InputId\x12\x1c\n\x18IN\
+    \n\x1ccommands/common/inputs.proto*\xf9\x1c\n\x07InputId\x12\x1c\n\x18IN\
     PUT_ACTION_UNSPECIFIED\x10\0\x12\x14\n\x10BUTTON_1_PRESSED\x10\x01\x12\
     \x14\n\x10BUTTON_2_PRESSED\x10\x02\x12\x14\n\x10BUTTON_3_PRESSED\x10\x03\
     \x12\x14\n\x10BUTTON_4_PRESSED\x10\x04\x12\x14\n\x10BUTTON_5_PRESSED\x10\
@@ -294,7 +869,69 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     UCHSCREEN_ZONE_1_PRESSED\x10!\x12\x1e\n\x1aTOUCHSCREEN_ZONE_2_PRESSED\
     \x10\"\x12\x1e\n\x1aTOUCHSCREEN_ZONE_3_PRESSED\x10#\x12\x1e\n\x1aTOUCHSC\
     REEN_ZONE_4_PRESSED\x10$\x12\x1b\n\x17TOUCHSCREEN_SWIPED_LEFT\x10%\x12\
-    \x1c\n\x18TOUCHSCREEN_SWIPED_RIGHT\x10&b\x06proto3\
+    \x1c\n\x18TOUCHSCREEN_SWIPED_RIGHT\x10&\x12\x15\n\x11BUTTON_11_PRESSED\
+    \x10'\x12\x15\n\x11BUTTON_12_PRESSED\x10(\x12\x15\n\x11BUTTON_13_PRESSED\
+    \x10)\x12\x15\n\x11BUTTON_14_PRESSED\x10*\x12\x15\n\x11BUTTON_15_PRESSED\
+    \x10+\x12\x16\n\x12BUTTON_11_RELEASED\x10,\x12\x16\n\x12BUTTON_12_RELEAS\
+    ED\x10-\x12\x16\n\x12BUTTON_13_RELEASED\x10.\x12\x16\n\x12BUTTON_14_RELE\
+    ASED\x10/\x12\x16\n\x12BUTTON_15_RELEASED\x100\x12\x10\n\x0cBUTTON_1_TAP\
+    \x101\x12\x10\n\x0cBUTTON_2_TAP\x102\x12\x10\n\x0cBUTTON_3_TAP\x103\x12\
+    \x10\n\x0cBUTTON_4_TAP\x104\x12\x10\n\x0cBUTTON_5_TAP\x105\x12\x10\n\x0c\
+    BUTTON_6_TAP\x106\x12\x10\n\x0cBUTTON_7_TAP\x107\x12\x10\n\x0cBUTTON_8_T\
+    AP\x108\x12\x10\n\x0cBUTTON_9_TAP\x109\x12\x11\n\rBUTTON_10_TAP\x10:\x12\
+    \x11\n\rBUTTON_11_TAP\x10;\x12\x11\n\rBUTTON_12_TAP\x10<\x12\x11\n\rBUTT\
+    ON_13_TAP\x10=\x12\x11\n\rBUTTON_14_TAP\x10>\x12\x11\n\rBUTTON_15_TAP\
+    \x10?\x12\x17\n\x13BUTTON_1_LONG_PRESS\x10@\x12\x17\n\x13BUTTON_2_LONG_P\
+    RESS\x10A\x12\x17\n\x13BUTTON_3_LONG_PRESS\x10B\x12\x17\n\x13BUTTON_4_LO\
+    NG_PRESS\x10C\x12\x17\n\x13BUTTON_5_LONG_PRESS\x10D\x12\x17\n\x13BUTTON_\
+    6_LONG_PRESS\x10E\x12\x17\n\x13BUTTON_7_LONG_PRESS\x10F\x12\x17\n\x13BUT\
+    TON_8_LONG_PRESS\x10G\x12\x17\n\x13BUTTON_9_LONG_PRESS\x10H\x12\x18\n\
+    \x14BUTTON_10_LONG_PRESS\x10I\x12\x18\n\x14BUTTON_11_LONG_PRESS\x10J\x12\
+    \x18\n\x14BUTTON_12_LONG_PRESS\x10K\x12\x18\n\x14BUTTON_13_LONG_PRESS\
+    \x10L\x12\x18\n\x14BUTTON_14_LONG_PRESS\x10M\x12\x18\n\x14BUTTON_15_LONG\
+    _PRESS\x10N\x12\x17\n\x13BUTTON_1_DOUBLE_TAP\x10O\x12\x17\n\x13BUTTON_2_\
+    DOUBLE_TAP\x10P\x12\x17\n\x13BUTTON_3_DOUBLE_TAP\x10Q\x12\x17\n\x13BUTTO\
+    N_4_DOUBLE_TAP\x10R\x12\x17\n\x13BUTTON_5_DOUBLE_TAP\x10S\x12\x17\n\x13B\
+    UTTON_6_DOUBLE_TAP\x10T\x12\x17\n\x13BUTTON_7_DOUBLE_TAP\x10U\x12\x17\n\
+    \x13BUTTON_8_DOUBLE_TAP\x10V\x12\x17\n\x13BUTTON_9_DOUBLE_TAP\x10W\x12\
+    \x18\n\x14BUTTON_10_DOUBLE_TAP\x10X\x12\x18\n\x14BUTTON_11_DOUBLE_TAP\
+    \x10Y\x12\x18\n\x14BUTTON_12_DOUBLE_TAP\x10Z\x12\x18\n\x14BUTTON_13_DOUB\
+    LE_TAP\x10[\x12\x18\n\x14BUTTON_14_DOUBLE_TAP\x10\\\x12\x18\n\x14BUTTON_\
+    15_DOUBLE_TAP\x10]\x12\x18\n\x14BUTTON_1_HOLD_REPEAT\x10^\x12\x18\n\x14B\
+    UTTON_2_HOLD_REPEAT\x10_\x12\x18\n\x14BUTTON_3_HOLD_REPEAT\x10`\x12\x18\
+    \n\x14BUTTON_4_HOLD_REPEAT\x10a\x12\x18\n\x14BUTTON_5_HOLD_REPEAT\x10b\
+    \x12\x18\n\x14BUTTON_6_HOLD_REPEAT\x10c\x12\x18\n\x14BUTTON_7_HOLD_REPEA\
+    T\x10d\x12\x18\n\x14BUTTON_8_HOLD_REPEAT\x10e\x12\x18\n\x14BUTTON_9_HOLD\
+    _REPEAT\x10f\x12\x19\n\x15BUTTON_10_HOLD_REPEAT\x10g\x12\x19\n\x15BUTTON\
+    _11_HOLD_REPEAT\x10h\x12\x19\n\x15BUTTON_12_HOLD_REPEAT\x10i\x12\x19\n\
+    \x15BUTTON_13_HOLD_REPEAT\x10j\x12\x19\n\x15BUTTON_14_HOLD_REPEAT\x10k\
+    \x12\x19\n\x15BUTTON_15_HOLD_REPEAT\x10l\x12\x15\n\x11CHORD_BUTTONS_1_2\
+    \x10m\x12\x15\n\x11CHORD_BUTTONS_1_3\x10n\x12\x15\n\x11CHORD_BUTTONS_1_4\
+    \x10o\x12\x15\n\x11CHORD_BUTTONS_1_5\x10p\x12\x15\n\x11CHORD_BUTTONS_1_6\
+    \x10q\x12\x15\n\x11CHORD_BUTTONS_1_7\x10r\x12\x15\n\x11CHORD_BUTTONS_1_8\
+    \x10s\x12\x15\n\x11CHORD_BUTTONS_1_9\x10t\x12\x16\n\x12CHORD_BUTTONS_1_1\
+    0\x10u\x12\x15\n\x11CHORD_BUTTONS_2_3\x10v\x12\x15\n\x11CHORD_BUTTONS_2_\
+    4\x10w\x12\x15\n\x11CHORD_BUTTONS_2_5\x10x\x12\x15\n\x11CHORD_BUTTONS_2_\
+    6\x10y\x12\x15\n\x11CHORD_BUTTONS_2_7\x10z\x12\x15\n\x11CHORD_BUTTONS_2_\
+    8\x10{\x12\x15\n\x11CHORD_BUTTONS_2_9\x10|\x12\x16\n\x12CHORD_BUTTONS_2_\
+    10\x10}\x12\x15\n\x11CHORD_BUTTONS_3_4\x10~\x12\x15\n\x11CHORD_BUTTONS_3\
+    _5\x10\x7f\x12\x16\n\x11CHORD_BUTTONS_3_6\x10\x80\x01\x12\x16\n\x11CHORD\
+    _BUTTONS_3_7\x10\x81\x01\x12\x16\n\x11CHORD_BUTTONS_3_8\x10\x82\x01\x12\
+    \x16\n\x11CHORD_BUTTONS_3_9\x10\x83\x01\x12\x17\n\x12CHORD_BUTTONS_3_10\
+    \x10\x84\x01\x12\x16\n\x11CHORD_BUTTONS_4_5\x10\x85\x01\x12\x16\n\x11CHO\
+    RD_BUTTONS_4_6\x10\x86\x01\x12\x16\n\x11CHORD_BUTTONS_4_7\x10\x87\x01\
+    \x12\x16\n\x11CHORD_BUTTONS_4_8\x10\x88\x01\x12\x16\n\x11CHORD_BUTTONS_4\
+    _9\x10\x89\x01\x12\x17\n\x12CHORD_BUTTONS_4_10\x10\x8a\x01\x12\x16\n\x11\
+    CHORD_BUTTONS_5_6\x10\x8b\x01\x12\x16\n\x11CHORD_BUTTONS_5_7\x10\x8c\x01\
+    \x12\x16\n\x11CHORD_BUTTONS_5_8\x10\x8d\x01\x12\x16\n\x11CHORD_BUTTONS_5\
+    _9\x10\x8e\x01\x12\x17\n\x12CHORD_BUTTONS_5_10\x10\x8f\x01\x12\x16\n\x11\
+    CHORD_BUTTONS_6_7\x10\x90\x01\x12\x16\n\x11CHORD_BUTTONS_6_8\x10\x91\x01\
+    \x12\x16\n\x11CHORD_BUTTONS_6_9\x10\x92\x01\x12\x17\n\x12CHORD_BUTTONS_6\
+    _10\x10\x93\x01\x12\x16\n\x11CHORD_BUTTONS_7_8\x10\x94\x01\x12\x16\n\x11\
+    CHORD_BUTTONS_7_9\x10\x95\x01\x12\x17\n\x12CHORD_BUTTONS_7_10\x10\x96\
+    \x01\x12\x16\n\x11CHORD_BUTTONS_8_9\x10\x97\x01\x12\x17\n\x12CHORD_BUTTO\
+    NS_8_10\x10\x98\x01\x12\x17\n\x12CHORD_BUTTONS_9_10\x10\x99\x01b\x06prot\
+    o3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
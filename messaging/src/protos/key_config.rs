@@ -165,6 +165,128 @@ impl ::protobuf::reflect::ProtobufValue for KeyConfig {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
+// @@protoc_insertion_point(message:key_config.SetLongPress)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct SetLongPress {
+    // message fields
+    // @@protoc_insertion_point(field:key_config.SetLongPress.duration_ms)
+    pub duration_ms: u32,
+    // special fields
+    // @@protoc_insertion_point(special_field:key_config.SetLongPress.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a SetLongPress {
+    fn default() -> &'a SetLongPress {
+        <SetLongPress as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl SetLongPress {
+    pub fn new() -> SetLongPress {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "duration_ms",
+            |m: &SetLongPress| { &m.duration_ms },
+            |m: &mut SetLongPress| { &mut m.duration_ms },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<SetLongPress>(
+            "SetLongPress",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for SetLongPress {
+    const NAME: &'static str = "SetLongPress";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.duration_ms = is.read_uint32()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.duration_ms != 0 {
+            my_size += ::protobuf::rt::uint32_size(1, self.duration_ms);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.duration_ms != 0 {
+            os.write_uint32(1, self.duration_ms)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> SetLongPress {
+        SetLongPress::new()
+    }
+
+    fn clear(&mut self) {
+        self.duration_ms = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static SetLongPress {
+        static instance: SetLongPress = SetLongPress {
+            duration_ms: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for SetLongPress {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("SetLongPress").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for SetLongPress {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SetLongPress {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
 // @@protoc_insertion_point(message:key_config.Action)
 #[derive(PartialEq,Clone,Default,Debug)]
 pub struct Action {
@@ -613,15 +735,16 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x19commands/key_config.proto\x12\nkey_config\x1a\x1acommands/common/k\
     eys.proto\x1a\x1ccommands/common/inputs.proto\"^\n\tKeyConfig\x12#\n\x08\
     input_id\x18\x01\x20\x01(\x0e2\x08.InputIdR\x07inputId\x12,\n\x07actions\
-    \x18\x02\x20\x03(\x0b2\x12.key_config.ActionR\x07actions\"{\n\x06Action\
-    \x12*\n\x04type\x18\x01\x20\x01(\x0e2\x16.key_config.ActionTypeR\x04type\
-    \x126\n\nkey_action\x18\x02\x20\x01(\x0b2\x15.key_config.KeyActionH\0R\t\
-    keyActionB\r\n\x0baction_data\"\x8c\x01\n\tKeyAction\x12\x16\n\x03key\
-    \x18\x01\x20\x01(\x0e2\x04.KeyR\x03key\x12\x1d\n\x07unicode\x18\x02\x20\
-    \x01(\rH\0R\x07unicode\x88\x01\x01\x12)\n\x0eother_key_code\x18\x03\x20\
-    \x01(\rH\x01R\x0cotherKeyCode\x88\x01\x01B\n\n\x08_unicodeB\x11\n\x0f_ot\
-    her_key_code*!\n\nActionType\x12\x13\n\x0fACTION_TYPE_KEY\x10\0b\x06prot\
-    o3\
+    \x18\x02\x20\x03(\x0b2\x12.key_config.ActionR\x07actions\"/\n\x0cSetLong\
+    Press\x12\x1f\n\x0bduration_ms\x18\x01\x20\x01(\rR\ndurationMs\"{\n\x06A\
+    ction\x12*\n\x04type\x18\x01\x20\x01(\x0e2\x16.key_config.ActionTypeR\
+    \x04type\x126\n\nkey_action\x18\x02\x20\x01(\x0b2\x15.key_config.KeyActi\
+    onH\0R\tkeyActionB\r\n\x0baction_data\"\x8c\x01\n\tKeyAction\x12\x16\n\
+    \x03key\x18\x01\x20\x01(\x0e2\x04.KeyR\x03key\x12\x1d\n\x07unicode\x18\
+    \x02\x20\x01(\rH\0R\x07unicode\x88\x01\x01\x12)\n\x0eother_key_code\x18\
+    \x03\x20\x01(\rH\x01R\x0cotherKeyCode\x88\x01\x01B\n\n\x08_unicodeB\x11\
+    \n\x0f_other_key_code*!\n\nActionType\x12\x13\n\x0fACTION_TYPE_KEY\x10\0\
+    b\x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
@@ -641,8 +764,9 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
             let mut deps = ::std::vec::Vec::with_capacity(2);
             deps.push(super::keys::file_descriptor().clone());
             deps.push(super::inputs::file_descriptor().clone());
-            let mut messages = ::std::vec::Vec::with_capacity(3);
+            let mut messages = ::std::vec::Vec::with_capacity(4);
             messages.push(KeyConfig::generated_message_descriptor_data());
+            messages.push(SetLongPress::generated_message_descriptor_data());
             messages.push(Action::generated_message_descriptor_data());
             messages.push(KeyAction::generated_message_descriptor_data());
             let mut enums = ::std::vec::Vec::with_capacity(1);
@@ -7,4 +7,6 @@ pub mod display_zones;
 pub mod inputs;
 pub mod key_config;
 pub mod keys;
+pub mod server_message;
 pub mod top_level;
+pub mod upload_progress;
@@ -0,0 +1,271 @@
+// This file is generated by rust-protobuf 3.7.2. Do not edit
+// .proto file is parsed by protoc 31.1
+// @generated
+
+// https://github.com/rust-lang/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![allow(unused_attributes)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unused_results)]
+#![allow(unused_mut)]
+
+//! Generated file from `server_message.proto`
+
+/// Generated files are compatible only with the same version
+/// of protobuf runtime.
+const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_7_2;
+
+// @@protoc_insertion_point(message:ServerMessage)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct ServerMessage {
+    // message oneof groups
+    pub message: ::std::option::Option<server_message::Message>,
+    // special fields
+    // @@protoc_insertion_point(special_field:ServerMessage.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a ServerMessage {
+    fn default() -> &'a ServerMessage {
+        <ServerMessage as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ServerMessage {
+    pub fn new() -> ServerMessage {
+        ::std::default::Default::default()
+    }
+
+    // .upload_progress.UploadProgress upload_progress = 1;
+
+    pub fn upload_progress(&self) -> &super::upload_progress::UploadProgress {
+        match self.message {
+            ::std::option::Option::Some(server_message::Message::UploadProgress(ref v)) => v,
+            _ => <super::upload_progress::UploadProgress as ::protobuf::Message>::default_instance(),
+        }
+    }
+
+    pub fn clear_upload_progress(&mut self) {
+        self.message = ::std::option::Option::None;
+    }
+
+    pub fn has_upload_progress(&self) -> bool {
+        match self.message {
+            ::std::option::Option::Some(server_message::Message::UploadProgress(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_upload_progress(&mut self, v: super::upload_progress::UploadProgress) {
+        self.message = ::std::option::Option::Some(server_message::Message::UploadProgress(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_upload_progress(&mut self) -> &mut super::upload_progress::UploadProgress {
+        if let ::std::option::Option::Some(server_message::Message::UploadProgress(_)) = self.message {
+        } else {
+            self.message = ::std::option::Option::Some(server_message::Message::UploadProgress(super::upload_progress::UploadProgress::new()));
+        }
+        match self.message {
+            ::std::option::Option::Some(server_message::Message::UploadProgress(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_upload_progress(&mut self) -> super::upload_progress::UploadProgress {
+        if self.has_upload_progress() {
+            match self.message.take() {
+                ::std::option::Option::Some(server_message::Message::UploadProgress(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            super::upload_progress::UploadProgress::new()
+        }
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(1);
+        fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, super::upload_progress::UploadProgress>(
+            "upload_progress",
+            ServerMessage::has_upload_progress,
+            ServerMessage::upload_progress,
+            ServerMessage::mut_upload_progress,
+            ServerMessage::set_upload_progress,
+        ));
+        oneofs.push(server_message::Message::generated_oneof_descriptor_data());
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ServerMessage>(
+            "ServerMessage",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for ServerMessage {
+    const NAME: &'static str = "ServerMessage";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.message = ::std::option::Option::Some(server_message::Message::UploadProgress(is.read_message()?));
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if let ::std::option::Option::Some(ref v) = self.message {
+            match v {
+                &server_message::Message::UploadProgress(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                },
+            };
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if let ::std::option::Option::Some(ref v) = self.message {
+            match v {
+                &server_message::Message::UploadProgress(ref v) => {
+                    ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+                },
+            };
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> ServerMessage {
+        ServerMessage::new()
+    }
+
+    fn clear(&mut self) {
+        self.message = ::std::option::Option::None;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static ServerMessage {
+        static instance: ServerMessage = ServerMessage {
+            message: ::std::option::Option::None,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for ServerMessage {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("ServerMessage").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for ServerMessage {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ServerMessage {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+/// Nested message and enums of message `ServerMessage`
+pub mod server_message {
+
+    #[derive(Clone,PartialEq,Debug)]
+    #[non_exhaustive]
+    // @@protoc_insertion_point(oneof:ServerMessage.message)
+    pub enum Message {
+        // @@protoc_insertion_point(oneof_field:ServerMessage.upload_progress)
+        UploadProgress(super::super::upload_progress::UploadProgress),
+    }
+
+    impl ::protobuf::Oneof for Message {
+    }
+
+    impl ::protobuf::OneofFull for Message {
+        fn descriptor() -> ::protobuf::reflect::OneofDescriptor {
+            static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::OneofDescriptor> = ::protobuf::rt::Lazy::new();
+            descriptor.get(|| <super::ServerMessage as ::protobuf::MessageFull>::descriptor().oneof_by_name("message").unwrap()).clone()
+        }
+    }
+
+    impl Message {
+        pub(in super) fn generated_oneof_descriptor_data() -> ::protobuf::reflect::GeneratedOneofDescriptorData {
+            ::protobuf::reflect::GeneratedOneofDescriptorData::new::<Message>("message")
+        }
+    }
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\x14server_message.proto\x1a\x1cevents/upload_progress.proto\"f\n\rSer\
+    verMessage\x12J\n\x0fupload_progress\x18\x01\x20\x01(\x0b2\x1f.upload_pr\
+    ogress.UploadProgressH\0R\x0euploadProgressB\t\n\x07messageb\x06proto3\
+";
+
+/// `FileDescriptorProto` object which was a source for this generated file
+fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    static file_descriptor_proto_lazy: ::protobuf::rt::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::Lazy::new();
+    file_descriptor_proto_lazy.get(|| {
+        ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
+    })
+}
+
+/// `FileDescriptor` object which allows dynamic access to files
+pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
+    static generated_file_descriptor_lazy: ::protobuf::rt::Lazy<::protobuf::reflect::GeneratedFileDescriptor> = ::protobuf::rt::Lazy::new();
+    static file_descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::FileDescriptor> = ::protobuf::rt::Lazy::new();
+    file_descriptor.get(|| {
+        let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
+            let mut deps = ::std::vec::Vec::with_capacity(1);
+            deps.push(super::upload_progress::file_descriptor().clone());
+            let mut messages = ::std::vec::Vec::with_capacity(1);
+            messages.push(ServerMessage::generated_message_descriptor_data());
+            let mut enums = ::std::vec::Vec::with_capacity(0);
+            ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
+                file_descriptor_proto(),
+                deps,
+                messages,
+                enums,
+            )
+        });
+        ::protobuf::reflect::FileDescriptor::new_generated_2(generated_file_descriptor)
+    })
+}
@@ -27,6 +27,9 @@ const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_7_2;
 // @@protoc_insertion_point(message:TopLevel)
 #[derive(PartialEq,Clone,Default,Debug)]
 pub struct TopLevel {
+    // message fields
+    // @@protoc_insertion_point(field:TopLevel.device_serial)
+    pub device_serial: ::std::string::String,
     // message oneof groups
     pub command: ::std::option::Option<top_level::Command>,
     // special fields
@@ -339,8 +342,155 @@ impl TopLevel {
         }
     }
 
+    // .brightness.FadeBrightness fade_brightness_command = 8;
+
+    pub fn fade_brightness_command(&self) -> &super::brightness::FadeBrightness {
+        match self.command {
+            ::std::option::Option::Some(top_level::Command::FadeBrightnessCommand(ref v)) => v,
+            _ => <super::brightness::FadeBrightness as ::protobuf::Message>::default_instance(),
+        }
+    }
+
+    pub fn clear_fade_brightness_command(&mut self) {
+        self.command = ::std::option::Option::None;
+    }
+
+    pub fn has_fade_brightness_command(&self) -> bool {
+        match self.command {
+            ::std::option::Option::Some(top_level::Command::FadeBrightnessCommand(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fade_brightness_command(&mut self, v: super::brightness::FadeBrightness) {
+        self.command = ::std::option::Option::Some(top_level::Command::FadeBrightnessCommand(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_fade_brightness_command(&mut self) -> &mut super::brightness::FadeBrightness {
+        if let ::std::option::Option::Some(top_level::Command::FadeBrightnessCommand(_)) = self.command {
+        } else {
+            self.command = ::std::option::Option::Some(top_level::Command::FadeBrightnessCommand(super::brightness::FadeBrightness::new()));
+        }
+        match self.command {
+            ::std::option::Option::Some(top_level::Command::FadeBrightnessCommand(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_fade_brightness_command(&mut self) -> super::brightness::FadeBrightness {
+        if self.has_fade_brightness_command() {
+            match self.command.take() {
+                ::std::option::Option::Some(top_level::Command::FadeBrightnessCommand(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            super::brightness::FadeBrightness::new()
+        }
+    }
+
+    // .boot_logo.CancelUpload cancel_upload_command = 9;
+
+    pub fn cancel_upload_command(&self) -> &super::boot_logo::CancelUpload {
+        match self.command {
+            ::std::option::Option::Some(top_level::Command::CancelUploadCommand(ref v)) => v,
+            _ => <super::boot_logo::CancelUpload as ::protobuf::Message>::default_instance(),
+        }
+    }
+
+    pub fn clear_cancel_upload_command(&mut self) {
+        self.command = ::std::option::Option::None;
+    }
+
+    pub fn has_cancel_upload_command(&self) -> bool {
+        match self.command {
+            ::std::option::Option::Some(top_level::Command::CancelUploadCommand(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cancel_upload_command(&mut self, v: super::boot_logo::CancelUpload) {
+        self.command = ::std::option::Option::Some(top_level::Command::CancelUploadCommand(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_cancel_upload_command(&mut self) -> &mut super::boot_logo::CancelUpload {
+        if let ::std::option::Option::Some(top_level::Command::CancelUploadCommand(_)) = self.command {
+        } else {
+            self.command = ::std::option::Option::Some(top_level::Command::CancelUploadCommand(super::boot_logo::CancelUpload::new()));
+        }
+        match self.command {
+            ::std::option::Option::Some(top_level::Command::CancelUploadCommand(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_cancel_upload_command(&mut self) -> super::boot_logo::CancelUpload {
+        if self.has_cancel_upload_command() {
+            match self.command.take() {
+                ::std::option::Option::Some(top_level::Command::CancelUploadCommand(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            super::boot_logo::CancelUpload::new()
+        }
+    }
+
+    // .key_config.SetLongPress set_long_press_command = 10;
+
+    pub fn set_long_press_command(&self) -> &super::key_config::SetLongPress {
+        match self.command {
+            ::std::option::Option::Some(top_level::Command::SetLongPressCommand(ref v)) => v,
+            _ => <super::key_config::SetLongPress as ::protobuf::Message>::default_instance(),
+        }
+    }
+
+    pub fn clear_set_long_press_command(&mut self) {
+        self.command = ::std::option::Option::None;
+    }
+
+    pub fn has_set_long_press_command(&self) -> bool {
+        match self.command {
+            ::std::option::Option::Some(top_level::Command::SetLongPressCommand(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_set_long_press_command(&mut self, v: super::key_config::SetLongPress) {
+        self.command = ::std::option::Option::Some(top_level::Command::SetLongPressCommand(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_set_long_press_command(&mut self) -> &mut super::key_config::SetLongPress {
+        if let ::std::option::Option::Some(top_level::Command::SetLongPressCommand(_)) = self.command {
+        } else {
+            self.command = ::std::option::Option::Some(top_level::Command::SetLongPressCommand(super::key_config::SetLongPress::new()));
+        }
+        match self.command {
+            ::std::option::Option::Some(top_level::Command::SetLongPressCommand(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_set_long_press_command(&mut self) -> super::key_config::SetLongPress {
+        if self.has_set_long_press_command() {
+            match self.command.take() {
+                ::std::option::Option::Some(top_level::Command::SetLongPressCommand(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            super::key_config::SetLongPress::new()
+        }
+    }
+
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(6);
+        let mut fields = ::std::vec::Vec::with_capacity(10);
         let mut oneofs = ::std::vec::Vec::with_capacity(1);
         fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, super::key_config::KeyConfig>(
             "key_config_command",
@@ -384,6 +534,32 @@ impl TopLevel {
             TopLevel::mut_set_boot_logo_command,
             TopLevel::set_set_boot_logo_command,
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, super::brightness::FadeBrightness>(
+            "fade_brightness_command",
+            TopLevel::has_fade_brightness_command,
+            TopLevel::fade_brightness_command,
+            TopLevel::mut_fade_brightness_command,
+            TopLevel::set_fade_brightness_command,
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, super::boot_logo::CancelUpload>(
+            "cancel_upload_command",
+            TopLevel::has_cancel_upload_command,
+            TopLevel::cancel_upload_command,
+            TopLevel::mut_cancel_upload_command,
+            TopLevel::set_cancel_upload_command,
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, super::key_config::SetLongPress>(
+            "set_long_press_command",
+            TopLevel::has_set_long_press_command,
+            TopLevel::set_long_press_command,
+            TopLevel::mut_set_long_press_command,
+            TopLevel::set_set_long_press_command,
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "device_serial",
+            |m: &TopLevel| { &m.device_serial },
+            |m: &mut TopLevel| { &mut m.device_serial },
+        ));
         oneofs.push(top_level::Command::generated_oneof_descriptor_data());
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<TopLevel>(
             "TopLevel",
@@ -421,6 +597,18 @@ impl ::protobuf::Message for TopLevel {
                 50 => {
                     self.command = ::std::option::Option::Some(top_level::Command::SetBootLogoCommand(is.read_message()?));
                 },
+                66 => {
+                    self.command = ::std::option::Option::Some(top_level::Command::FadeBrightnessCommand(is.read_message()?));
+                },
+                74 => {
+                    self.command = ::std::option::Option::Some(top_level::Command::CancelUploadCommand(is.read_message()?));
+                },
+                82 => {
+                    self.command = ::std::option::Option::Some(top_level::Command::SetLongPressCommand(is.read_message()?));
+                },
+                58 => {
+                    self.device_serial = is.read_string()?;
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -433,6 +621,9 @@ impl ::protobuf::Message for TopLevel {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u64 {
         let mut my_size = 0;
+        if !self.device_serial.is_empty() {
+            my_size += ::protobuf::rt::string_size(7, &self.device_serial);
+        }
         if let ::std::option::Option::Some(ref v) = self.command {
             match v {
                 &top_level::Command::KeyConfigCommand(ref v) => {
@@ -459,6 +650,18 @@ impl ::protobuf::Message for TopLevel {
                     let len = v.compute_size();
                     my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
                 },
+                &top_level::Command::FadeBrightnessCommand(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                },
+                &top_level::Command::CancelUploadCommand(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                },
+                &top_level::Command::SetLongPressCommand(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                },
             };
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
@@ -467,6 +670,9 @@ impl ::protobuf::Message for TopLevel {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.device_serial.is_empty() {
+            os.write_string(7, &self.device_serial)?;
+        }
         if let ::std::option::Option::Some(ref v) = self.command {
             match v {
                 &top_level::Command::KeyConfigCommand(ref v) => {
@@ -487,6 +693,15 @@ impl ::protobuf::Message for TopLevel {
                 &top_level::Command::SetBootLogoCommand(ref v) => {
                     ::protobuf::rt::write_message_field_with_cached_size(6, v, os)?;
                 },
+                &top_level::Command::FadeBrightnessCommand(ref v) => {
+                    ::protobuf::rt::write_message_field_with_cached_size(8, v, os)?;
+                },
+                &top_level::Command::CancelUploadCommand(ref v) => {
+                    ::protobuf::rt::write_message_field_with_cached_size(9, v, os)?;
+                },
+                &top_level::Command::SetLongPressCommand(ref v) => {
+                    ::protobuf::rt::write_message_field_with_cached_size(10, v, os)?;
+                },
             };
         }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
@@ -512,11 +727,16 @@ impl ::protobuf::Message for TopLevel {
         self.command = ::std::option::Option::None;
         self.command = ::std::option::Option::None;
         self.command = ::std::option::Option::None;
+        self.command = ::std::option::Option::None;
+        self.command = ::std::option::Option::None;
+        self.command = ::std::option::Option::None;
+        self.device_serial.clear();
         self.special_fields.clear();
     }
 
     fn default_instance() -> &'static TopLevel {
         static instance: TopLevel = TopLevel {
+            device_serial: ::std::string::String::new(),
             command: ::std::option::Option::None,
             special_fields: ::protobuf::SpecialFields::new(),
         };
@@ -560,6 +780,12 @@ pub mod top_level {
         SetBrightnessCommand(super::super::brightness::SetBrightness),
         // @@protoc_insertion_point(oneof_field:TopLevel.set_boot_logo_command)
         SetBootLogoCommand(super::super::boot_logo::SetBootLogo),
+        // @@protoc_insertion_point(oneof_field:TopLevel.fade_brightness_command)
+        FadeBrightnessCommand(super::super::brightness::FadeBrightness),
+        // @@protoc_insertion_point(oneof_field:TopLevel.cancel_upload_command)
+        CancelUploadCommand(super::super::boot_logo::CancelUpload),
+        // @@protoc_insertion_point(oneof_field:TopLevel.set_long_press_command)
+        SetLongPressCommand(super::super::key_config::SetLongPress),
     }
 
     impl ::protobuf::Oneof for Command {
@@ -582,7 +808,7 @@ pub mod top_level {
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x0ftop_level.proto\x1a\x18commands/boot_logo.proto\x1a!commands/displ\
     ay_zone_image.proto\x1a\x19commands/brightness.proto\x1a\x19commands/key\
-    _config.proto\"\xe3\x04\n\x08TopLevel\x12E\n\x12key_config_command\x18\
+    _config.proto\"\xfe\x06\n\x08TopLevel\x12E\n\x12key_config_command\x18\
     \x01\x20\x01(\x0b2\x15.key_config.KeyConfigH\0R\x10keyConfigCommand\x12m\
     \n\x1eset_display_zone_image_command\x18\x02\x20\x01(\x0b2'.display_zone\
     _image.SetDisplayZoneImageH\0R\x1asetDisplayZoneImageCommand\x12s\n\x20c\
@@ -593,7 +819,12 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     mand\x12Q\n\x16set_brightness_command\x18\x05\x20\x01(\x0b2\x19.brightne\
     ss.SetBrightnessH\0R\x14setBrightnessCommand\x12K\n\x15set_boot_logo_com\
     mand\x18\x06\x20\x01(\x0b2\x16.boot_logo.SetBootLogoH\0R\x12setBootLogoC\
-    ommandB\t\n\x07commandb\x06proto3\
+    ommand\x12T\n\x17fade_brightness_command\x18\x08\x20\x01(\x0b2\x1a.brigh\
+    tness.FadeBrightnessH\0R\x15fadeBrightnessCommand\x12M\n\x15cancel_uploa\
+    d_command\x18\t\x20\x01(\x0b2\x17.boot_logo.CancelUploadH\0R\x13cancelUp\
+    loadCommand\x12O\n\x16set_long_press_command\x18\n\x20\x01(\x0b2\x18.key\
+    _config.SetLongPressH\0R\x13setLongPressCommand\x12#\n\rdevice_serial\
+    \x18\x07\x20\x01(\tR\x0cdeviceSerialB\t\n\x07commandb\x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
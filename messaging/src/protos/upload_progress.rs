@@ -0,0 +1,219 @@
+// This file is generated by rust-protobuf 3.7.2. Do not edit
+// .proto file is parsed by protoc 31.1
+// @generated
+
+// https://github.com/rust-lang/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![allow(unused_attributes)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unused_results)]
+#![allow(unused_mut)]
+
+//! Generated file from `events/upload_progress.proto`
+
+/// Generated files are compatible only with the same version
+/// of protobuf runtime.
+const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_7_2;
+
+// @@protoc_insertion_point(message:upload_progress.UploadProgress)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct UploadProgress {
+    // message fields
+    // @@protoc_insertion_point(field:upload_progress.UploadProgress.device_serial)
+    pub device_serial: ::std::string::String,
+    // @@protoc_insertion_point(field:upload_progress.UploadProgress.bytes_sent)
+    pub bytes_sent: u64,
+    // @@protoc_insertion_point(field:upload_progress.UploadProgress.total_bytes)
+    pub total_bytes: u64,
+    // special fields
+    // @@protoc_insertion_point(special_field:upload_progress.UploadProgress.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a UploadProgress {
+    fn default() -> &'a UploadProgress {
+        <UploadProgress as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl UploadProgress {
+    pub fn new() -> UploadProgress {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "device_serial",
+            |m: &UploadProgress| { &m.device_serial },
+            |m: &mut UploadProgress| { &mut m.device_serial },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "bytes_sent",
+            |m: &UploadProgress| { &m.bytes_sent },
+            |m: &mut UploadProgress| { &mut m.bytes_sent },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "total_bytes",
+            |m: &UploadProgress| { &m.total_bytes },
+            |m: &mut UploadProgress| { &mut m.total_bytes },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<UploadProgress>(
+            "UploadProgress",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for UploadProgress {
+    const NAME: &'static str = "UploadProgress";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.device_serial = is.read_string()?;
+                },
+                16 => {
+                    self.bytes_sent = is.read_uint64()?;
+                },
+                24 => {
+                    self.total_bytes = is.read_uint64()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.device_serial.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.device_serial);
+        }
+        if self.bytes_sent != 0 {
+            my_size += ::protobuf::rt::uint64_size(2, self.bytes_sent);
+        }
+        if self.total_bytes != 0 {
+            my_size += ::protobuf::rt::uint64_size(3, self.total_bytes);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.device_serial.is_empty() {
+            os.write_string(1, &self.device_serial)?;
+        }
+        if self.bytes_sent != 0 {
+            os.write_uint64(2, self.bytes_sent)?;
+        }
+        if self.total_bytes != 0 {
+            os.write_uint64(3, self.total_bytes)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> UploadProgress {
+        UploadProgress::new()
+    }
+
+    fn clear(&mut self) {
+        self.device_serial.clear();
+        self.bytes_sent = 0;
+        self.total_bytes = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static UploadProgress {
+        static instance: UploadProgress = UploadProgress {
+            device_serial: ::std::string::String::new(),
+            bytes_sent: 0,
+            total_bytes: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for UploadProgress {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("UploadProgress").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for UploadProgress {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for UploadProgress {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\x1cevents/upload_progress.proto\x12\x0fupload_progress\"u\n\x0eUpload\
+    Progress\x12#\n\rdevice_serial\x18\x01\x20\x01(\tR\x0cdeviceSerial\x12\
+    \x1d\n\nbytes_sent\x18\x02\x20\x01(\x04R\tbytesSent\x12\x1f\n\x0btotal_b\
+    ytes\x18\x03\x20\x01(\x04R\ntotalBytesb\x06proto3\
+";
+
+/// `FileDescriptorProto` object which was a source for this generated file
+fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    static file_descriptor_proto_lazy: ::protobuf::rt::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::Lazy::new();
+    file_descriptor_proto_lazy.get(|| {
+        ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
+    })
+}
+
+/// `FileDescriptor` object which allows dynamic access to files
+pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
+    static generated_file_descriptor_lazy: ::protobuf::rt::Lazy<::protobuf::reflect::GeneratedFileDescriptor> = ::protobuf::rt::Lazy::new();
+    static file_descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::FileDescriptor> = ::protobuf::rt::Lazy::new();
+    file_descriptor.get(|| {
+        let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
+            let mut deps = ::std::vec::Vec::with_capacity(0);
+            let mut messages = ::std::vec::Vec::with_capacity(1);
+            messages.push(UploadProgress::generated_message_descriptor_data());
+            let mut enums = ::std::vec::Vec::with_capacity(0);
+            ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
+                file_descriptor_proto(),
+                deps,
+                messages,
+                enums,
+            )
+        });
+        ::protobuf::reflect::FileDescriptor::new_generated_2(generated_file_descriptor)
+    })
+}
@@ -14,9 +14,23 @@ pub struct Client {
 
 pub struct Server {
     unix_listener: UnixListener,
-    unix_streams: Vec<UnixStream>,
+    unix_streams: Vec<Connection>,
+    next_client_id: u64,
 }
 
+/// A client accepted by the `Server`, its stream never blocks
+struct Connection {
+    client_id: ClientId,
+    stream: UnixStream,
+    /// The end of a message the client was not ready for yet, it is sent before anything else
+    unsent: Vec<u8>,
+}
+
+/// Identifies a connection accepted by the `Server`, which counts the ids up from 0 and never
+/// reuses them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClientId(pub u64);
+
 pub trait MessageSender {
     fn send_message(&mut self, msg: &[u8]) -> Result<(), Error>;
 }
@@ -79,9 +93,20 @@ impl Server {
         Ok(Self {
             unix_listener,
             unix_streams: vec![],
+            next_client_id: 0,
         })
     }
 
+    fn add_stream(&mut self, stream: UnixStream) {
+        let client_id = ClientId(self.next_client_id);
+        self.next_client_id += 1;
+        self.unix_streams.push(Connection {
+            client_id,
+            stream,
+            unsent: Vec::new(),
+        });
+    }
+
     /// _Blocking_ call to accept an incoming connection. Will add the connection
     /// to the list of connections if it is added successfully, otherwise the resulting
     /// error will need to be handled accordingly
@@ -90,7 +115,7 @@ impl Server {
         match self.unix_listener.accept() {
             Ok((stream, _addr)) => {
                 stream.set_nonblocking(true)?;
-                self.unix_streams.push(stream);
+                self.add_stream(stream);
             }
             Err(e) => return Err(e),
         }
@@ -106,7 +131,7 @@ impl Server {
         match self.unix_listener.accept() {
             Ok((stream, _addr)) => {
                 stream.set_nonblocking(true)?;
-                self.unix_streams.push(stream);
+                self.add_stream(stream);
             }
             Err(e) if e.kind() == ErrorKind::WouldBlock => {
                 info!("No connections to accept from");
@@ -118,40 +143,73 @@ impl Server {
         Ok(())
     }
 
-    /// Removes any invalid connections from the list of `unix_streams`
+    /// Removes any invalid connections from the list of `unix_streams`, and sends what is left
+    /// of the messages the others were not ready for
     pub fn cleanup_disconnected(&mut self) {
-        self.unix_streams
-            .retain(|stream| stream.peer_addr().is_ok());
+        self.unix_streams.retain_mut(|connection| {
+            connection.stream.peer_addr().is_ok()
+                && !connection
+                    .write_unsent()
+                    .is_err_and(|e| e.kind() != ErrorKind::WouldBlock)
+        });
     }
 
     pub fn connected_clients(&self) -> usize {
         self.unix_streams.len()
     }
+
+    /// Same as `read_message`, but also tells which client sent the message
+    /// so that it can be answered with `send_message_to`
+    pub fn read_message_from(&mut self) -> Result<(ClientId, Vec<u8>), Error> {
+        for connection in &mut self.unix_streams {
+            match try_read_message(&mut connection.stream) {
+                Ok(Some(msg)) => return Ok((connection.client_id, msg)),
+                Ok(None) => continue, // No data available on this stream
+                Err(_) => continue,   // Error on this stream, try next
+            }
+        }
+
+        Err(Error::new(ErrorKind::WouldBlock, "No messages available"))
+    }
+
+    /// Non-blocking message write to a single client, the connection is dropped if writing fails.
+    ///
+    /// A client that is not reading fast enough is not dropped: the message fails with
+    /// `WouldBlock` and is not sent at all while an earlier one is still waiting to be finished.
+    ///
+    /// * `client_id` - the client to send to, as returned by `read_message_from`
+    /// * `msg` - the byte array containing the message to send
+    pub fn send_message_to(&mut self, client_id: ClientId, msg: &[u8]) -> Result<(), Error> {
+        let index = self
+            .unix_streams
+            .iter()
+            .position(|connection| connection.client_id == client_id)
+            .ok_or_else(|| Error::new(ErrorKind::NotConnected, "Client is not connected"))?;
+
+        let write_result = self.unix_streams[index].write_message(msg);
+        if write_result
+            .as_ref()
+            .is_err_and(|e| e.kind() != ErrorKind::WouldBlock)
+        {
+            self.unix_streams.remove(index);
+        }
+        write_result
+    }
 }
 
 impl MessageSender for Server {
-    /// Blocking message write, sends a byte-encoded message to each of the clients
+    /// Non-blocking message write, sends a byte-encoded message to each of the clients
     /// The transformation of the message should be handled by the called
     ///
+    /// Clients that are not ready for it miss the message, see `send_message_to`.
+    ///
     /// * `msg` - the byte array containing the message to send to all clients
     fn send_message(&mut self, msg: &[u8]) -> Result<(), Error> {
-        let msg_bytes = msg;
-        let len = msg_bytes.len() as u64;
-        let mut failed_streams = Vec::new();
-
-        for (index, stream) in self.unix_streams.iter_mut().enumerate() {
-            let write_result = stream
-                .write_all(&len.to_le_bytes())
-                .and_then(|_| stream.write_all(msg_bytes));
-
-            if write_result.is_err() {
-                failed_streams.push(index);
-            }
-        }
-
-        for &index in failed_streams.iter().rev() {
-            self.unix_streams.remove(index);
-        }
+        self.unix_streams.retain_mut(|connection| {
+            !connection
+                .write_message(msg)
+                .is_err_and(|e| e.kind() != ErrorKind::WouldBlock)
+        });
 
         Ok(())
     }
@@ -161,15 +219,37 @@ impl MessageReceiver for Server {
     /// Non-blocking read for all connected clients. Will pass if there is
     /// currently nothing to read, otherwise it will return the first received message
     fn read_message(&mut self) -> Result<Vec<u8>, Error> {
-        for stream in &mut self.unix_streams {
-            match try_read_message(stream) {
-                Ok(Some(msg)) => return Ok(msg),
-                Ok(None) => continue, // No data available on this stream
-                Err(_) => continue,   // Error on this stream, try next
+        self.read_message_from().map(|(_, msg)| msg)
+    }
+}
+
+impl Connection {
+    /// Writes as much of the message as the client is ready for, the rest is kept in `unsent`.
+    /// Fails with `WouldBlock` without writing anything while an earlier message is unfinished,
+    /// so the length headers always line up with the messages.
+    fn write_message(&mut self, msg: &[u8]) -> Result<(), Error> {
+        self.write_unsent()?;
+
+        let len = msg.len() as u64;
+        let mut frame = Vec::with_capacity(8 + msg.len());
+        frame.extend_from_slice(&len.to_le_bytes());
+        frame.extend_from_slice(msg);
+
+        let written = self.stream.write(&frame)?;
+        self.unsent = frame.split_off(written);
+        Ok(())
+    }
+
+    fn write_unsent(&mut self) -> Result<(), Error> {
+        while !self.unsent.is_empty() {
+            match self.stream.write(&self.unsent)? {
+                0 => return Err(Error::from(ErrorKind::WriteZero)),
+                written => {
+                    self.unsent.drain(..written);
+                }
             }
         }
-
-        Err(Error::new(ErrorKind::WouldBlock, "No messages available"))
+        Ok(())
     }
 }
 
@@ -216,6 +296,8 @@ mod tests {
     fn test_all_scenarios() {
         test_client_server_communication();
         test_multiple_clients();
+        test_replying_to_a_single_client();
+        test_clients_that_do_not_read_are_kept();
     }
 
     fn test_client_server_communication() {
@@ -281,4 +363,69 @@ mod tests {
             handle.join().expect("Client thread panicked");
         }
     }
+
+    fn test_replying_to_a_single_client() {
+        let mut server = Server::new().expect("Failed to create server");
+        let mut asking = Client::new().expect("Failed to create client");
+        let mut other = Client::new().expect("Failed to create client");
+        server
+            .accept_connection()
+            .expect("Failed to accept connections");
+        server
+            .accept_connection()
+            .expect("Failed to accept connections");
+
+        asking
+            .send_message(b"Question")
+            .expect("Failed to send message");
+        thread::sleep(Duration::from_millis(50));
+
+        let (client_id, _) = server.read_message_from().expect("Failed to read message");
+        server
+            .send_message_to(client_id, b"Answer")
+            .expect("Failed to send message");
+        server
+            .send_message(b"Everyone")
+            .expect("Failed to send message");
+
+        assert_eq!(asking.read_message().unwrap(), b"Answer");
+        assert_eq!(asking.read_message().unwrap(), b"Everyone");
+        assert_eq!(other.read_message().unwrap(), b"Everyone");
+    }
+
+    fn test_clients_that_do_not_read_are_kept() {
+        let mut server = Server::new().expect("Failed to create server");
+        let mut client = Client::new().expect("Failed to create client");
+        server
+            .accept_connection()
+            .expect("Failed to accept connections");
+        let client_id = ClientId(0);
+
+        // Sent until the socket buffer is full, the last message may only be partly written
+        let message = [7u8; 1000];
+        let mut sent = 0;
+        while server.send_message_to(client_id, &message).is_ok() {
+            sent += 1;
+            assert!(sent < 100_000, "the socket never filled up");
+        }
+        assert_eq!(
+            server
+                .send_message_to(client_id, &message)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::WouldBlock
+        );
+        assert_eq!(server.connected_clients(), 1);
+
+        let reader = thread::spawn(move || {
+            for _ in 0..sent {
+                assert_eq!(client.read_message().unwrap(), message);
+            }
+        });
+        while !reader.is_finished() {
+            server.cleanup_disconnected();
+            thread::sleep(Duration::from_millis(1));
+        }
+        reader.join().expect("Client thread panicked");
+    }
 }